@@ -0,0 +1,182 @@
+//! Programmatic input for the Claude pane: prompt library insertion and the
+//! "Send to Claude" context actions (Ctrl+F4).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::prompts;
+use crate::types::PaneId;
+use crate::ui::context_menu::ClaudeContext;
+use crate::ui::dialog::{DialogAction, DialogType};
+
+use super::App;
+
+impl App {
+    /// Paste `text` into the Claude PTY (bracketed when Claude enabled
+    /// DECSET 2004, so embedded newlines don't submit early) and focus it.
+    pub(super) fn send_to_claude(&mut self, text: &str) {
+        if let Some(pty) = self.terminals.get_mut(&PaneId::Claude) {
+            let _ = pty.write_paste(text);
+        }
+        self.active_pane = PaneId::Claude;
+    }
+
+    /// Like [`Self::send_to_claude`], but asks first when `text` exceeds
+    /// `claude.context_warn_kb`. With `clear_marks` the marked files are
+    /// unmarked once the text is actually sent (not when it is cancelled).
+    fn send_to_claude_guarded(&mut self, text: String, clear_marks: bool) {
+        let limit = self.config.claude.context_warn_kb * 1024;
+        if limit == 0 || text.len() <= limit {
+            self.send_to_claude(&text);
+            if clear_marks {
+                self.file_browser.marked.clear();
+            }
+            return;
+        }
+        self.dialog.dialog_type = DialogType::Confirm {
+            title: "Large Claude Input".to_string(),
+            message: format!(
+                "Send {} KB ({} lines) to Claude?",
+                text.len().div_ceil(1024),
+                text.lines().count()
+            ),
+            action: DialogAction::SendToClaude { text, clear_marks },
+        };
+    }
+
+    /// Expand a prompt library template from the current state and send it to Claude
+    pub(super) fn send_prompt_to_claude(&mut self, template: &str) {
+        let root = self.file_browser.root_dir.clone();
+        let repo_root = crate::git::find_repo_root(&root);
+
+        let mut values = HashMap::new();
+        for name in prompts::used_placeholders(template) {
+            let value = match name {
+                "file" => self.current_file().map(|path| relative_to(&path, &root)),
+                "selection" => self.selected_lines().map(|(_, lines)| lines.join("\n")),
+                "git_diff" => repo_root.as_deref().and_then(crate::git::get_diff),
+                "branch" => repo_root
+                    .as_deref()
+                    .and_then(crate::git::get_current_branch),
+                _ => None,
+            };
+            if let Some(value) = value {
+                values.insert(name, value);
+            }
+        }
+
+        let text = prompts::expand(template, &values);
+        self.send_to_claude_guarded(text, false);
+    }
+
+    /// Collect `context` from the workbench state and paste it into Claude.
+    /// Shows a flash message when there is nothing to send.
+    pub(super) fn send_context_to_claude(&mut self, context: ClaudeContext) {
+        match self.collect_context(context) {
+            Ok(text) => {
+                let clear_marks = context == ClaudeContext::MarkedFiles;
+                self.send_to_claude_guarded(text, clear_marks)
+            }
+            Err(message) => self.set_clipboard_error_flash(message),
+        }
+    }
+
+    fn collect_context(&self, context: ClaudeContext) -> Result<String, String> {
+        let root = self.file_browser.root_dir.clone();
+        match context {
+            ClaudeContext::CurrentFile => self
+                .current_file()
+                .map(|path| format!("@{} ", relative_to(&path, &root)))
+                .ok_or_else(|| "No file open".to_string()),
+            ClaudeContext::GitDiff => {
+                let diff = crate::git::find_repo_root(&root)
+                    .and_then(|repo| crate::git::get_diff(&repo))
+                    .ok_or_else(|| "Not a git repository".to_string())?;
+                if diff.is_empty() {
+                    return Err("No uncommitted changes".to_string());
+                }
+                Ok(format!("```diff\n{}\n```\n", diff))
+            }
+            ClaudeContext::FailingCommand => {
+                let pty = self
+                    .terminals
+                    .get(&PaneId::Terminal)
+                    .ok_or_else(|| "Terminal pane not running".to_string())?;
+                let (mut all, blocks) = pty.command_blocks();
+                while all.last().is_some_and(|l| l.trim().is_empty()) {
+                    all.pop();
+                }
+                let block = super::clipboard::last_failing_command_block(
+                    &all,
+                    &blocks,
+                    self.config.pty.copy_lines_count,
+                )
+                .ok_or_else(|| "No failing command in Terminal output".to_string())?;
+                let lines: Vec<String> = block.lines.iter().map(|l| l.replace('\r', "")).collect();
+                Ok(format!(
+                    "```{}\n{}\n```\n",
+                    block.syntax_hint.unwrap_or_default(),
+                    lines.join("\n")
+                ))
+            }
+            ClaudeContext::MarkedFiles => {
+                let files = self.file_browser.marked_files();
+                if files.is_empty() {
+                    return Err("No files marked (Space in file browser)".to_string());
+                }
+                let refs: Vec<String> = files
+                    .iter()
+                    .map(|path| format!("@{}", relative_to(path, &root)))
+                    .collect();
+                Ok(format!("{} ", refs.join(" ")))
+            }
+            ClaudeContext::SearchResults => {
+                let file = self
+                    .preview
+                    .current_file
+                    .as_ref()
+                    .filter(|_| !self.preview.search.matches.is_empty())
+                    .ok_or_else(|| "No search results in preview".to_string())?;
+                let file = relative_to(file, &root);
+                let content: Vec<&str> = self.preview.content.lines().collect();
+                let mut line_numbers: Vec<usize> = self
+                    .preview
+                    .search
+                    .matches
+                    .iter()
+                    .map(|(line, _, _)| *line)
+                    .collect();
+                line_numbers.dedup();
+                let hits: Vec<String> = line_numbers
+                    .into_iter()
+                    .filter_map(|i| {
+                        content
+                            .get(i)
+                            .map(|text| format!("{}:{}: {}", file, i + 1, text.trim_end()))
+                    })
+                    .collect();
+                Ok(format!(
+                    "Matches for \"{}\":\n```\n{}\n```\n",
+                    self.preview.search.query,
+                    hits.join("\n")
+                ))
+            }
+        }
+    }
+
+    /// File open in the preview, else the file selected in the browser
+    fn current_file(&self) -> Option<PathBuf> {
+        self.preview
+            .current_file
+            .clone()
+            .or_else(|| self.file_browser.selected_file())
+    }
+}
+
+/// `path` relative to `root` for `@path` references (absolute if outside)
+fn relative_to(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
@@ -1,20 +1,17 @@
+use std::collections::HashSet;
+
 use crate::clipboard::ClipboardOutcome;
-use crate::types::{EditorMode, PaneId};
+use crate::terminal::CommandBlock;
+use crate::types::{CopyTarget, EditorMode, PaneId};
 use crate::ui;
 
 use super::App;
 
 impl App {
-    pub(super) fn copy_selection_to_claude(&mut self) {
-        use crate::filter::{filter_lines, FilterOptions};
-
-        let Some((start, end)) = self.terminal_selection.line_range() else {
-            return;
-        };
-
-        let Some(source_pane) = self.terminal_selection.source_pane else {
-            return;
-        };
+    /// Lines of the current line selection (terminal or preview) with their source pane
+    pub(super) fn selected_lines(&self) -> Option<(PaneId, Vec<String>)> {
+        let (start, end) = self.terminal_selection.line_range()?;
+        let source_pane = self.terminal_selection.source_pane?;
 
         // Extract lines from source (terminal or preview)
         let lines = if source_pane == PaneId::Preview {
@@ -22,18 +19,41 @@ impl App {
             let content_lines: Vec<String> =
                 self.preview.content.lines().map(String::from).collect();
             if start > content_lines.len() || end > content_lines.len() {
-                return;
+                return None;
             }
             content_lines[start..=end.min(content_lines.len().saturating_sub(1))].to_vec()
-        } else if let Some(pty) = self.terminals.get(&source_pane) {
-            pty.extract_lines(start, end)
         } else {
-            return;
+            self.terminals.get(&source_pane)?.extract_lines(start, end)
         };
 
-        if lines.is_empty() {
-            return;
+        (!lines.is_empty()).then_some((source_pane, lines))
+    }
+
+    /// Prompt lines inside the terminal line selection (relative to its
+    /// first line) when the pane reports OSC 133 prompt marks
+    fn selection_prompt_lines(&self, pane: PaneId) -> Option<HashSet<usize>> {
+        let (start, end) = self.terminal_selection.line_range()?;
+        let pty = self.terminals.get(&pane)?;
+        let blocks = pty.command_block_list();
+        if blocks.is_empty() {
+            return None;
         }
+        let (top, _) = pty.view_origin();
+        let (first, last) = (top + start, top + end);
+        Some(
+            prompt_lines(&blocks)
+                .filter(|line| (first..=last).contains(line))
+                .map(|line| line - first)
+                .collect(),
+        )
+    }
+
+    pub(super) fn copy_selection_to_claude(&mut self) {
+        use crate::filter::{filter_lines, FilterOptions};
+
+        let Some((source_pane, lines)) = self.selected_lines() else {
+            return;
+        };
 
         // For preview, use syntax from file; for terminal, detect syntax
         let (formatted_lines, syntax_hint) = if source_pane == PaneId::Preview {
@@ -41,8 +61,13 @@ impl App {
             let syntax = self.preview.syntax_name.as_deref().unwrap_or("");
             (lines, Some(syntax.to_lowercase()))
         } else {
-            // Apply intelligent filtering for terminal output
-            let filtered = filter_lines(lines, &FilterOptions::default());
+            // Apply intelligent filtering for terminal output; exact prompt
+            // lines from shell integration beat the prompt patterns
+            let options = FilterOptions {
+                prompt_lines: self.selection_prompt_lines(source_pane),
+                ..FilterOptions::default()
+            };
+            let filtered = filter_lines(lines, &options);
             if filtered.lines.is_empty() {
                 return;
             }
@@ -60,41 +85,19 @@ impl App {
         let syntax = syntax_hint.as_deref().unwrap_or("");
         let formatted = format!("```{}\n{}\n```\n", syntax, formatted_lines.join("\n"));
 
-        // Send to Claude PTY
+        // Send to Claude PTY as a paste (bracketed when Claude enabled
+        // DECSET 2004, so the block's newlines don't submit)
         if let Some(claude_pty) = self.terminals.get_mut(&PaneId::Claude) {
-            let _ = claude_pty.write_input(formatted.as_bytes());
+            let _ = claude_pty.write_paste(&formatted);
         }
     }
 
     /// Copy selected lines to system clipboard (from terminal or preview)
     pub(super) fn copy_selection_to_clipboard(&mut self) {
-        let Some((start, end)) = self.terminal_selection.line_range() else {
+        let Some((_, lines)) = self.selected_lines() else {
             return;
         };
 
-        let Some(source_pane) = self.terminal_selection.source_pane else {
-            return;
-        };
-
-        // Extract lines from source (terminal or preview)
-        let lines = if source_pane == PaneId::Preview {
-            // Extract lines from preview content
-            let content_lines: Vec<String> =
-                self.preview.content.lines().map(String::from).collect();
-            if start > content_lines.len() || end > content_lines.len() {
-                return;
-            }
-            content_lines[start..=end.min(content_lines.len().saturating_sub(1))].to_vec()
-        } else if let Some(pty) = self.terminals.get(&source_pane) {
-            pty.extract_lines(start, end)
-        } else {
-            return;
-        };
-
-        if lines.is_empty() {
-            return;
-        }
-
         // Join lines and copy to system clipboard
         let text = lines.join("\n");
 
@@ -134,13 +137,14 @@ impl App {
     /// terminal pane to the system clipboard.
     ///
     /// Reads the *full* scrollback buffer (not just the visible screen) and
-    /// uses prompt-line heuristics to isolate the last command block. If no
+    /// isolates the last command block from the shell's OSC 133 prompt marks,
+    /// or with prompt-line heuristics when the shell sends none. If no
     /// usable prompt boundary is found, falls back to the last
     /// `config.pty.copy_lines_count` lines of the full buffer.
     pub(super) fn copy_last_command_output(&mut self) {
         let pane = self.active_pane;
         if let Some(pty) = self.terminals.get(&pane) {
-            let mut all = pty.extract_all_lines();
+            let (mut all, blocks) = pty.command_blocks();
             while all.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
                 all.pop();
             }
@@ -148,7 +152,9 @@ impl App {
                 return;
             }
 
-            let lines = select_last_command_block(&all, self.config.pty.copy_lines_count);
+            let lines = last_marked_block(&all, &blocks).unwrap_or_else(|| {
+                select_last_command_block(&all, self.config.pty.copy_lines_count)
+            });
             if !lines.is_empty() {
                 let text = lines.join("\n");
                 let outcome = crate::clipboard::copy_to_clipboard(&text);
@@ -247,6 +253,21 @@ impl App {
         }
     }
 
+    /// Copy `text` to the clipboard (with the copy flash) or send it to Claude
+    pub(super) fn deliver_text(&mut self, text: &str, target: CopyTarget) {
+        match target {
+            CopyTarget::Clipboard => {
+                let outcome = crate::clipboard::copy_to_clipboard(text);
+                if outcome.is_success() {
+                    self.last_copy_time = Some(std::time::Instant::now());
+                    self.copy_flash_lines = text.lines().count();
+                }
+                self.handle_copy_outcome(outcome);
+            }
+            CopyTarget::Claude => self.send_to_claude(text),
+        }
+    }
+
     /// Drain any clipboard worker outcome that completed since the last
     /// frame and surface failures via the footer error flash. Called once
     /// per event-loop tick. Successful outcomes are silent — the caller
@@ -268,17 +289,10 @@ impl App {
         };
 
         match self.active_pane {
-            PaneId::Claude => {
-                // Claude CLI doesn't understand bracketed paste sequences.
-                // Send raw bytes — for multiline, the user must use \ continuation.
-                if let Some(pty) = self.terminals.get_mut(&PaneId::Claude) {
-                    let _ = pty.write_input(text.as_bytes());
-                }
-            }
-            PaneId::LazyGit | PaneId::Terminal => {
+            PaneId::Claude | PaneId::LazyGit | PaneId::Terminal => {
+                // Bracketed when the child enabled DECSET 2004
                 if let Some(pty) = self.terminals.get_mut(&self.active_pane) {
-                    let bracketed = format!("\x1b[200~{}\x1b[201~", text);
-                    let _ = pty.write_input(bracketed.as_bytes());
+                    let _ = pty.write_paste(&text);
                 }
             }
             PaneId::Preview => {
@@ -351,6 +365,76 @@ fn select_last_command_block(all: &[String], fallback_n: usize) -> Vec<String> {
         .collect()
 }
 
+/// Prompt and command-input lines of OSC 133 command blocks
+fn prompt_lines(blocks: &[CommandBlock]) -> impl Iterator<Item = usize> + '_ {
+    blocks
+        .iter()
+        .flat_map(|block| block.prompt..block.output.unwrap_or(block.prompt + 1))
+}
+
+/// Non-blank lines of a marked command block: prompt, command and output
+fn block_lines(all: &[String], block: &CommandBlock) -> Vec<String> {
+    all.get(block.prompt..block.end.min(all.len()))
+        .unwrap_or_default()
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .cloned()
+        .collect()
+}
+
+/// The most recent command that ran, from OSC 133 marks. `None` without
+/// shell integration.
+fn last_marked_block(all: &[String], blocks: &[CommandBlock]) -> Option<Vec<String>> {
+    blocks
+        .iter()
+        .rev()
+        .find(|block| block.output.is_some())
+        .map(|block| block_lines(all, block))
+}
+
+/// The most recent command block in `all` that failed. With OSC 133 marks
+/// that is a non-zero exit code (error patterns when the shell reports no
+/// code); otherwise the output must contain an error, walking back prompt by
+/// prompt. Prompt lines are kept so the failing command itself is part of
+/// the block.
+pub(super) fn last_failing_command_block(
+    all: &[String],
+    blocks: &[CommandBlock],
+    fallback_n: usize,
+) -> Option<crate::filter::FilteredOutput> {
+    use crate::filter::{filter_lines, is_prompt_line, FilterOptions};
+
+    let options = FilterOptions {
+        filter_prompts: false,
+        ..FilterOptions::default()
+    };
+
+    if blocks.iter().any(|block| block.output.is_some()) {
+        return blocks
+            .iter()
+            .rev()
+            .filter(|block| block.output.is_some())
+            .find_map(|block| {
+                let filtered = filter_lines(block_lines(all, block), &options);
+                let failed = block
+                    .exit_code
+                    .map_or(filtered.contains_error, |code| code != 0);
+                failed.then_some(filtered)
+            });
+    }
+
+    let mut end = all.len();
+    while end > 0 {
+        let block = select_last_command_block(&all[..end], fallback_n);
+        let filtered = filter_lines(block, &options);
+        if filtered.contains_error {
+            return Some(filtered);
+        }
+        end = all[..end].iter().rposition(|l| is_prompt_line(l))?;
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,4 +466,56 @@ mod tests {
         let all = s(&["aaa", "bbb", "ccc"]);
         assert_eq!(select_last_command_block(&all, 2), s(&["bbb", "ccc"]));
     }
+
+    #[test]
+    fn failing_block_skips_successful_commands() {
+        let all = s(&[
+            "❯ cargo build",
+            "error[E0425]: cannot find value `x`",
+            "❯ ls",
+            "Cargo.toml",
+            "❯",
+        ]);
+        let block = last_failing_command_block(&all, &[], 50).unwrap();
+        assert_eq!(
+            block.lines,
+            s(&["❯ cargo build", "error[E0425]: cannot find value `x`"])
+        );
+
+        let ok = s(&["❯ ls", "Cargo.toml", "❯"]);
+        assert!(last_failing_command_block(&ok, &[], 50).is_none());
+    }
+
+    #[test]
+    fn marked_blocks_use_exit_codes() {
+        let all = s(&["λ make", "warning: unused", "λ grep x y", "", "λ"]);
+        let block = |prompt, end, exit_code| CommandBlock {
+            prompt,
+            input: None,
+            output: Some(prompt + 1),
+            end,
+            exit_code,
+            duration: None,
+            running: false,
+        };
+        let waiting = CommandBlock {
+            prompt: 4,
+            input: None,
+            output: None,
+            end: 5,
+            exit_code: None,
+            duration: None,
+            running: false,
+        };
+        let blocks = [block(0, 2, Some(0)), block(2, 4, Some(1)), waiting];
+
+        // A silent failure is found without any error text
+        let failing = last_failing_command_block(&all, &blocks, 50).unwrap();
+        assert_eq!(failing.lines, s(&["λ grep x y"]));
+        assert_eq!(last_marked_block(&all, &blocks), Some(s(&["λ grep x y"])));
+        assert_eq!(prompt_lines(&blocks).collect::<Vec<_>>(), vec![0, 2, 4]);
+
+        let ok = [block(0, 2, Some(0))];
+        assert!(last_failing_command_block(&all, &ok, 50).is_none());
+    }
 }
@@ -0,0 +1,183 @@
+//! Command blocks of the Terminal pane (`Ctrl+B ↑`): jump between the
+//! commands recorded via shell integration (OSC 133 marks) and copy a
+//! block's command line or output, or send it to Claude.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::terminal::CommandBlock;
+use crate::types::CopyTarget;
+
+use super::App;
+
+/// Typed command of a block: from the `B` mark up to the output. Without a
+/// `B` mark the prompt lines are returned as they are.
+pub(crate) fn command_text(lines: &[String], block: &CommandBlock) -> String {
+    let end = block.output.unwrap_or(block.end).min(lines.len());
+    let (first, col) = block
+        .input
+        .map_or((block.prompt, 0), |(line, col)| (line, col as usize));
+    lines
+        .get(first..end)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => line.chars().skip(col).collect(),
+            _ => line.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Output of a block without trailing blank lines
+pub(crate) fn output_text(lines: &[String], block: &CommandBlock) -> String {
+    let Some(start) = block.output else {
+        return String::new();
+    };
+    let mut output = lines
+        .get(start..block.end.min(lines.len()))
+        .unwrap_or_default()
+        .to_vec();
+    while output.last().is_some_and(|l| l.trim().is_empty()) {
+        output.pop();
+    }
+    output.join("\n")
+}
+
+impl App {
+    /// Enter block mode in the active pane on the most recent command
+    pub(super) fn open_block_mode(&mut self) {
+        let Some(pty) = self.terminals.get(&self.active_pane) else {
+            return;
+        };
+        let blocks = pty.command_block_list();
+        let Some(block) = blocks.iter().rev().find(|b| b.output.is_some()) else {
+            self.set_clipboard_error_flash(
+                "No command blocks (shell integration not active)".to_string(),
+            );
+            return;
+        };
+        // Block end first, then the prompt: shows as much of the block as fits
+        pty.scroll_line_into_view(block.end.saturating_sub(1));
+        pty.scroll_line_into_view(block.prompt);
+        self.terminal_selection.clear();
+        self.block_mode.open(self.active_pane, block.prompt);
+    }
+
+    /// Leave block mode and return the pane to the live output
+    fn close_block_mode(&mut self) {
+        if let Some(pty) = self.block_mode.pane.and_then(|p| self.terminals.get(&p)) {
+            pty.scroll_down(usize::MAX);
+        }
+        self.block_mode.close();
+    }
+
+    /// Keys in block mode. Returns false for keys that keep their normal
+    /// meaning (F-keys), true when consumed.
+    pub(super) fn handle_block_mode_key(&mut self, key: KeyEvent) -> bool {
+        let Some(pane) = self.block_mode.pane else {
+            return false;
+        };
+        let Some(pty) = self.terminals.get(&pane) else {
+            self.block_mode.close();
+            return false;
+        };
+        if matches!(key.code, KeyCode::F(_)) {
+            return false;
+        }
+
+        let (lines, blocks) = pty.command_blocks();
+        let Some(current) = self.block_mode.selected(&blocks) else {
+            self.close_block_mode();
+            return true;
+        };
+        let last = blocks.len() - 1;
+        let block = &blocks[current];
+
+        let next = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close_block_mode();
+                return true;
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.deliver_block_text(output_text(&lines, block), CopyTarget::Clipboard);
+                return true;
+            }
+            KeyCode::Char('Y') => {
+                self.deliver_block_text(command_text(&lines, block), CopyTarget::Clipboard);
+                return true;
+            }
+            KeyCode::Char('s') => {
+                self.deliver_block_text(output_text(&lines, block), CopyTarget::Claude);
+                return true;
+            }
+            KeyCode::Char('S') => {
+                self.deliver_block_text(command_text(&lines, block), CopyTarget::Claude);
+                return true;
+            }
+            KeyCode::Up | KeyCode::Char('k') => current.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => (current + 1).min(last),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            _ => return true,
+        };
+
+        let block = &blocks[next];
+        pty.scroll_line_into_view(block.end.saturating_sub(1));
+        pty.scroll_line_into_view(block.prompt);
+        self.block_mode.prompt = block.prompt;
+        true
+    }
+
+    /// Copy `text` or send it to Claude, then leave block mode
+    fn deliver_block_text(&mut self, text: String, target: CopyTarget) {
+        self.close_block_mode();
+        if text.trim().is_empty() {
+            self.set_clipboard_error_flash("Block is empty".to_string());
+            return;
+        }
+        self.deliver_text(&text, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(
+        prompt: usize,
+        input: Option<(usize, u16)>,
+        output: usize,
+        end: usize,
+    ) -> CommandBlock {
+        CommandBlock {
+            prompt,
+            input,
+            output: Some(output),
+            end,
+            exit_code: Some(0),
+            duration: None,
+            running: false,
+        }
+    }
+
+    #[test]
+    fn command_and_output_text_of_a_block() {
+        let lines: Vec<String> = ["~/src", "$ cargo build \\", "  --release", "done", "", "$ "]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let marked = block(0, Some((1, 2)), 3, 5);
+        assert_eq!(command_text(&lines, &marked), "cargo build \\\n  --release");
+        assert_eq!(output_text(&lines, &marked), "done");
+
+        // Without a B mark the prompt lines are the command
+        let unmarked = block(0, None, 3, 5);
+        assert_eq!(
+            command_text(&lines, &unmarked),
+            "~/src\n$ cargo build \\\n  --release"
+        );
+    }
+}
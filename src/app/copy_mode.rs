@@ -0,0 +1,335 @@
+//! Vim-style copy mode for the terminal panes (Shift+F6, `Ctrl+B [`):
+//! a cursor over the whole scrollback with hjkl/w/b/e motions, char / line /
+//! block selection (`v` / `V` / Ctrl+V), `/` search and `y` to yank.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::types::{CopyModeState, CopySelectionKind, CopyTarget};
+
+use super::App;
+
+type Pos = (usize, usize);
+
+/// Character class for word motions: 0 = blank, 1 = word, 2 = punctuation
+fn class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+/// Cursor positions in a line; an empty line still has column 0
+fn line_len(lines: &[String], line: usize) -> usize {
+    lines.get(line).map_or(1, |l| l.chars().count().max(1))
+}
+
+fn char_at(lines: &[String], (line, col): Pos) -> char {
+    lines
+        .get(line)
+        .and_then(|l| l.chars().nth(col))
+        .unwrap_or(' ')
+}
+
+fn next_pos(lines: &[String], (line, col): Pos) -> Option<Pos> {
+    if col + 1 < line_len(lines, line) {
+        Some((line, col + 1))
+    } else if line + 1 < lines.len() {
+        Some((line + 1, 0))
+    } else {
+        None
+    }
+}
+
+fn prev_pos(lines: &[String], (line, col): Pos) -> Option<Pos> {
+    let col = col.min(line_len(lines, line) - 1);
+    if col > 0 {
+        Some((line, col - 1))
+    } else if line > 0 {
+        Some((line - 1, line_len(lines, line - 1) - 1))
+    } else {
+        None
+    }
+}
+
+/// `w`: start of the next word
+fn word_forward(lines: &[String], pos: Pos) -> Pos {
+    let cls = |p: Pos| class(char_at(lines, p));
+    let start = cls(pos);
+    let mut p = pos;
+    loop {
+        let Some(n) = next_pos(lines, p) else {
+            return p;
+        };
+        let crossed_line = n.0 != p.0;
+        p = n;
+        if crossed_line || cls(p) != start {
+            break;
+        }
+    }
+    while cls(p) == 0 {
+        match next_pos(lines, p) {
+            Some(n) => p = n,
+            None => return p,
+        }
+    }
+    p
+}
+
+/// `e` (forward) / `b` (backward): end resp. start of the next word in that direction
+fn word_edge(lines: &[String], pos: Pos, forward: bool) -> Pos {
+    let step = |p: Pos| {
+        if forward {
+            next_pos(lines, p)
+        } else {
+            prev_pos(lines, p)
+        }
+    };
+    let cls = |p: Pos| class(char_at(lines, p));
+    let Some(mut p) = step(pos) else {
+        return pos;
+    };
+    while cls(p) == 0 {
+        match step(p) {
+            Some(n) => p = n,
+            None => return p,
+        }
+    }
+    while let Some(n) = step(p) {
+        if n.0 != p.0 || cls(n) != cls(p) {
+            break;
+        }
+        p = n;
+    }
+    p
+}
+
+/// Text covered by the copy-mode selection (the cursor line without one)
+pub(crate) fn selected_text(lines: &[String], state: &CopyModeState) -> String {
+    let Some(anchor) = state.anchor else {
+        return lines.get(state.cursor.0).cloned().unwrap_or_default();
+    };
+    let (start, end) = (anchor.min(state.cursor), anchor.max(state.cursor));
+    let end_line = end.0.min(lines.len().saturating_sub(1));
+    let rows = lines.get(start.0..=end_line).unwrap_or_default();
+
+    let slice = |line: &str, from: usize, to: usize| -> String {
+        line.chars()
+            .skip(from)
+            .take((to + 1).saturating_sub(from))
+            .collect()
+    };
+
+    let parts: Vec<String> = match state.kind {
+        CopySelectionKind::Line => rows.to_vec(),
+        CopySelectionKind::Block => {
+            let left = anchor.1.min(state.cursor.1);
+            let right = anchor.1.max(state.cursor.1);
+            rows.iter()
+                .map(|l| slice(l, left, right).trim_end().to_string())
+                .collect()
+        }
+        CopySelectionKind::Char => rows
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let line = start.0 + i;
+                let from = if line == start.0 { start.1 } else { 0 };
+                let to = if line == end.0 { end.1 } else { usize::MAX - 1 };
+                slice(l, from, to)
+            })
+            .collect(),
+    };
+    parts.join("\n")
+}
+
+impl App {
+    /// Enter copy mode in the active terminal pane at the terminal cursor
+    pub(super) fn open_copy_mode(&mut self) {
+        let Some(pty) = self.terminals.get(&self.active_pane) else {
+            return;
+        };
+        let (top, (row, col)) = pty.view_origin();
+        self.terminal_selection.clear();
+        self.copy_mode.open(
+            self.active_pane,
+            (top + row as usize, col as usize),
+            self.config.pty.copy_mode_target,
+        );
+    }
+
+    /// Leave copy mode and return the pane to the live output
+    fn close_copy_mode(&mut self) {
+        if let Some(pty) = self.copy_mode.pane.and_then(|p| self.terminals.get(&p)) {
+            pty.scroll_down(usize::MAX);
+        }
+        self.copy_mode.close();
+    }
+
+    /// Keys in copy mode. Returns false for keys that keep their normal
+    /// meaning (F-keys), true when consumed.
+    pub(super) fn handle_copy_mode_key(&mut self, key: KeyEvent) -> bool {
+        let Some(pane) = self.copy_mode.pane else {
+            return false;
+        };
+        let Some(pty) = self.terminals.get(&pane) else {
+            self.copy_mode.close();
+            return false;
+        };
+        if matches!(key.code, KeyCode::F(_)) {
+            return false;
+        }
+
+        let lines = pty.extract_all_lines();
+        let page = (pty.rows() as usize).max(2);
+        let last_line = lines.len().saturating_sub(1);
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let (line, col) = self.copy_mode.cursor;
+        let col = col.min(line_len(&lines, line) - 1);
+
+        let cursor = match key.code {
+            KeyCode::Esc if self.copy_mode.anchor.is_some() => {
+                self.copy_mode.anchor = None;
+                return true;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close_copy_mode();
+                return true;
+            }
+            KeyCode::Char('v') if ctrl => {
+                self.copy_mode.toggle_selection(CopySelectionKind::Block);
+                return true;
+            }
+            KeyCode::Char('v') => {
+                self.copy_mode.toggle_selection(CopySelectionKind::Char);
+                return true;
+            }
+            KeyCode::Char('V') => {
+                self.copy_mode.toggle_selection(CopySelectionKind::Line);
+                return true;
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.yank_copy_selection(&lines);
+                return true;
+            }
+            KeyCode::Char('t') => {
+                self.copy_mode.target = match self.copy_mode.target {
+                    CopyTarget::Clipboard => CopyTarget::Claude,
+                    CopyTarget::Claude => CopyTarget::Clipboard,
+                };
+                return true;
+            }
+            KeyCode::Char('/') => {
+                self.terminal_search.open(pane);
+                return true;
+            }
+            KeyCode::Char('n') if !ctrl => {
+                self.copy_mode_search_step(&pty.extract_all_text(), true)
+            }
+            KeyCode::Char('N') => self.copy_mode_search_step(&pty.extract_all_text(), false),
+            KeyCode::Char('u') if ctrl => (line.saturating_sub(page / 2), col),
+            KeyCode::Char('d') if ctrl => ((line + page / 2).min(last_line), col),
+            KeyCode::PageUp => (line.saturating_sub(page), col),
+            KeyCode::PageDown => ((line + page).min(last_line), col),
+            KeyCode::Left | KeyCode::Char('h') => (line, col.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => {
+                (line, (col + 1).min(line_len(&lines, line) - 1))
+            }
+            KeyCode::Up | KeyCode::Char('k') => (line.saturating_sub(1), col),
+            KeyCode::Down | KeyCode::Char('j') => ((line + 1).min(last_line), col),
+            KeyCode::Char('w') => word_forward(&lines, (line, col)),
+            KeyCode::Char('b') => word_edge(&lines, (line, col), false),
+            KeyCode::Char('e') => word_edge(&lines, (line, col), true),
+            KeyCode::Home | KeyCode::Char('0') => (line, 0),
+            KeyCode::Char('^') => {
+                let indent = lines
+                    .get(line)
+                    .map_or(0, |l| l.chars().take_while(|c| c.is_whitespace()).count());
+                (line, indent.min(line_len(&lines, line) - 1))
+            }
+            KeyCode::End | KeyCode::Char('$') => (line, line_len(&lines, line) - 1),
+            KeyCode::Char('g') => (0, 0),
+            KeyCode::Char('G') => (last_line, 0),
+            _ => return true,
+        };
+
+        self.copy_mode.cursor = cursor;
+        pty.scroll_line_into_view(cursor.0);
+        true
+    }
+
+    /// `n` / `N`: next hit of the last `/` search after (before) the cursor.
+    /// `text` is the buffer as displayed (`extract_all_text`): hit columns
+    /// are cells, like the cursor column.
+    fn copy_mode_search_step(&self, text: &[String], forward: bool) -> Pos {
+        let cursor = self.copy_mode.cursor;
+        let Some((query, regex, case_sensitive)) = &self.copy_mode.last_search else {
+            return cursor;
+        };
+        let hits: Vec<Pos> =
+            super::terminal_search::find_matches(text, query, *regex, *case_sensitive)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(line, start, _)| (line, start))
+                .collect();
+        let next = if forward {
+            hits.iter().find(|&&p| p > cursor).or(hits.first())
+        } else {
+            hits.iter().rev().find(|&&p| p < cursor).or(hits.last())
+        };
+        next.copied().unwrap_or(cursor)
+    }
+
+    /// Send the selection to the copy-mode target and leave copy mode
+    fn yank_copy_selection(&mut self, lines: &[String]) {
+        let text = selected_text(lines, &self.copy_mode);
+        let target = self.copy_mode.target;
+        self.close_copy_mode();
+        if text.trim().is_empty() {
+            return;
+        }
+        self.deliver_text(&text, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn word_motions_cross_lines_and_punctuation() {
+        let buf = lines("let x = foo.bar();\n\n  next line");
+        assert_eq!(word_forward(&buf, (0, 0)), (0, 4));
+        assert_eq!(word_forward(&buf, (0, 8)), (0, 11));
+        assert_eq!(word_forward(&buf, (0, 16)), (2, 2));
+        assert_eq!(word_edge(&buf, (0, 8), true), (0, 10));
+        assert_eq!(word_edge(&buf, (2, 2), false), (0, 15));
+        assert_eq!(word_edge(&buf, (0, 12), false), (0, 11));
+        assert_eq!(word_edge(&buf, (0, 0), false), (0, 0));
+    }
+
+    #[test]
+    fn selected_text_per_kind() {
+        let buf = lines("alpha beta\ngamma delta\nepsilon");
+        let mut state = CopyModeState {
+            cursor: (1, 3),
+            ..CopyModeState::default()
+        };
+        assert_eq!(selected_text(&buf, &state), "gamma delta");
+
+        state.anchor = Some((0, 6));
+        assert_eq!(selected_text(&buf, &state), "beta\ngamm");
+        state.kind = CopySelectionKind::Line;
+        assert_eq!(selected_text(&buf, &state), "alpha beta\ngamma delta");
+        state.kind = CopySelectionKind::Block;
+        state.cursor = (2, 2);
+        assert_eq!(selected_text(&buf, &state), "pha b\nmma d\nsilon");
+        assert!(state.is_selected(1, 4) && !state.is_selected(1, 7));
+    }
+}
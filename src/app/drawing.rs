@@ -1,43 +1,25 @@
-use std::collections::HashMap;
-
 use ratatui::{layout::Rect, Frame};
 
-use crate::terminal::PseudoTerminal;
 use crate::types::PaneId;
 use crate::ui;
 
-use super::App;
+use super::{App, LayoutRects};
 
 impl App {
     pub(super) fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
-        let (files, preview, claude, lazygit, terminal, footer) = ui::layout::compute_layout(
-            area,
-            self.show_file_browser,
-            self.show_terminal,
-            self.show_lazygit,
-            self.show_preview,
-            self.preview_maximized,
-            &self.config.layout,
-        );
-
-        // Helper to resize PTY
-        // We need to account for borders (1px each side => -2)
-        // Ensure strictly positive
-        let resize_pty =
-            |terminals: &mut HashMap<PaneId, PseudoTerminal>, id: PaneId, rect: Rect| {
-                if let Some(pty) = terminals.get_mut(&id) {
-                    let w = rect.width.saturating_sub(2);
-                    let h = rect.height.saturating_sub(2);
-                    if w > 0 && h > 0 {
-                        let _ = pty.resize(h, w);
-                    }
-                }
-            };
-
-        resize_pty(&mut self.terminals, PaneId::Claude, claude);
-        resize_pty(&mut self.terminals, PaneId::LazyGit, lazygit);
-        resize_pty(&mut self.terminals, PaneId::Terminal, terminal);
+        let LayoutRects {
+            files,
+            preview,
+            claude,
+            lazygit,
+            terminal,
+            footer,
+        } = self.layout_rects(area);
+
+        // PTYs follow the layout, hidden panes included
+        self.pty_sizer.set_area(area);
+        self.sync_pty_sizes();
 
         // Cache border positions for interactive pane resizing
         self.border_areas.total_width = area.width;
@@ -154,6 +136,13 @@ impl App {
             .filter(|(_, t)| t.elapsed().as_secs() < 3)
             .map(|(msg, _)| msg.clone());
 
+        // Claude notification flash (5 s).
+        let notification = self
+            .notification_flash
+            .as_ref()
+            .filter(|(_, t)| t.elapsed().as_secs() < 5)
+            .map(|(msg, _)| msg.clone());
+
         // Persistent clipboard warning banner (10 s, dismissable).
         let clipboard_warning = if self.clipboard_warning_dismissed {
             None
@@ -184,6 +173,7 @@ impl App {
                 copy_flash_message,
                 preview_maximized: self.preview_maximized,
                 clipboard_error,
+                notification,
                 clipboard_warning,
                 ssh_image_paste_hint,
                 terminal_prefix_armed: self.terminal_prefix_armed,
@@ -221,6 +211,18 @@ impl App {
             ui::fuzzy_finder::render(frame, area, &mut self.fuzzy_finder);
         }
 
+        if self.context_menu.visible {
+            ui::context_menu::render(frame, area, &self.context_menu);
+        }
+
+        if self.prompt_picker.visible {
+            ui::prompt_picker::render(frame, area, &mut self.prompt_picker);
+        }
+
+        if self.history.visible {
+            ui::history::render(frame, area, &mut self.history);
+        }
+
         if self.wizard.visible {
             ui::wizard_ui::render(frame, area, &self.wizard);
         }
@@ -256,8 +258,9 @@ fn render_export_chooser(frame: &mut Frame, area: Rect, state: &crate::types::Ex
         widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     };
 
-    let popup_width: u16 = 40;
-    let popup_height: u16 = 7; // border + title + 2 items + spacer + footer
+    let popup_width: u16 = 52;
+    // border + 4 items + spacer + template + profile (+ book) + spacer + footer
+    let popup_height: u16 = if state.is_batch { 12 } else { 11 };
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
     let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
@@ -281,38 +284,79 @@ fn render_export_chooser(frame: &mut Frame, area: Rect, state: &crate::types::Ex
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
 
-    let items: Vec<ListItem> = vec![
-        {
-            let marker = if state.selected == 0 { "● " } else { "○ " };
-            let style = if state.selected == 0 {
+    let items: Vec<ListItem> = crate::browser::pdf_export::ExportFormat::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, format)| {
+            let marker = if state.selected == i { "● " } else { "○ " };
+            let style = if state.selected == i {
                 Style::default().fg(Color::Black).bg(Color::Cyan)
             } else {
                 Style::default()
             };
-            ListItem::new(Line::from(format!("{}Markdown (.md)", marker))).style(style)
-        },
-        {
-            let marker = if state.selected == 1 { "● " } else { "○ " };
-            let style = if state.selected == 1 {
-                Style::default().fg(Color::Black).bg(Color::Cyan)
-            } else {
-                Style::default()
-            };
-            ListItem::new(Line::from(format!("{}PDF (.pdf)", marker))).style(style)
-        },
-    ];
-
-    let list_area = Rect::new(inner.x, inner.y, inner.width, 2);
+            ListItem::new(Line::from(format!(
+                "{}{} (.{})",
+                marker,
+                format.label(),
+                format.extension()
+            )))
+            .style(style)
+        })
+        .collect();
+    let item_count = items.len() as u16;
+
+    let list_area = Rect::new(inner.x, inner.y, inner.width, item_count);
     let list = List::new(items);
     frame.render_widget(list, list_area);
 
+    // Template selector (applies to PDF export)
+    if inner.height > item_count + 2 {
+        let template_area = Rect::new(inner.x, inner.y + item_count + 1, inner.width, 1);
+        let template = Paragraph::new(Line::from(vec![
+            Span::raw("  Template: "),
+            Span::styled(
+                format!("\u{25C0} {} \u{25B6}", state.template_label()),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        frame.render_widget(template, template_area);
+    }
+
+    // Branding profile selector (Auto = path rules)
+    if inner.height > item_count + 3 {
+        let profile_area = Rect::new(inner.x, inner.y + item_count + 2, inner.width, 1);
+        let profile = Paragraph::new(Line::from(vec![
+            Span::raw("  Profile:  "),
+            Span::styled(
+                format!("\u{25C0} {} \u{25B6}", state.profile_label()),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        frame.render_widget(profile, profile_area);
+    }
+
+    // Book mode toggle (folder exports only)
+    if state.is_batch && inner.height > item_count + 4 {
+        let book_area = Rect::new(inner.x, inner.y + item_count + 3, inner.width, 1);
+        let book = Paragraph::new(Line::from(vec![
+            Span::raw("  Book (one PDF): "),
+            Span::styled(
+                if state.book { "[x]" } else { "[ ]" },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        frame.render_widget(book, book_area);
+    }
+
     // Footer hint
     if inner.height > 3 {
         let footer_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
-        let footer = Paragraph::new(Span::styled(
-            "j/k: Select │ Enter: OK │ Esc: Cancel",
-            Style::default().fg(Color::DarkGray),
-        ));
+        let hint = if state.is_batch {
+            "j/k │ h/l │ p: Profile │ b: Book │ Enter │ Esc"
+        } else {
+            "j/k │ h/l: Template │ p: Profile │ Enter │ Esc"
+        };
+        let footer = Paragraph::new(Span::styled(hint, Style::default().fg(Color::DarkGray)));
         frame.render_widget(footer, footer_area);
     }
 }
@@ -150,12 +150,11 @@ impl App {
             MenuAction::ExportFile => {
                 if let Some(path) = &self.preview.current_file {
                     if self.preview.is_markdown {
-                        self.export_chooser = crate::types::ExportChooserState {
-                            visible: true,
-                            source_path: path.clone(),
-                            selected: 0,
-                            is_batch: false,
-                        };
+                        self.export_chooser = crate::types::ExportChooserState::open(
+                            path.clone(),
+                            false,
+                            &self.config.document,
+                        );
                     }
                 }
             }
@@ -290,6 +289,26 @@ impl App {
                 self.sync_terminals();
                 self.check_repo_change();
             }
+            DialogAction::ResumeClaudeSession {
+                project,
+                session_id,
+            } => {
+                self.resume_claude_session(&project, &session_id);
+            }
+            DialogAction::SendToClaude { text, clear_marks } => {
+                self.send_to_claude(&text);
+                if clear_marks {
+                    self.file_browser.marked.clear();
+                }
+            }
+            DialogAction::SaveScrollback { pane } => {
+                if let Some(target) = value {
+                    self.save_scrollback(pane, &target);
+                }
+            }
+            DialogAction::OverwriteScrollback { pane, path } => {
+                self.write_scrollback(pane, &path);
+            }
             DialogAction::GitPull { repo_root } => {
                 // Execute git pull
                 match crate::git::pull(&repo_root) {
@@ -376,7 +395,12 @@ impl App {
                     }
                 }
             }
-            DialogAction::ExportMarkdown { source, format } => {
+            DialogAction::ExportMarkdown {
+                source,
+                format,
+                template,
+                profile,
+            } => {
                 if let Some(target_str) = value {
                     if !target_str.is_empty() {
                         // Expand tilde
@@ -417,6 +441,9 @@ impl App {
                             author: self.config.document.resolved_author(),
                             date: crate::browser::pdf_export::date_now_dmy(),
                             format,
+                            template,
+                            meta: Default::default(),
+                            profile,
                         };
 
                         if format == crate::browser::pdf_export::ExportFormat::Pdf {
@@ -443,21 +470,30 @@ impl App {
                             self.copy_flash_lines = 0;
                             self.last_copy_time = Some(std::time::Instant::now());
                         } else {
-                            // Markdown export is instant — run synchronously
+                            // Markdown/HTML/DOCX export is instant — run synchronously
                             match crate::browser::pdf_export::export_markdown(
                                 &source,
                                 &target_path,
                                 &options,
                                 &self.config.document,
                             ) {
-                                Ok(path) => {
-                                    self.copy_flash_message = Some("Markdown exported".to_string());
+                                Ok(output) => {
+                                    let path = output.path;
+                                    self.copy_flash_message =
+                                        Some(format!("{} exported", format.label()));
                                     self.copy_flash_lines = 0;
                                     self.last_copy_time = Some(std::time::Instant::now());
-                                    let _ = crate::browser::opener::open_file_with_browser(
-                                        &path,
-                                        &self.config.ui.browser,
-                                    );
+                                    // Word documents go to the system handler, not the browser
+                                    let _ = if format
+                                        == crate::browser::pdf_export::ExportFormat::Docx
+                                    {
+                                        crate::browser::opener::open_file(&path)
+                                    } else {
+                                        crate::browser::opener::open_file_with_browser(
+                                            &path,
+                                            &self.config.ui.browser,
+                                        )
+                                    };
                                 }
                                 Err(e) => {
                                     self.dialog.dialog_type = ui::dialog::DialogType::Confirm {
@@ -484,11 +520,23 @@ impl App {
             PollOutcome::Ready(result) => {
                 let browser = self.export_browser.take().unwrap_or_default();
                 match result {
-                    crate::types::ExportJobResult::Single(Ok(path)) => {
-                        self.copy_flash_message = Some("PDF exported".to_string());
+                    crate::types::ExportJobResult::Single(Ok(output)) => {
+                        // Mention font substitutions/fallbacks and ignored
+                        // settings, stay quiet otherwise
+                        let mut message = match &output.fonts {
+                            Some(report) if !report.is_clean() => {
+                                format!("PDF exported \u{00B7} {}", report.summary())
+                            }
+                            _ => "PDF exported".to_string(),
+                        };
+                        for warning in &output.warnings {
+                            message.push_str(&format!(" \u{00B7} {}", warning));
+                        }
+                        self.copy_flash_message = Some(message);
                         self.copy_flash_lines = 0;
                         self.last_copy_time = Some(std::time::Instant::now());
-                        let _ = crate::browser::opener::open_file_with_browser(&path, &browser);
+                        let _ =
+                            crate::browser::opener::open_file_with_browser(&output.path, &browser);
                     }
                     crate::types::ExportJobResult::Single(Err(e)) => {
                         self.copy_flash_message = None;
@@ -540,6 +588,8 @@ impl App {
         &mut self,
         source: std::path::PathBuf,
         format: crate::browser::pdf_export::ExportFormat,
+        template: Option<String>,
+        profile: Option<String>,
     ) {
         // Guard: if already running, ignore
         if self.export_job.is_running() {
@@ -547,24 +597,7 @@ impl App {
         }
 
         // Collect top-level .md files (case-insensitive extension)
-        let md_files: Vec<std::path::PathBuf> = std::fs::read_dir(&source)
-            .into_iter()
-            .flatten()
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| {
-                p.is_file()
-                    && p.extension()
-                        .and_then(|x| x.to_str())
-                        .map(|x| {
-                            matches!(
-                                x.to_lowercase().as_str(),
-                                "md" | "markdown" | "mdown" | "mkd"
-                            )
-                        })
-                        .unwrap_or(false)
-            })
-            .collect();
+        let md_files = crate::browser::pdf_export::markdown_files_in(&source);
 
         if md_files.is_empty() {
             self.copy_flash_message = Some("No .md files in folder".to_string());
@@ -605,6 +638,9 @@ impl App {
                     author: doc_config.resolved_author(),
                     date: crate::browser::pdf_export::date_now_dmy(),
                     format,
+                    template: template.clone(),
+                    meta: Default::default(),
+                    profile: profile.clone(),
                 };
                 match crate::browser::pdf_export::export_markdown(src, &out, &options, &doc_config)
                 {
@@ -623,35 +659,113 @@ impl App {
         self.export_job = super::JobState::running(rx);
     }
 
+    /// Start an async book export: the Markdown files of `source` merged into
+    /// one PDF (chapter order from `_order.yaml` / `SUMMARY.md`).
+    pub(crate) fn start_book_export(
+        &mut self,
+        source: std::path::PathBuf,
+        template: Option<String>,
+        profile: Option<String>,
+    ) {
+        // Guard: if already running, ignore
+        if self.export_job.is_running() {
+            return;
+        }
+
+        let format = crate::browser::pdf_export::ExportFormat::Pdf;
+        let project_name = self
+            .file_browser
+            .root_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string();
+        let filename =
+            crate::browser::pdf_export::default_export_filename(&source, format, &project_name);
+        let target = source.join(filename);
+        let options = crate::browser::pdf_export::ExportOptions {
+            title: source
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Export")
+                .to_string(),
+            author: self.config.document.resolved_author(),
+            date: crate::browser::pdf_export::date_now_dmy(),
+            format,
+            template,
+            meta: Default::default(),
+            profile,
+        };
+        let doc_config = self.config.document.clone();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::browser::book::export_book(&source, &target, &options, &doc_config);
+            let _ = tx.send(crate::types::ExportJobResult::Single(
+                result.map_err(|e| format!("{}", e)),
+            ));
+        });
+        self.export_job = super::JobState::running(rx);
+        self.export_browser = Some(self.config.ui.browser.clone());
+        self.copy_flash_message = Some("Generating PDF book...".to_string());
+        self.copy_flash_lines = 0;
+        self.last_copy_time = Some(std::time::Instant::now());
+    }
+
     /// Open a file in the configured browser (with Markdown→HTML conversion)
     pub(crate) fn open_in_browser(&mut self, path: &std::path::Path) {
         use crate::browser;
+        if !browser::can_preview_in_browser(path) {
+            return;
+        }
+        if self.config.ui.live_preview {
+            if let Some(url) = self.live_preview_url(path) {
+                let _ = browser::open_url_with_browser(&url, &self.config.ui.browser);
+                return;
+            }
+        }
         let project_name = &self.config.document.company.name;
-        if browser::can_preview_in_browser(path) {
-            let preview_path = if browser::is_markdown(path) {
-                match browser::markdown_to_html(path, &self.config.document, project_name) {
-                    Ok(handles) => {
-                        // Get the primary path before moving handles into the vec
-                        let primary = handles[0].path().to_path_buf();
-                        self.temp_preview_files.extend(handles);
-                        primary
-                    }
-                    Err(_) => path.to_path_buf(),
-                }
-            } else if browser::can_syntax_highlight(path) {
-                match browser::text_to_html(path, &self.config.document, project_name) {
-                    Ok(tmp) => {
-                        let p = tmp.path().to_path_buf();
-                        self.temp_preview_files.push(tmp);
-                        p
-                    }
-                    Err(_) => path.to_path_buf(),
+        let preview_path = if browser::is_markdown(path) {
+            match browser::markdown_to_html(path, &self.config.document, project_name) {
+                Ok(handles) => {
+                    // Get the primary path before moving handles into the vec
+                    let primary = handles[0].path().to_path_buf();
+                    self.temp_preview_files.extend(handles);
+                    primary
+                }
+                Err(_) => path.to_path_buf(),
+            }
+        } else if browser::can_syntax_highlight(path) {
+            match browser::text_to_html(path, &self.config.document, project_name) {
+                Ok(tmp) => {
+                    let p = tmp.path().to_path_buf();
+                    self.temp_preview_files.push(tmp);
+                    p
                 }
-            } else {
-                path.to_path_buf()
-            };
-            let _ = browser::open_file_with_browser(&preview_path, &self.config.ui.browser);
+                Err(_) => path.to_path_buf(),
+            }
+        } else {
+            path.to_path_buf()
+        };
+        let _ = browser::open_file_with_browser(&preview_path, &self.config.ui.browser);
+    }
+
+    /// URL of `path` on the live preview server. Starts the server on first
+    /// use, rooted at the project (or the file's folder for outside files).
+    fn live_preview_url(&mut self, path: &std::path::Path) -> Option<String> {
+        if let Some(url) = self.preview_server.as_ref().and_then(|s| s.url_for(path)) {
+            return Some(url);
         }
+        let root_dir = self.file_browser.root_dir.canonicalize().ok();
+        let root = match (root_dir, path.canonicalize().ok()) {
+            (Some(root), Some(file)) if file.starts_with(&root) => root,
+            (_, Some(file)) => file.parent()?.to_path_buf(),
+            _ => return None,
+        };
+        // Replacing the server stops the old one (root changed)
+        self.preview_server =
+            crate::browser::preview_server::PreviewServer::start(&root, &self.config.document).ok();
+        self.preview_server.as_ref()?.url_for(path)
     }
 
     /// Handle wizard input
@@ -853,7 +967,7 @@ impl App {
 mod batch_export_tests {
     use std::path::{Path, PathBuf};
 
-    /// The same filter predicate used in start_batch_export — extracted as a testable fn.
+    /// The same filter predicate as `pdf_export::markdown_files_in` (used by start_batch_export).
     fn is_md_file(p: &Path) -> bool {
         p.is_file()
             && p.extension()
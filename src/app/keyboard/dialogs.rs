@@ -46,6 +46,49 @@ impl App {
         }
     }
 
+    pub(super) fn handle_context_menu_key(&mut self, key: KeyEvent) {
+        let context = match key.code {
+            KeyCode::Esc => {
+                self.context_menu.visible = false;
+                return;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.context_menu.prev();
+                return;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.context_menu.next();
+                return;
+            }
+            KeyCode::Enter => self.context_menu.action(),
+            KeyCode::Char(c) => match ui::context_menu::ClaudeContext::from_key(c) {
+                Some(context) => context,
+                None => return,
+            },
+            _ => return,
+        };
+        self.context_menu.visible = false;
+        self.send_context_to_claude(context);
+    }
+
+    pub(super) fn handle_prompt_picker_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.prompt_picker.close(),
+            KeyCode::Enter => {
+                if let Some(prompt) = self.prompt_picker.selected() {
+                    let template = prompt.template.clone();
+                    self.prompt_picker.close();
+                    self.send_prompt_to_claude(&template);
+                }
+            }
+            KeyCode::Up => self.prompt_picker.prev(),
+            KeyCode::Down => self.prompt_picker.next(),
+            KeyCode::Backspace => self.prompt_picker.pop_char(),
+            KeyCode::Char(c) => self.prompt_picker.push_char(c),
+            _ => {}
+        }
+    }
+
     pub(super) fn handle_update_dialog_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -106,6 +149,45 @@ impl App {
         }
     }
 
+    pub(super) fn handle_history_key(&mut self, key: KeyEvent) {
+        if self.history.searching {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter => self.history.searching = false,
+                KeyCode::Backspace => self.history.pop_char(),
+                KeyCode::Char(c) => self.history.push_char(c),
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.history.close(),
+            KeyCode::Up | KeyCode::Char('k') => self.history.prev(),
+            KeyCode::Down | KeyCode::Char('j') => self.history.next(),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.history.scroll_down(10),
+            KeyCode::PageUp => self.history.scroll_up(10),
+            KeyCode::Char('J') => self.history.scroll_down(1),
+            KeyCode::Char('K') => self.history.scroll_up(1),
+            KeyCode::Char('/') => self.history.searching = true,
+            KeyCode::Enter => {
+                if let Some(session) = self.history.selected() {
+                    self.dialog.dialog_type = ui::dialog::DialogType::Confirm {
+                        title: "Resume Session".to_string(),
+                        message: format!(
+                            "Restart Claude and resume this session?\n{}",
+                            session.first_prompt
+                        ),
+                        action: ui::dialog::DialogAction::ResumeClaudeSession {
+                            project: self.history.project.clone(),
+                            session_id: session.id.clone(),
+                        },
+                    };
+                    self.history.close();
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub(super) fn handle_export_chooser_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => self.export_chooser.visible = false,
@@ -115,23 +197,37 @@ impl App {
                 }
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if self.export_chooser.selected < 1 {
+                if self.export_chooser.selected + 1
+                    < crate::browser::pdf_export::ExportFormat::ALL.len()
+                {
                     self.export_chooser.selected += 1;
                 }
             }
+            KeyCode::Left | KeyCode::Char('h') => self.export_chooser.cycle_template(false),
+            KeyCode::Right | KeyCode::Char('l') => self.export_chooser.cycle_template(true),
+            KeyCode::Char('b') => self.export_chooser.toggle_book(),
+            KeyCode::Char('p') => self.export_chooser.cycle_profile(true),
+            KeyCode::Char('P') => self.export_chooser.cycle_profile(false),
             KeyCode::Enter => {
-                let format = if self.export_chooser.selected == 0 {
-                    crate::browser::pdf_export::ExportFormat::Markdown
-                } else {
-                    crate::browser::pdf_export::ExportFormat::Pdf
+                let Some(&format) =
+                    crate::browser::pdf_export::ExportFormat::ALL.get(self.export_chooser.selected)
+                else {
+                    return;
                 };
                 let source = self.export_chooser.source_path.clone();
                 let is_batch = self.export_chooser.is_batch;
+                let template = self.export_chooser.selected_template();
+                let profile = self.export_chooser.selected_profile();
+                let book = self.export_chooser.book
+                    && format == crate::browser::pdf_export::ExportFormat::Pdf;
                 self.export_chooser.visible = false;
 
-                if is_batch {
+                if is_batch && book {
+                    // Book export: whole folder → one PDF with global TOC
+                    self.start_book_export(source, template, profile);
+                } else if is_batch {
                     // Batch export: skip filename dialog, go directly to export
-                    self.start_batch_export(source, format);
+                    self.start_batch_export(source, format, template, profile);
                 } else {
                     let export_dir =
                         crate::browser::pdf_export::resolve_export_dir(&self.config.ui.export_dir);
@@ -150,17 +246,15 @@ impl App {
                     let target_str = target.to_string_lossy().to_string();
                     let cursor = target_str.len();
                     self.dialog.dialog_type = ui::dialog::DialogType::Input {
-                        title: format!(
-                            "Export as {}",
-                            if format == crate::browser::pdf_export::ExportFormat::Pdf {
-                                "PDF"
-                            } else {
-                                "Markdown"
-                            }
-                        ),
+                        title: format!("Export as {}", format.label()),
                         value: target_str,
                         cursor,
-                        action: ui::dialog::DialogAction::ExportMarkdown { source, format },
+                        action: ui::dialog::DialogAction::ExportMarkdown {
+                            source,
+                            format,
+                            template,
+                            profile,
+                        },
                     };
                 }
             }
@@ -184,6 +278,7 @@ impl App {
                         ui::dialog::DialogAction::GoToPath
                             | ui::dialog::DialogAction::OpenMarkdownPreview
                             | ui::dialog::DialogAction::ExportMarkdown { .. }
+                            | ui::dialog::DialogAction::SaveScrollback { .. }
                     ) {
                         self.dialog.try_complete_path();
                     }
@@ -364,6 +459,7 @@ impl App {
                         session_name: self.config.claude.default_session_name.clone(),
                         worktree: self.config.claude.default_worktree.clone(),
                         remote_control: self.config.claude.remote_control,
+                        resume: String::new(),
                     };
                     self.init_claude_pty(opts);
                 }
@@ -397,6 +493,7 @@ impl App {
                         session_name,
                         worktree,
                         remote_control: remote,
+                        resume: String::new(),
                     };
                     self.init_claude_pty(opts);
                 }
@@ -1,6 +1,6 @@
 //! File-browser pane key handling — j/k navigation, h/l directory entry,
-//! `o`/`O` external open, `.` toggle hidden, `i` add-to-gitignore,
-//! Ctrl+A toggle autosave, `q` quit.
+//! Space mark (for Ctrl+F4 "Send to Claude"), `o`/`O` external open,
+//! `.` toggle hidden, `i` add-to-gitignore, Ctrl+A toggle autosave, `q` quit.
 
 use crossterm::event::KeyCode;
 
@@ -48,6 +48,10 @@ impl App {
                 self.file_browser.refresh();
                 self.update_preview();
             }
+            KeyCode::Char(' ') => {
+                self.file_browser.toggle_mark();
+                self.update_preview();
+            }
             KeyCode::Char('i') => {
                 if let Some(path) = self.file_browser.selected_file() {
                     self.add_to_gitignore(&path);
@@ -1,7 +1,10 @@
 //! Global keyboard shortcuts that fire regardless of which pane is active —
-//! help (F12), about (F10), F7 ~/.claude jump, F9 copy-N-lines / file-menu,
-//! F11 universal paste, Ctrl+P/O/X pickers, Ctrl+Alt+E external editor,
-//! F8 settings, Ctrl+Shift+W wizard. Returns true when the key was consumed
+//! help (F12), about (F10), Shift+F4 prompt library,
+//! Ctrl+F4 send-to-Claude context menu, Shift+F5 recording,
+//! Shift+F6 copy mode, F7 ~/.claude jump,
+//! Shift+F7 Claude history, F9 copy-N-lines / file-menu,
+//! Alt+F9 save scrollback, F11 universal paste, Ctrl+P/O/X pickers,
+//! Ctrl+Alt+E external editor, F8 settings, Ctrl+Shift+W wizard. Returns true when the key was consumed
 //! so the caller can stop routing it further.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -23,6 +26,45 @@ impl App {
             return true;
         }
 
+        // Ctrl+F4: Send context (file, diff, failing command, ...) to Claude
+        if key.code == KeyCode::F(4) && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.context_menu.open();
+            return true;
+        }
+
+        // Shift+F4: Prompt library (expanded and sent to Claude)
+        if key.code == KeyCode::F(4) && key.modifiers.contains(KeyModifiers::SHIFT) {
+            self.prompt_picker.open(self.config.claude.prompts.clone());
+            return true;
+        }
+
+        // Shift+F7: Claude conversation history of the directory Claude runs in
+        if key.code == KeyCode::F(7) && key.modifiers.contains(KeyModifiers::SHIFT) {
+            let project = self
+                .claude_launch
+                .as_ref()
+                .map_or(self.file_browser.root_dir.clone(), |(_, cwd)| cwd.clone());
+            self.history.open(&project);
+            return true;
+        }
+
+        // Shift+F6: Copy mode in the focused terminal pane
+        if key.code == KeyCode::F(6) && key.modifiers.contains(KeyModifiers::SHIFT) {
+            if matches!(
+                self.active_pane,
+                PaneId::Claude | PaneId::LazyGit | PaneId::Terminal
+            ) {
+                self.open_copy_mode();
+            }
+            return true;
+        }
+
+        // Shift+F5: Start/stop the asciicast recording of the focused terminal pane
+        if key.code == KeyCode::F(5) && key.modifiers.contains(KeyModifiers::SHIFT) {
+            self.toggle_recording();
+            return true;
+        }
+
         // F7: Toggle between ~/.claude and previous directory
         if key.code == KeyCode::F(7) {
             if let Some(home) = std::env::var_os("HOME") {
@@ -54,6 +96,18 @@ impl App {
             return true;
         }
 
+        // Alt+F9: Save the scrollback of the focused terminal pane to a file
+        // (before plain F9, which ignores Alt)
+        if key.code == KeyCode::F(9) && key.modifiers.contains(KeyModifiers::ALT) {
+            if matches!(
+                self.active_pane,
+                PaneId::Claude | PaneId::LazyGit | PaneId::Terminal
+            ) {
+                self.open_save_scrollback_dialog();
+            }
+            return true;
+        }
+
         // Shift+F9 or Ctrl+F9: Copy last N lines with interactive count input
         if key.code == KeyCode::F(9)
             && (key.modifiers.contains(KeyModifiers::SHIFT)
@@ -133,12 +187,11 @@ impl App {
             if self.active_pane == PaneId::FileBrowser {
                 if let Some(entry) = self.file_browser.selected_entry() {
                     if entry.is_dir && entry.name != ".." {
-                        self.export_chooser = crate::types::ExportChooserState {
-                            visible: true,
-                            source_path: entry.path.clone(),
-                            selected: 0,
-                            is_batch: true,
-                        };
+                        self.export_chooser = crate::types::ExportChooserState::open(
+                            entry.path.clone(),
+                            true,
+                            &self.config.document,
+                        );
                         return true;
                     }
                 }
@@ -146,12 +199,11 @@ impl App {
             // Priority 2: Preview has a Markdown file open (existing single-file behavior)
             if let Some(path) = &self.preview.current_file {
                 if self.preview.is_markdown {
-                    self.export_chooser = crate::types::ExportChooserState {
-                        visible: true,
-                        source_path: path.clone(),
-                        selected: 0,
-                        is_batch: false,
-                    };
+                    self.export_chooser = crate::types::ExportChooserState::open(
+                        path.clone(),
+                        false,
+                        &self.config.document,
+                    );
                     return true;
                 }
             }
@@ -1,7 +1,7 @@
 //! Keyboard event dispatch.
 //!
 //! Splits per-context into submodules:
-//! - [`dialogs`] — overlay key handlers (fuzzy finder, update, dialogs, menu, about, help, permission mode, claude startup)
+//! - [`dialogs`] — overlay key handlers (fuzzy finder, context menu, prompt picker, history, update, dialogs, menu, about, help, permission mode, claude startup)
 //! - [`global`] — keys that fire regardless of active pane (F12/F10/F7/F9/F11, Ctrl+P/O/X/E, F8, Ctrl+Shift+W)
 //! - [`preview`]    — preview-pane handler (search, edit mode, read-only)
 //! - [`terminal`]    — terminal-pane handler (Claude/LazyGit/User)
@@ -33,6 +33,24 @@ impl App {
             return;
         }
 
+        // "Send to Claude" context menu
+        if self.context_menu.visible {
+            self.handle_context_menu_key(key);
+            return;
+        }
+
+        // Prompt library picker
+        if self.prompt_picker.visible {
+            self.handle_prompt_picker_key(key);
+            return;
+        }
+
+        // Claude history browser
+        if self.history.visible {
+            self.handle_history_key(key);
+            return;
+        }
+
         // Update dialog handling (high priority)
         if self.update_state.show_dialog {
             self.handle_update_dialog_key(key);
@@ -89,6 +107,24 @@ impl App {
             return;
         }
 
+        // Scrollback search bar of the focused terminal pane (before the
+        // global shortcuts so Ctrl+P/Ctrl+N navigate instead of opening pickers)
+        if self.terminal_search.pane == Some(self.active_pane)
+            && self.handle_terminal_search_key(key)
+        {
+            return;
+        }
+
+        // Copy mode of the focused terminal pane
+        if self.copy_mode.pane == Some(self.active_pane) && self.handle_copy_mode_key(key) {
+            return;
+        }
+
+        // Command-block navigation of the focused terminal pane
+        if self.block_mode.pane == Some(self.active_pane) && self.handle_block_mode_key(key) {
+            return;
+        }
+
         // Terminal-pane passthrough (tmux-style prefix). When the User Terminal
         // is focused and a prefix is configured, keys go straight to the PTY so
         // TUI apps (nano, mc, vim) work; Workbench commands are reached via the
@@ -164,19 +200,11 @@ impl App {
 
     pub(super) fn handle_paste_event(&mut self, text: String) {
         match self.active_pane {
-            PaneId::Claude => {
-                // Claude CLI doesn't understand bracketed paste sequences
-                // Send text directly - for multiline, user must use \ continuation
-                if let Some(pty) = self.terminals.get_mut(&PaneId::Claude) {
-                    let _ = pty.write_input(text.as_bytes());
-                }
-            }
-            PaneId::LazyGit | PaneId::Terminal => {
+            PaneId::Claude | PaneId::LazyGit | PaneId::Terminal => {
+                // Bracketed (ESC[200~ … ESC[201~) when the child enabled
+                // DECSET 2004, so multi-line pastes don't submit early
                 if let Some(pty) = self.terminals.get_mut(&self.active_pane) {
-                    // Wrap in bracketed paste escape sequences
-                    // \x1b[200~ = start paste, \x1b[201~ = end paste
-                    let bracketed = format!("\x1b[200~{}\x1b[201~", text);
-                    let _ = pty.write_input(bracketed.as_bytes());
+                    let _ = pty.write_paste(&text);
                 }
             }
             PaneId::Preview => {
@@ -339,9 +367,9 @@ impl App {
             return true;
         }
 
-        // PTY exited & no auto-restart: Enter restarts; swallow everything else.
+        // PTY exited: Enter restarts now; swallow everything else.
         if let Some(pty) = self.terminals.get(&self.active_pane) {
-            if pty.has_exited() && !self.config.pty.auto_restart {
+            if pty.has_exited() {
                 if key.code == KeyCode::Enter {
                     self.restart_single_pty(self.active_pane);
                 }
@@ -402,6 +430,11 @@ impl App {
                 }
             }
             KeyCode::Char('c') => self.copy_last_command_output(),
+            KeyCode::Char('/') => self.open_terminal_search(),
+            KeyCode::Char('[') => self.open_copy_mode(),
+            KeyCode::Up => self.open_block_mode(),
+            KeyCode::Char('r') => self.toggle_recording(),
+            KeyCode::Char('w') => self.open_save_scrollback_dialog(),
             // Unknown command (incl. Esc) — already disarmed, ignore.
             _ => {}
         }
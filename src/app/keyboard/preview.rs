@@ -1,6 +1,7 @@
 //! Preview-pane key handling — search/replace overlay, edit-mode bindings
 //! (Ctrl+S save, F-keys block ops, autosave toggle, MC-style edit), and
-//! read-only navigation (j/k/h/l, page, search jump, terminal-selection).
+//! read-only navigation (j/k/h/l, page, search jump, terminal-selection)
+//! and the asciicast player controls.
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 
@@ -319,6 +320,44 @@ impl App {
             return;
         }
 
+        // asciicast player controls
+        if let Some(player) = self.preview.cast.as_mut() {
+            let consumed = match key.code {
+                KeyCode::Char(' ') => {
+                    player.toggle_pause();
+                    true
+                }
+                KeyCode::Left | KeyCode::Char('h') => {
+                    player.seek(player.position() - 5.0);
+                    true
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    player.seek(player.position() + 5.0);
+                    true
+                }
+                KeyCode::Char('+') => {
+                    player.speed = (player.speed * 2.0).min(16.0);
+                    true
+                }
+                KeyCode::Char('-') => {
+                    player.speed = (player.speed / 2.0).max(0.25);
+                    true
+                }
+                KeyCode::Home | KeyCode::Char('0') => {
+                    player.seek(0.0);
+                    true
+                }
+                KeyCode::End => {
+                    player.seek(player.duration());
+                    true
+                }
+                _ => false,
+            };
+            if consumed {
+                return;
+            }
+        }
+
         let is_ctrl_s = (key.code == KeyCode::Char('s')
             && key.modifiers.contains(KeyModifiers::CONTROL))
             || key.code == KeyCode::Char('\x13');
@@ -1,6 +1,7 @@
 //! Terminal-pane key handling — Claude, LazyGit, and the user terminal.
-//! Handles terminal-selection mode (Ctrl+S), Shift+PageUp/Down scrollback,
-//! the SSH-image-paste hint, and forwards everything else to the PTY.
+//! Handles terminal-selection mode (Ctrl+S), scrollback search (Ctrl+F),
+//! command blocks (Ctrl+Up), Shift+PageUp/Down scrollback, the SSH-image-paste hint, and forwards
+//! everything else to the PTY.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
@@ -59,8 +60,23 @@ impl App {
             return;
         }
 
+        // Ctrl+F: Search the scrollback
+        if key.code == KeyCode::Char('f') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.open_terminal_search();
+            return;
+        }
+
+        // Ctrl+Up: Command blocks of the user terminal (shell integration)
+        if self.active_pane == PaneId::Terminal
+            && key.code == KeyCode::Up
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            self.open_block_mode();
+            return;
+        }
+
         if let Some(pty) = self.terminals.get(&self.active_pane) {
-            if pty.has_exited() && !self.config.pty.auto_restart {
+            if pty.has_exited() {
                 if key.code == KeyCode::Enter {
                     self.restart_single_pty(self.active_pane);
                 }
@@ -0,0 +1,300 @@
+//! Clickable links in the terminal panes: URLs and `path[:line[:col]]`
+//! references found in the rendered screen, plus OSC 8 hyperlinks emitted
+//! by the child. Hovering underlines a link; Ctrl+click opens URLs in the
+//! browser and files in the Preview at the referenced line.
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unicode_width::UnicodeWidthStr;
+
+use crate::browser;
+use crate::types::{EditorMode, LinkTarget, PaneId, PaneLink};
+
+use super::App;
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?:https?|file)://[^\s<>"'`]+"#).unwrap());
+
+/// `path[:line[:col]]`, optionally starting with `/`, `./`, `../` or `~/`
+static PATH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:~/|\.{0,2}/)?[\w.@+-]+(?:/[\w.@+-]+)*(?::(\d+))?(?::(\d+))?").unwrap()
+});
+
+/// Hovered cell as (pane, row, col) plus the content generation and the
+/// view top it was resolved at
+pub(super) type LinkHoverKey = (PaneId, u16, u16, u64, usize);
+
+/// Drop trailing sentence punctuation and unbalanced closing brackets
+fn trim_url(mut url: &str) -> &str {
+    while let Some(last) = url.chars().last() {
+        let unbalanced = |open: char| url.matches(open).count() < url.matches(last).count();
+        let strip = match last {
+            '.' | ',' | ';' | ':' | '!' | '?' => true,
+            ')' => unbalanced('('),
+            ']' => unbalanced('['),
+            '}' => unbalanced('{'),
+            _ => false,
+        };
+        if !strip {
+            break;
+        }
+        url = &url[..url.len() - 1];
+    }
+    url
+}
+
+/// All link candidates in one screen line (as displayed, see
+/// `PseudoTerminal::extract_row_text`) as `(start_col, end_col, target)`,
+/// columns counted in terminal cells (wide characters take two). File paths
+/// are returned as written; they still have to be resolved against a
+/// directory.
+pub(crate) fn find_links(text: &str) -> Vec<(usize, usize, LinkTarget)> {
+    let col = |byte: usize| text[..byte].width();
+    let mut links = Vec::new();
+    let mut urls = Vec::new();
+
+    for m in URL_RE.find_iter(text) {
+        let url = trim_url(m.as_str());
+        urls.push(m.start()..m.start() + url.len());
+        let start = col(m.start());
+        links.push((start, start + url.width(), LinkTarget::Url(url.to_string())));
+    }
+
+    for caps in PATH_RE.captures_iter(text) {
+        let m = caps.get(0).expect("group 0 always matches");
+        if urls.iter().any(|u| m.start() < u.end && u.start < m.end()) {
+            continue;
+        }
+        // A trailing `:col` is part of the link but not needed to open it
+        let line = caps.get(1).and_then(|n| n.as_str().parse().ok());
+        let path_end = caps.get(1).map_or(m.end(), |n| n.start() - 1);
+        let mut path = &text[m.start()..path_end];
+        if line.is_none() {
+            // "see src/main.rs." — the period ends the sentence
+            path = path.trim_end_matches('.');
+        }
+        // Plain words are not paths; "." and ".." on their own neither
+        let looks_like_path = path.contains('/') || path.contains('.');
+        if !looks_like_path || path.chars().all(|c| c == '.' || c == '/') {
+            continue;
+        }
+        let end = if line.is_some() {
+            m.end()
+        } else {
+            m.start() + path.len()
+        };
+        links.push((
+            col(m.start()),
+            col(end),
+            LinkTarget::File {
+                path: PathBuf::from(path),
+                line,
+            },
+        ));
+    }
+
+    links.sort_by_key(|(start, _, _)| *start);
+    links
+}
+
+/// Link candidate covering cell column `col` of `text`
+pub(crate) fn link_in_line(text: &str, col: usize) -> Option<(usize, usize, LinkTarget)> {
+    find_links(text)
+        .into_iter()
+        .find(|(start, end, _)| (*start..*end).contains(&col))
+}
+
+/// Existing file for a path reference: absolute, `~/…` or relative to the
+/// first of `bases` that contains it
+fn resolve_file(path: &Path, bases: &[&Path]) -> Option<PathBuf> {
+    let path = match path.strip_prefix("~") {
+        Ok(rest) => dirs::home_dir()?.join(rest),
+        Err(_) => path.to_path_buf(),
+    };
+    if path.is_absolute() {
+        return path.is_file().then_some(path);
+    }
+    bases
+        .iter()
+        .map(|base| base.join(&path))
+        .find(|candidate| candidate.is_file())
+}
+
+impl App {
+    /// Link at visible cell (`row`, `col`) of a terminal pane: an OSC 8
+    /// hyperlink first, otherwise a URL or an existing `path[:line[:col]]`
+    pub(crate) fn link_at(&self, pane: PaneId, row: u16, col: u16) -> Option<PaneLink> {
+        let pty = self.terminals.get(&pane)?;
+        let (top, _) = pty.view_origin();
+
+        if let Some((start, end, uri)) = pty.hyperlink_at(top + row as usize, col) {
+            let file = uri
+                .strip_prefix("file://")
+                // Skip the host part: file://host/path
+                .and_then(|rest| rest.find('/').map(|slash| PathBuf::from(&rest[slash..])))
+                .filter(|path| path.is_file());
+            let target = match file {
+                Some(path) => LinkTarget::File { path, line: None },
+                None => LinkTarget::Url(uri),
+            };
+            return Some(PaneLink {
+                row,
+                start_col: start,
+                end_col: end,
+                target,
+            });
+        }
+
+        let text = pty.extract_row_text(row);
+        let (start, end, target) = link_in_line(&text, col as usize)?;
+        let target = match target {
+            LinkTarget::File { path, line } => {
+                // The shell's own directory (OSC 7) first
+                let shell_cwd = pty.cwd();
+                let claude_cwd = self
+                    .claude_launch
+                    .as_ref()
+                    .filter(|_| pane == PaneId::Claude)
+                    .map(|(_, cwd)| cwd.as_path());
+                let bases: Vec<&Path> = shell_cwd
+                    .as_deref()
+                    .into_iter()
+                    .chain(claude_cwd)
+                    .chain([
+                        self.file_browser.current_dir.as_path(),
+                        self.file_browser.root_dir.as_path(),
+                    ])
+                    .collect();
+                LinkTarget::File {
+                    path: resolve_file(&path, &bases)?,
+                    line,
+                }
+            }
+            url => url,
+        };
+        Some(PaneLink {
+            row,
+            start_col: start as u16,
+            end_col: end as u16,
+            target,
+        })
+    }
+
+    /// Resolve the link under `link_hover` for the renderer. Finding it runs
+    /// regexes and checks the filesystem, so the result is kept until the
+    /// hovered cell, the scroll position or the buffer content changes.
+    pub(super) fn refresh_link_hover(&mut self) {
+        let key = self.link_hover.and_then(|(pane, row, col)| {
+            let pty = self.terminals.get(&pane)?;
+            Some((
+                pane,
+                row,
+                col,
+                pty.content_generation(),
+                pty.view_origin().0,
+            ))
+        });
+        if self.link_hover_cache.as_ref().map(|(cached, _)| *cached) == key {
+            return;
+        }
+        self.link_hover_cache = key.map(|key| {
+            let (pane, row, col, _, _) = key;
+            (key, self.link_at(pane, row, col))
+        });
+    }
+
+    /// Link under the mouse pointer in `pane`, as of the last
+    /// [`Self::refresh_link_hover`]
+    pub(crate) fn hovered_link(&self, pane: PaneId) -> Option<&PaneLink> {
+        self.link_hover_cache
+            .as_ref()
+            .filter(|((hovered, ..), _)| *hovered == pane)
+            .and_then(|(_, link)| link.as_ref())
+    }
+
+    /// Ctrl+click: URLs go to the browser, files to the Preview at their line
+    pub(super) fn open_link(&mut self, target: LinkTarget) {
+        match target {
+            LinkTarget::Url(url) => {
+                if let Err(e) = browser::open_url_with_browser(&url, &self.config.ui.browser) {
+                    self.set_clipboard_error_flash(format!("Cannot open {}: {}", url, e));
+                }
+            }
+            LinkTarget::File { path, line } => {
+                if self.preview.mode == EditorMode::Edit {
+                    if self.preview.modified {
+                        self.set_clipboard_error_flash("Preview has unsaved changes".to_string());
+                        return;
+                    }
+                    self.preview.exit_edit_mode(true);
+                }
+                self.show_preview = true;
+                self.preview.load_file(path, &self.syntax_manager);
+                let line = line.unwrap_or(1).saturating_sub(1);
+                self.preview.scroll = line.min(u16::MAX as usize) as u16;
+                self.active_pane = PaneId::Preview;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, line: Option<usize>) -> LinkTarget {
+        LinkTarget::File {
+            path: PathBuf::from(path),
+            line,
+        }
+    }
+
+    #[test]
+    fn finds_urls_and_path_references() {
+        let links = find_links("  --> src/app/pty.rs:213:9 (see https://docs.rs/vt100).");
+        assert_eq!(
+            links,
+            vec![
+                (6, 26, file("src/app/pty.rs", Some(213))),
+                (32, 53, LinkTarget::Url("https://docs.rs/vt100".to_string())),
+            ]
+        );
+
+        // Sentence punctuation is not part of a path, plain words are no paths
+        assert_eq!(
+            find_links("edit Cargo.toml. then run tests"),
+            vec![(5, 15, file("Cargo.toml", None))]
+        );
+        assert_eq!(
+            link_in_line("ä ./a/b.rs:3", 4),
+            Some((2, 12, file("./a/b.rs", Some(3))))
+        );
+        assert_eq!(link_in_line("ä ./a/b.rs:3", 0), None);
+    }
+
+    #[test]
+    fn link_columns_are_terminal_cells() {
+        assert_eq!(
+            find_links("日本 src/main.rs:3 ✓ https://a.b"),
+            vec![
+                (5, 18, file("src/main.rs", Some(3))),
+                (21, 32, LinkTarget::Url("https://a.b".to_string())),
+            ]
+        );
+        assert_eq!(
+            link_in_line("日本 src/main.rs", 6),
+            Some((5, 16, file("src/main.rs", None)))
+        );
+    }
+
+    #[test]
+    fn url_trailing_brackets_only_when_unbalanced() {
+        assert_eq!(
+            trim_url("https://en.wikipedia.org/wiki/Rust_(language))."),
+            "https://en.wikipedia.org/wiki/Rust_(language)"
+        );
+        assert_eq!(trim_url("https://a.b/c],"), "https://a.b/c");
+    }
+}
@@ -1,12 +1,22 @@
+mod claude_input;
 mod clipboard;
+mod command_blocks;
+mod copy_mode;
 mod drawing;
 mod file_ops;
 mod git_ops;
 mod job_state;
 mod keyboard;
+mod links;
 mod mouse;
+mod notify;
 mod pty;
+mod pty_size;
+mod recording;
+mod restart;
+mod scrollback;
 mod ssh_paste;
+mod terminal_search;
 mod update;
 
 pub(crate) use job_state::{JobState, PollOutcome};
@@ -30,9 +40,11 @@ use crate::ui::syntax::SyntaxManager;
 
 use crate::setup::wizard::WizardState;
 use crate::ui::about::AboutState;
+use crate::ui::context_menu::ContextMenu;
 use crate::ui::dialog::Dialog;
 use crate::ui::fuzzy_finder::FuzzyFinder;
 use crate::ui::menu::MenuBar;
+use crate::ui::prompt_picker::PromptPicker;
 use crate::ui::settings::SettingsState;
 use crate::ui::update_dialog::{UpdateDialogAreas, UpdateDialogButton};
 use crate::update::{UpdateCheckResult, UpdateResult, UpdateState};
@@ -89,6 +101,9 @@ pub struct App {
     pub menu: MenuBar,
     pub dialog: Dialog,
     pub fuzzy_finder: FuzzyFinder,
+    pub prompt_picker: PromptPicker,
+    pub context_menu: ContextMenu,
+    pub history: ui::history::HistoryBrowser,
     pub syntax_manager: SyntaxManager,
     pub wizard: WizardState,
     pub settings: SettingsState,
@@ -106,11 +121,25 @@ pub struct App {
     pub claude_permission_mode: ClaudePermissionMode,
     // Whether Claude PTY is pending (waiting for permission mode selection)
     pub claude_pty_pending: bool,
+    /// Startup options and directory of the running Claude PTY (replayed on restart)
+    claude_launch: Option<(pty::StartupOptions, std::path::PathBuf)>,
+    /// Backoff / crash-loop state of exited PTY panes
+    restart_trackers: HashMap<PaneId, restart::RestartTracker>,
     // Double-click tracking
     last_click_time: std::time::Instant,
     last_click_idx: Option<usize>,
     // Terminal line selection for copying to Claude
     pub terminal_selection: TerminalSelection,
+    // Scrollback search bar of a terminal pane
+    pub terminal_search: crate::types::TerminalSearchState,
+    // Vim-style copy mode of a terminal pane
+    pub copy_mode: crate::types::CopyModeState,
+    // Command-block navigation of a terminal pane
+    pub block_mode: crate::types::CommandBlockState,
+    // Terminal-pane cell under the mouse pointer (pane, row, col) for link hover
+    pub link_hover: Option<(PaneId, u16, u16)>,
+    // Link found at `link_hover`, cached until the cell, view or content changes
+    link_hover_cache: Option<(links::LinkHoverKey, Option<crate::types::PaneLink>)>,
     // Drag and drop state for file paths
     pub drag_state: DragState,
     // Mouse-based text selection in terminal panes
@@ -142,6 +171,8 @@ pub struct App {
     pub files_pane_height: u16,
     // Interactive pane resizing state
     pub resize_state: ResizeState,
+    /// Screen area and debounced PTY resizes
+    pty_sizer: pty_size::PtySizer,
     pub border_areas: BorderAreas,
     // Flash state for autosave "✓ SAVED" indicator (2s duration)
     pub last_autosave_time: Option<std::time::Instant>,
@@ -151,6 +182,11 @@ pub struct App {
     pub copy_flash_message: Option<String>,
     /// Short-lived footer error flash (3 s) for failed clipboard ops.
     pub clipboard_error_flash: Option<(String, std::time::Instant)>,
+    /// Footer flash (5 s) for Claude notifications.
+    pub notification_flash: Option<(String, std::time::Instant)>,
+    /// Claude pane title badge for the last event, until the pane is focused
+    pub claude_badge: Option<crate::types::ClaudeEvent>,
+    pub claude_watch: notify::ClaudeWatch,
     /// Startup banner shown when no Linux clipboard helpers are present.
     /// Auto-hides after 10 s or when dismissed via Esc.
     pub clipboard_warning: Option<(String, std::time::Instant)>,
@@ -166,6 +202,8 @@ pub struct App {
     pub dependency_report: crate::setup::DependencyReport,
     // Temp files created for browser previews (auto-deleted on drop via NamedTempFile)
     pub temp_preview_files: Vec<tempfile::NamedTempFile>,
+    // Live preview server (localhost, auto-reload); started on first preview
+    pub preview_server: Option<crate::browser::preview_server::PreviewServer>,
     // Export format chooser (Ctrl+X on Markdown files or directories)
     pub export_chooser: crate::types::ExportChooserState,
     // Async job: PDF export (single file or batch folder)
@@ -175,8 +213,17 @@ pub struct App {
 
 impl App {
     pub fn new(config: Config, session: SessionState, fake_version: Option<String>) -> Self {
-        let rows = 24;
-        let cols = 80;
+        // Spawn every PTY at its pane's size in the startup layout
+        let pty_sizer = pty_size::PtySizer::new();
+        let visible = pty_size::PaneVisibility {
+            file_browser: config.ui.show_file_browser,
+            preview: config.ui.show_preview,
+            lazygit: config.ui.show_lazygit,
+            terminal: config.ui.show_terminal,
+            preview_maximized: false,
+        };
+        let pty_size =
+            |pane| pty_size::pane_pty_size(pty_sizer.area(), pane, visible, &config.layout);
 
         let file_browser = FileBrowserState::new(config.file_browser.show_hidden);
         let cwd = file_browser.current_dir.clone();
@@ -186,6 +233,7 @@ impl App {
         let mut claude_pty_pending = false;
         let mut permission_mode_dialog = ui::permission_mode::PermissionModeState::default();
         let mut claude_permission_mode = ClaudePermissionMode::Default;
+        let mut claude_launch = None;
         // Determine if we should show permission mode dialog
         // Don't show if wizard needs to run first (first-time setup)
         let should_show_permission_dialog =
@@ -220,13 +268,16 @@ impl App {
                 session_name: config.claude.default_session_name.clone(),
                 worktree: config.claude.default_worktree.clone(),
                 remote_control: config.claude.remote_control,
+                resume: String::new(),
             };
             let claude_cmd = Self::build_claude_command(&config, &opts);
             claude_command_str = claude_cmd.join(" ");
 
+            let (rows, cols) = pty_size(PaneId::Claude);
             match PseudoTerminal::new(&claude_cmd, rows, cols, &cwd) {
                 Ok(pty) => {
                     terminals.insert(PaneId::Claude, pty);
+                    claude_launch = Some((opts, cwd.clone()));
                 }
                 Err(e) => {
                     claude_error = Some(format!(
@@ -245,6 +296,7 @@ impl App {
             } else {
                 config.pty.lazygit_command.clone()
             };
+            let (rows, cols) = pty_size(PaneId::LazyGit);
             match PseudoTerminal::new(&lazygit_cmd, rows, cols, &cwd) {
                 Ok(pty) => {
                     terminals.insert(PaneId::LazyGit, pty);
@@ -262,11 +314,9 @@ impl App {
         // 3. User Terminal (lazy-init: spawn only if pane is configured to be visible)
         let mut terminal_error: Option<String> = None;
         if config.ui.show_terminal {
-            let shell = &config.terminal.shell_path;
-            let args = &config.terminal.shell_args;
-            let mut cmd = vec![shell.clone()];
-            cmd.extend(args.clone());
+            let cmd = crate::shell_integration::terminal_command(&config.terminal);
 
+            let (rows, cols) = pty_size(PaneId::Terminal);
             match PseudoTerminal::new(&cmd, rows, cols, &cwd) {
                 Ok(pty) => {
                     terminals.insert(PaneId::Terminal, pty);
@@ -313,6 +363,9 @@ impl App {
             menu: MenuBar::default(),
             dialog: Dialog::default(),
             fuzzy_finder: FuzzyFinder::default(),
+            prompt_picker: PromptPicker::default(),
+            context_menu: ContextMenu::default(),
+            history: ui::history::HistoryBrowser::default(),
             syntax_manager,
             wizard: WizardState::new(),
             settings: SettingsState::new(),
@@ -325,9 +378,16 @@ impl App {
             permission_mode_dialog,
             claude_permission_mode,
             claude_pty_pending,
+            claude_launch,
+            restart_trackers: HashMap::new(),
             last_click_time: std::time::Instant::now(),
             last_click_idx: None,
             terminal_selection: TerminalSelection::default(),
+            terminal_search: crate::types::TerminalSearchState::default(),
+            copy_mode: crate::types::CopyModeState::default(),
+            block_mode: crate::types::CommandBlockState::default(),
+            link_hover: None,
+            link_hover_cache: None,
             drag_state: DragState::default(),
             mouse_selection: MouseSelection::default(),
             git_remote: GitRemoteState::default(),
@@ -343,17 +403,22 @@ impl App {
             preview_width: 80,
             files_pane_height: 0,
             resize_state: ResizeState::default(),
+            pty_sizer,
             border_areas: BorderAreas::default(),
             last_autosave_time: None,
             last_copy_time: None,
             copy_flash_lines: 0,
             copy_flash_message: None,
             clipboard_error_flash: None,
+            notification_flash: None,
+            claude_badge: None,
+            claude_watch: notify::ClaudeWatch::default(),
             clipboard_warning: None,
             clipboard_warning_dismissed: false,
             ssh_image_paste_hint: None,
             dependency_report: crate::setup::DependencyReport::default(),
             temp_preview_files: Vec::new(),
+            preview_server: None,
             export_chooser: crate::types::ExportChooserState::default(),
             export_job: JobState::default(),
             export_browser: None,
@@ -435,8 +500,9 @@ impl App {
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<bool> {
         while !self.should_quit {
-            // Check for exited PTYs and restart them with a shell
+            // Restart exited PTYs according to their restart policy
             self.check_and_restart_exited_ptys();
+            self.follow_terminal_cwd();
 
             // Auto-refresh file browser
             let refresh_interval = self.config.file_browser.auto_refresh_ms;
@@ -463,6 +529,13 @@ impl App {
             // Drain clipboard worker outcomes (footer flash on Failed)
             self.poll_clipboard_outcome();
 
+            // Bell / desktop notification when Claude finishes or needs input
+            self.poll_claude_notifications();
+
+            // Search hits and the hovered link follow new terminal output
+            self.refresh_terminal_search();
+            self.refresh_link_hover();
+
             terminal.draw(|frame| self.draw(frame))?;
 
             if event::poll(std::time::Duration::from_millis(16))? {
@@ -470,24 +543,7 @@ impl App {
                     Event::Mouse(mouse) => {
                         let size = terminal.size()?;
                         let area = Rect::new(0, 0, size.width, size.height);
-                        let (files, preview, claude, lazygit, terminal_rect, footer) =
-                            ui::layout::compute_layout(
-                                area,
-                                self.show_file_browser,
-                                self.show_terminal,
-                                self.show_lazygit,
-                                self.show_preview,
-                                self.preview_maximized,
-                                &self.config.layout,
-                            );
-                        let rects = LayoutRects {
-                            files,
-                            preview,
-                            claude,
-                            lazygit,
-                            terminal: terminal_rect,
-                            footer,
-                        };
+                        let rects = self.layout_rects(area);
                         self.handle_mouse_event(mouse, rects);
                     }
                     Event::Key(key) => {
@@ -502,6 +558,8 @@ impl App {
             }
         }
         // temp_preview_files (Vec<NamedTempFile>) auto-delete on App drop
+        // Stop the live preview server with the workbench
+        self.preview_server = None;
 
         Ok(self.should_restart)
     }
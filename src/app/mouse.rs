@@ -1,4 +1,4 @@
-use crossterm::event::{MouseEvent, MouseEventKind};
+use crossterm::event::{KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::layout::Rect;
 
 use crate::browser;
@@ -36,6 +36,18 @@ fn pane_at_position(rects: &super::LayoutRects, x: u16, y: u16) -> Option<PaneId
     }
 }
 
+/// Terminal pane and content cell (row, col) under (x, y), inside the border
+fn terminal_cell_at(rects: &super::LayoutRects, x: u16, y: u16) -> Option<(PaneId, u16, u16)> {
+    [
+        (PaneId::Claude, rects.claude),
+        (PaneId::LazyGit, rects.lazygit),
+        (PaneId::Terminal, rects.terminal),
+    ]
+    .into_iter()
+    .find(|(_, r)| x > r.x && x + 1 < r.x + r.width && y > r.y && y + 1 < r.y + r.height)
+    .map(|(pane, r)| (pane, y - r.y - 1, x - r.x - 1))
+}
+
 /// Direction of a mouse-wheel scroll over the file-browser pane.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ScrollDirection {
@@ -396,6 +408,7 @@ impl App {
                             session_name: self.config.claude.default_session_name.clone(),
                             worktree: self.config.claude.default_worktree.clone(),
                             remote_control: self.config.claude.remote_control,
+                            resume: String::new(),
                         };
                         self.init_claude_pty(opts);
                     }
@@ -513,6 +526,16 @@ impl App {
                     }
                 }
 
+                // Ctrl+click on a URL or file:line reference in a terminal pane
+                if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+                    if let Some(link) = terminal_cell_at(&rects, x, y)
+                        .and_then(|(pane, row, col)| self.link_at(pane, row, col))
+                    {
+                        self.open_link(link.target);
+                        return;
+                    }
+                }
+
                 if is_inside(files, x, y) {
                     self.active_pane = PaneId::FileBrowser;
                     // File browser layout: [list with borders] + [info bar (1 line)]
@@ -1176,6 +1199,10 @@ impl App {
                     self.paste_from_clipboard_to_active_pane();
                 }
             }
+            MouseEventKind::Moved => {
+                // Hovered links are underlined (see `refresh_link_hover`)
+                self.link_hover = terminal_cell_at(&rects, x, y);
+            }
             _ => {}
         }
     }
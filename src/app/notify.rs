@@ -0,0 +1,209 @@
+//! Notifications about the Claude pane while working elsewhere: output that
+//! goes idle after a busy stretch, a BEL from the Claude CLI, or a permission
+//! prompt on screen. Depending on `claude.notifications` they ring the host
+//! terminal's bell, send an OSC 9/777 desktop notification through the host
+//! terminal, flash the footer and badge the pane title.
+
+use std::io::Write;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+
+use crate::types::{ClaudeEvent, DesktopNotification, PaneId};
+
+use super::App;
+
+/// "Do you want to …?" followed by a numbered "1. Yes" option, possibly
+/// inside the box the CLI draws around the prompt
+static PERMISSION_QUESTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bdo you want to\b[^?]*\?").unwrap());
+static PERMISSION_OPTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[\s│]*(?:[❯>]\s*)?1\.\s+Yes\b").unwrap());
+
+/// Whether the screen shows a permission prompt of the Claude CLI
+pub(crate) fn is_permission_prompt(lines: &[String]) -> bool {
+    let Some(question) = lines.iter().position(|l| PERMISSION_QUESTION.is_match(l)) else {
+        return false;
+    };
+    lines[question..]
+        .iter()
+        .any(|l| PERMISSION_OPTION.is_match(l))
+}
+
+/// Whether output went quiet for `idle` after a stretch of at least
+/// `min_busy`. The stretch starts at `busy_since` or at the last input,
+/// whichever is later, so echoed typing does not count as work.
+pub(crate) fn finished_busy_stretch(
+    busy_since: Option<Instant>,
+    last_output: Option<Instant>,
+    last_input: Option<Instant>,
+    now: Instant,
+    idle: Duration,
+    min_busy: Duration,
+) -> bool {
+    let (Some(busy_since), Some(last_output)) = (busy_since, last_output) else {
+        return false;
+    };
+    let start = last_input.map_or(busy_since, |input| input.max(busy_since));
+    now.saturating_duration_since(last_output) >= idle
+        && last_output.saturating_duration_since(start) >= min_busy
+}
+
+/// What the watcher knows about the Claude pane between polls
+#[derive(Debug, Default)]
+pub struct ClaudeWatch {
+    /// Start of the current stretch of output
+    busy_since: Option<Instant>,
+    /// Output timestamp seen in the last poll
+    seen_output: Option<Instant>,
+    /// A permission prompt is on screen and was already reported
+    permission_shown: bool,
+}
+
+impl App {
+    /// Check the Claude pane for events; called once per frame
+    pub(super) fn poll_claude_notifications(&mut self) {
+        if self.active_pane == PaneId::Claude {
+            self.claude_badge = None;
+        }
+        let Some(pty) = self.terminals.get(&PaneId::Claude) else {
+            self.claude_watch = ClaudeWatch::default();
+            return;
+        };
+        let cfg = &self.config.claude.notifications;
+        let now = Instant::now();
+        let mut events = Vec::new();
+
+        if pty.take_bells() > 0 {
+            // The CLI rang: that ends the stretch as well
+            self.claude_watch.busy_since = None;
+            if cfg.on_bell {
+                events.push(ClaudeEvent::Bell);
+            }
+        }
+
+        let last_output = pty.last_output();
+        let watch = &mut self.claude_watch;
+        if last_output != watch.seen_output {
+            watch.seen_output = last_output;
+            if watch.busy_since.is_none() {
+                watch.busy_since = last_output;
+            }
+            let prompt = is_permission_prompt(&pty.extract_last_n_lines(pty.rows() as usize));
+            if prompt && !watch.permission_shown {
+                // Waiting for an answer is not idle time after a task
+                watch.busy_since = None;
+                if cfg.on_permission {
+                    events.push(ClaudeEvent::Permission);
+                }
+            }
+            watch.permission_shown = prompt;
+        } else if last_output
+            .is_some_and(|t| now.saturating_duration_since(t) >= Duration::from_secs(cfg.idle_secs))
+        {
+            let finished = finished_busy_stretch(
+                watch.busy_since,
+                last_output,
+                pty.last_input(),
+                now,
+                Duration::from_secs(cfg.idle_secs),
+                Duration::from_secs(cfg.min_busy_secs),
+            );
+            watch.busy_since = None;
+            if finished && cfg.on_finish {
+                events.push(ClaudeEvent::Finished);
+            }
+        }
+
+        for event in events {
+            self.notify_claude_event(event);
+        }
+    }
+
+    /// Deliver `event` on the configured channels
+    fn notify_claude_event(&mut self, event: ClaudeEvent) {
+        let cfg = &self.config.claude.notifications;
+        let message = event.message();
+
+        // Bell and desktop notification go straight to the host terminal
+        let mut out = String::new();
+        if cfg.bell {
+            out.push('\x07');
+        }
+        match cfg.desktop {
+            DesktopNotification::Off => {}
+            DesktopNotification::Osc9 => out.push_str(&format!("\x1b]9;{}\x07", message)),
+            DesktopNotification::Osc777 => {
+                out.push_str(&format!("\x1b]777;notify;Claude Workbench;{}\x07", message))
+            }
+        }
+        if !out.is_empty() {
+            let _ = std::io::stdout().write_all(out.as_bytes());
+            let _ = std::io::stdout().flush();
+        }
+
+        if cfg.flash {
+            self.notification_flash = Some((message.to_string(), Instant::now()));
+        }
+        if cfg.badge && self.active_pane != PaneId::Claude {
+            self.claude_badge = Some(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn detects_permission_prompts() {
+        let prompt = lines(
+            "│ Bash command                     │\n\
+             │   cargo test                     │\n\
+             │ Do you want to proceed?          │\n\
+             │ ❯ 1. Yes                         │\n\
+             │   2. No, and tell Claude what to do differently │",
+        );
+        assert!(is_permission_prompt(&prompt));
+        assert!(is_permission_prompt(&lines(
+            "Do you want to make this edit to main.rs?\n> 1. Yes\n  2. No"
+        )));
+
+        // A question in the conversation alone is no prompt
+        assert!(!is_permission_prompt(&lines(
+            "Do you want to add tests?\n1. Unit tests\n2. Integration tests"
+        )));
+        assert!(!is_permission_prompt(&lines("1. Yes\nDo you want to?")));
+    }
+
+    #[test]
+    fn finish_needs_idle_output_after_a_long_stretch() {
+        let t0 = Instant::now();
+        let secs = Duration::from_secs;
+        // 20 s of output, then quiet; idle after 3 s, tasks from 10 s on
+        let finished = |busy: Option<Instant>, input: Option<Instant>, now: u64| {
+            finished_busy_stretch(
+                busy,
+                Some(t0 + secs(20)),
+                input,
+                t0 + secs(now),
+                secs(3),
+                secs(10),
+            )
+        };
+
+        assert!(finished(Some(t0), None, 23));
+        // Still within the idle time
+        assert!(!finished(Some(t0), None, 22));
+        // Input late in the stretch: only 5 s of output since
+        assert!(!finished(Some(t0), Some(t0 + secs(15)), 23));
+        // Earlier input does not shorten the stretch
+        assert!(finished(Some(t0 + secs(2)), Some(t0 + secs(1)), 23));
+        assert!(!finished(None, None, 23));
+    }
+}
@@ -16,6 +16,8 @@ pub(crate) struct StartupOptions {
     pub session_name: String,
     pub worktree: String,
     pub remote_control: bool,
+    /// Session id to continue (`--resume`); empty = new session
+    pub resume: String,
 }
 
 impl App {
@@ -78,6 +80,16 @@ impl App {
             if opts.remote_control && !cmd.iter().any(|a| a == "--remote-control" || a == "--rc") {
                 cmd.push("--remote-control".to_string());
             }
+
+            // Resume a past session (history browser)
+            if !opts.resume.is_empty()
+                && !cmd
+                    .iter()
+                    .any(|a| a == "--resume" || a == "-r" || a == "--continue" || a == "-c")
+            {
+                cmd.push("--resume".to_string());
+                cmd.push(opts.resume.clone());
+            }
         }
 
         cmd
@@ -85,17 +97,21 @@ impl App {
 
     /// Initialize Claude PTY with the given startup options
     pub(super) fn init_claude_pty(&mut self, opts: StartupOptions) {
+        let cwd = self.file_browser.current_dir.clone();
+        self.init_claude_pty_in(opts, &cwd);
+    }
+
+    /// Initialize Claude PTY with the given startup options in `cwd`
+    fn init_claude_pty_in(&mut self, opts: StartupOptions, cwd: &Path) {
         self.claude_permission_mode = opts.permission_mode;
         self.claude_pty_pending = false;
 
         let claude_cmd = Self::build_claude_command(&self.config, &opts);
+        self.claude_launch = Some((opts, cwd.to_path_buf()));
         self.claude_command_used = claude_cmd.join(" ");
 
-        let cwd = self.file_browser.current_dir.clone();
-        let rows = 24;
-        let cols = 80;
-
-        match PseudoTerminal::new(&claude_cmd, rows, cols, &cwd) {
+        let (rows, cols) = self.pty_size(PaneId::Claude);
+        match PseudoTerminal::new(&claude_cmd, rows, cols, cwd) {
             Ok(pty) => {
                 self.terminals.insert(PaneId::Claude, pty);
                 self.claude_error = None;
@@ -109,6 +125,25 @@ impl App {
         }
     }
 
+    /// Restart the Claude PTY in `project`, resuming `session_id`.
+    /// Keeps the current permission mode; the other options come from config.
+    pub(super) fn resume_claude_session(&mut self, project: &Path, session_id: &str) {
+        self.terminals.remove(&PaneId::Claude);
+        self.claude_error = None;
+
+        let opts = StartupOptions {
+            permission_mode: self.claude_permission_mode,
+            model: self.config.claude.default_model,
+            effort: self.config.claude.default_effort,
+            session_name: String::new(),
+            worktree: String::new(),
+            remote_control: self.config.claude.remote_control,
+            resume: session_id.to_string(),
+        };
+        self.init_claude_pty_in(opts, project);
+        self.active_pane = PaneId::Claude;
+    }
+
     /// Initialize Claude PTY after wizard completion
     /// Shows permission mode dialog if configured, otherwise starts Claude directly
     pub(super) fn init_claude_after_wizard(&mut self) {
@@ -140,6 +175,7 @@ impl App {
                 session_name: self.config.claude.default_session_name.clone(),
                 worktree: self.config.claude.default_worktree.clone(),
                 remote_control: self.config.claude.remote_control,
+                resume: String::new(),
             };
             self.init_claude_pty(opts);
             self.active_pane = PaneId::Claude;
@@ -202,6 +238,29 @@ impl App {
         }
     }
 
+    /// Let the file browser follow `cd` in the Terminal pane, as reported
+    /// by the shell via OSC 7 (`file_browser.follow_terminal_cwd`)
+    pub(super) fn follow_terminal_cwd(&mut self) {
+        let Some(dir) = self
+            .terminals
+            .get(&PaneId::Terminal)
+            .and_then(|pty| pty.take_cwd_change())
+        else {
+            return;
+        };
+        if !self.config.file_browser.follow_terminal_cwd
+            || !dir.is_dir()
+            || dir == self.file_browser.current_dir
+        {
+            return;
+        }
+        // No sync_terminals(): the shell is already there
+        self.file_browser.current_dir = dir;
+        self.file_browser.load_directory();
+        self.update_preview();
+        self.check_repo_change();
+    }
+
     /// Ensure a PTY exists for the given pane. Used for lazy-init of LazyGit/Terminal.
     ///
     /// - No-op if a PTY for `pane_id` is already in `terminals`.
@@ -216,15 +275,10 @@ impl App {
         }
 
         let cwd = self.file_browser.current_dir.clone();
-        let rows = 24;
-        let cols = 80;
+        let (rows, cols) = self.pty_size(pane_id);
 
         let cmd = match pane_id {
-            PaneId::Terminal => {
-                let mut c = vec![self.config.terminal.shell_path.clone()];
-                c.extend(self.config.terminal.shell_args.clone());
-                c
-            }
+            PaneId::Terminal => crate::shell_integration::terminal_command(&self.config.terminal),
             PaneId::LazyGit => {
                 if self.config.pty.lazygit_command.is_empty() {
                     vec!["lazygit".to_string()]
@@ -267,9 +321,7 @@ impl App {
     /// Restart LazyGit PTY in current directory
     pub(super) fn restart_lazygit_in_current_dir(&mut self) {
         let cwd = self.file_browser.current_dir.clone();
-        // Use default size, will be resized on first draw
-        let rows = 24;
-        let cols = 80;
+        let (rows, cols) = self.pty_size(PaneId::LazyGit);
 
         // Get lazygit command from config
         let lazygit_cmd = if self.config.pty.lazygit_command.is_empty() {
@@ -287,96 +339,44 @@ impl App {
         }
     }
 
-    pub(super) fn check_and_restart_exited_ptys(&mut self) {
-        // Skip if auto-restart is disabled
-        if !self.config.pty.auto_restart {
-            return;
-        }
-
-        let cwd = self.file_browser.current_dir.clone();
-        let rows = 24;
-        let cols = 80;
-
-        // Check each terminal PTY
-        let panes_to_restart: Vec<PaneId> = self
-            .terminals
-            .iter()
-            .filter(|(_, pty)| pty.has_exited())
-            .map(|(id, _)| *id)
-            .collect();
-
-        for pane_id in panes_to_restart {
-            // Skip restart for hidden lazy-init panes — leave the dead PTY removed
-            // so the slot stays empty until the user toggles the pane visible again.
-            let is_visible = match pane_id {
-                PaneId::LazyGit => self.show_lazygit,
-                PaneId::Terminal => self.show_terminal,
-                PaneId::Claude => true,
-                _ => false,
-            };
-            if !is_visible {
-                self.terminals.remove(&pane_id);
-                continue;
-            }
-
-            // Remove the old PTY
-            self.terminals.remove(&pane_id);
-
-            // Determine the command to restart based on pane type
-            let cmd = match pane_id {
-                PaneId::Claude => {
-                    if self.config.pty.claude_command.is_empty() {
-                        let mut cmd = vec![self.config.terminal.shell_path.clone()];
-                        cmd.extend(self.config.terminal.shell_args.clone());
-                        cmd
-                    } else {
-                        self.config.pty.claude_command.clone()
-                    }
-                }
-                PaneId::LazyGit => {
-                    if self.config.pty.lazygit_command.is_empty() {
-                        vec!["lazygit".to_string()]
-                    } else {
-                        self.config.pty.lazygit_command.clone()
-                    }
-                }
-                PaneId::Terminal => {
-                    let mut cmd = vec![self.config.terminal.shell_path.clone()];
-                    cmd.extend(self.config.terminal.shell_args.clone());
-                    cmd
-                }
-                _ => continue, // Skip non-terminal panes
-            };
-
-            // Start a fresh shell/process
-            if let Ok(new_pty) = PseudoTerminal::new(&cmd, rows, cols, &cwd) {
-                self.terminals.insert(pane_id, new_pty);
-            }
-        }
-    }
-
-    /// Restart a single PTY (manual restart when auto_restart is disabled)
-    pub(super) fn restart_single_pty(&mut self, pane_id: PaneId) {
-        // Don't restart hidden lazy-init panes — they will be re-spawned by
-        // ensure_pty_for_pane() the next time the user toggles them visible.
-        let is_visible = match pane_id {
+    /// Whether an exited PTY of `pane_id` should be respawned. Hidden
+    /// lazy-init panes are re-spawned by `ensure_pty_for_pane()` the next
+    /// time the user toggles them visible.
+    pub(super) fn is_pane_visible(&self, pane_id: PaneId) -> bool {
+        match pane_id {
             PaneId::LazyGit => self.show_lazygit,
             PaneId::Terminal => self.show_terminal,
             PaneId::Claude => true,
             _ => false,
-        };
-        if !is_visible {
+        }
+    }
+
+    /// Restart a single PTY on user request (Enter in an exited pane).
+    /// Resets the pane's backoff and crash-loop state.
+    pub(super) fn restart_single_pty(&mut self, pane_id: PaneId) {
+        self.restart_trackers.remove(&pane_id);
+        if !self.is_pane_visible(pane_id) {
             self.terminals.remove(&pane_id);
             return;
         }
+        self.respawn_pty(pane_id);
+    }
 
-        let cwd = self.file_browser.current_dir.clone();
-        let rows = 24;
-        let cols = 80;
-
-        // Remove the old PTY
+    /// Replace the PTY of `pane_id` with a fresh process. Claude replays the
+    /// startup options and directory it was launched with.
+    pub(super) fn respawn_pty(&mut self, pane_id: PaneId) {
         self.terminals.remove(&pane_id);
 
+        if pane_id == PaneId::Claude {
+            if let Some((opts, cwd)) = self.claude_launch.clone() {
+                self.init_claude_pty_in(opts, &cwd);
+                return;
+            }
+        }
+
+        let cwd = self.file_browser.current_dir.clone();
+        let (rows, cols) = self.pty_size(pane_id);
+
         // Determine the command to restart based on pane type
         let cmd = match pane_id {
             PaneId::Claude => {
@@ -395,11 +395,7 @@ impl App {
                     self.config.pty.lazygit_command.clone()
                 }
             }
-            PaneId::Terminal => {
-                let mut cmd = vec![self.config.terminal.shell_path.clone()];
-                cmd.extend(self.config.terminal.shell_args.clone());
-                cmd
-            }
+            PaneId::Terminal => crate::shell_integration::terminal_command(&self.config.terminal),
             _ => return, // Skip non-terminal panes
         };
 
@@ -423,8 +419,8 @@ impl App {
                     return;
                 }
             };
-            // Write to PTY (no newline - just insert the path)
-            let _ = pty.write_input(escaped.as_bytes());
+            // Insert as a paste (no newline; bracketed if the child enabled it)
+            let _ = pty.write_paste(&escaped);
         }
     }
 }
@@ -481,6 +477,7 @@ mod tests {
             session_name: String::new(),
             worktree: String::new(),
             remote_control: false,
+            resume: String::new(),
         }
     }
 
@@ -587,6 +584,7 @@ mod tests {
             session_name: "session1".to_string(),
             worktree: "feat".to_string(),
             remote_control: true,
+            resume: String::new(),
         };
         let cmd = App::build_claude_command(&cfg, &opts);
         assert_eq!(cmd[0], "claude");
@@ -602,4 +600,20 @@ mod tests {
         assert!(cmd.contains(&"feat".to_string()));
         assert!(cmd.contains(&"--remote-control".to_string()));
     }
+
+    #[test]
+    fn test_build_command_with_resume() {
+        let cfg = config_with_claude_command();
+        let mut opts = base_opts();
+        opts.resume = "abc-123".to_string();
+        let cmd = App::build_claude_command(&cfg, &opts);
+        let pos = cmd.iter().position(|a| a == "--resume").unwrap();
+        assert_eq!(cmd[pos + 1], "abc-123");
+
+        // An explicit --resume/--continue in claude_command wins
+        let mut cfg = config_with_claude_command();
+        cfg.pty.claude_command.push("--continue".to_string());
+        let cmd = App::build_claude_command(&cfg, &opts);
+        assert!(!cmd.iter().any(|a| a == "--resume"));
+    }
 }
@@ -0,0 +1,262 @@
+//! Central sizing of the terminal panes' PTYs. A pane's size follows from
+//! the layout whether or not it is visible, so processes start at their
+//! final size (no garbled first screen) and hidden panes are already right
+//! when shown. Layout changes in quick succession (border drags, window
+//! resizes) are coalesced into one `resize` per PTY once the layout has
+//! been stable for [`RESIZE_DEBOUNCE`].
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Rect;
+
+use crate::config::LayoutConfig;
+use crate::types::PaneId;
+use crate::ui;
+
+use super::{App, LayoutRects};
+
+/// Quiet time after the last layout change before PTYs are resized
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Screen size assumed when the host terminal cannot be queried
+const FALLBACK_AREA: Rect = Rect::new(0, 0, 80, 24);
+
+/// Which panes the layout shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PaneVisibility {
+    pub file_browser: bool,
+    pub preview: bool,
+    pub lazygit: bool,
+    pub terminal: bool,
+    pub preview_maximized: bool,
+}
+
+impl PaneVisibility {
+    /// The layout `pane` gets once shown: visible, preview not maximized
+    fn showing(self, pane: PaneId) -> Self {
+        Self {
+            lazygit: self.lazygit || pane == PaneId::LazyGit,
+            terminal: self.terminal || pane == PaneId::Terminal,
+            preview_maximized: false,
+            ..self
+        }
+    }
+}
+
+/// Pane rectangles of `area` for the panes in `visible`
+pub(crate) fn layout_rects(
+    area: Rect,
+    visible: PaneVisibility,
+    layout: &LayoutConfig,
+) -> LayoutRects {
+    let (files, preview, claude, lazygit, terminal, footer) = ui::layout::compute_layout(
+        area,
+        visible.file_browser,
+        visible.terminal,
+        visible.lazygit,
+        visible.preview,
+        visible.preview_maximized,
+        layout,
+    );
+    LayoutRects {
+        files,
+        preview,
+        claude,
+        lazygit,
+        terminal,
+        footer,
+    }
+}
+
+/// PTY size `(rows, cols)` of `pane` in `area`: its rectangle inside the
+/// borders, computed as if the pane were shown
+pub(crate) fn pane_pty_size(
+    area: Rect,
+    pane: PaneId,
+    visible: PaneVisibility,
+    layout: &LayoutConfig,
+) -> (u16, u16) {
+    let rects = layout_rects(area, visible.showing(pane), layout);
+    let rect = match pane {
+        PaneId::Claude => rects.claude,
+        PaneId::LazyGit => rects.lazygit,
+        _ => rects.terminal,
+    };
+    (
+        rect.height.saturating_sub(2).max(1),
+        rect.width.saturating_sub(2).max(1),
+    )
+}
+
+/// Screen area and the PTY sizes waiting for the debounce
+#[derive(Debug)]
+pub struct PtySizer {
+    area: Rect,
+    pending: HashMap<PaneId, (u16, u16)>,
+    /// When a pending size last changed
+    changed_at: Option<Instant>,
+}
+
+impl PtySizer {
+    /// Starts from the host terminal's size, until the first frame is drawn
+    pub fn new() -> Self {
+        let area = crossterm::terminal::size()
+            .ok()
+            .filter(|&(w, h)| w > 0 && h > 0)
+            .map_or(FALLBACK_AREA, |(w, h)| Rect::new(0, 0, w, h));
+        Self {
+            area,
+            pending: HashMap::new(),
+            changed_at: None,
+        }
+    }
+
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    pub fn set_area(&mut self, area: Rect) {
+        self.area = area;
+    }
+
+    /// Ask for `pane` to be `target`; `current` is the PTY's size now.
+    /// A new target restarts the debounce.
+    fn request(&mut self, pane: PaneId, target: (u16, u16), current: (u16, u16), now: Instant) {
+        if target == current {
+            // E.g. a border dragged back: nothing to do
+            self.pending.remove(&pane);
+        } else if self.pending.insert(pane, target) != Some(target) {
+            self.changed_at = Some(now);
+        }
+    }
+
+    /// The pending sizes, once the debounce has passed
+    fn take_due(&mut self, now: Instant) -> Vec<(PaneId, (u16, u16))> {
+        let settled = self
+            .changed_at
+            .is_some_and(|t| now.saturating_duration_since(t) >= RESIZE_DEBOUNCE);
+        if !settled {
+            return Vec::new();
+        }
+        self.changed_at = None;
+        self.pending.drain().collect()
+    }
+}
+
+impl App {
+    pub(super) fn pane_visibility(&self) -> PaneVisibility {
+        PaneVisibility {
+            file_browser: self.show_file_browser,
+            preview: self.show_preview,
+            lazygit: self.show_lazygit,
+            terminal: self.show_terminal,
+            preview_maximized: self.preview_maximized,
+        }
+    }
+
+    /// Pane rectangles of the current layout in `area`
+    pub(super) fn layout_rects(&self, area: Rect) -> LayoutRects {
+        layout_rects(area, self.pane_visibility(), &self.config.layout)
+    }
+
+    /// Size `(rows, cols)` to spawn the PTY of `pane` with
+    pub(super) fn pty_size(&self, pane: PaneId) -> (u16, u16) {
+        pane_pty_size(
+            self.pty_sizer.area(),
+            pane,
+            self.pane_visibility(),
+            &self.config.layout,
+        )
+    }
+
+    /// Bring every PTY to its pane's size, debounced; called once per frame
+    pub(super) fn sync_pty_sizes(&mut self) {
+        let now = Instant::now();
+        for pane in [PaneId::Claude, PaneId::LazyGit, PaneId::Terminal] {
+            let Some(pty) = self.terminals.get(&pane) else {
+                continue;
+            };
+            let target = self.pty_size(pane);
+            self.pty_sizer.request(pane, target, pty.size(), now);
+        }
+        for (pane, (rows, cols)) in self.pty_sizer.take_due(now) {
+            if let Some(pty) = self.terminals.get_mut(&pane) {
+                let _ = pty.resize(rows, cols);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHOWN: PaneVisibility = PaneVisibility {
+        file_browser: true,
+        preview: true,
+        lazygit: false,
+        terminal: true,
+        preview_maximized: false,
+    };
+
+    #[test]
+    fn hidden_panes_are_sized_as_if_shown() {
+        let area = Rect::new(0, 0, 200, 50);
+        let layout = LayoutConfig::default();
+        let terminal = pane_pty_size(area, PaneId::Terminal, SHOWN, &layout);
+        let rects = layout_rects(area, SHOWN, &layout);
+        assert_eq!(
+            terminal,
+            (rects.terminal.height - 2, rects.terminal.width - 2)
+        );
+
+        // Hidden or behind the maximized preview: same as when shown
+        let hidden = PaneVisibility {
+            terminal: false,
+            ..SHOWN
+        };
+        assert_eq!(
+            pane_pty_size(area, PaneId::Terminal, hidden, &layout),
+            terminal
+        );
+        let maximized = PaneVisibility {
+            preview_maximized: true,
+            ..SHOWN
+        };
+        assert_eq!(
+            pane_pty_size(area, PaneId::Terminal, maximized, &layout),
+            terminal
+        );
+        let (rows, cols) = pane_pty_size(area, PaneId::Claude, maximized, &layout);
+        assert!(rows > 1 && cols > 100);
+        // LazyGit shares the right column with the Terminal once shown
+        let (lazygit_rows, _) = pane_pty_size(area, PaneId::LazyGit, SHOWN, &layout);
+        assert!(lazygit_rows < terminal.0);
+    }
+
+    #[test]
+    fn resize_storm_is_coalesced() {
+        let mut sizer = PtySizer::new();
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        let current = (24, 80);
+
+        // Dragging: every step restarts the debounce
+        sizer.request(PaneId::Claude, (24, 90), current, t0);
+        sizer.request(PaneId::Claude, (24, 100), current, t0 + ms(60));
+        assert!(sizer.take_due(t0 + ms(120)).is_empty());
+        // The same target again does not
+        sizer.request(PaneId::Claude, (24, 100), current, t0 + ms(150));
+        assert_eq!(
+            sizer.take_due(t0 + ms(160)),
+            vec![(PaneId::Claude, (24, 100))]
+        );
+        assert!(sizer.take_due(t0 + ms(500)).is_empty());
+
+        // Dragged back to where it was: nothing to apply
+        sizer.request(PaneId::Claude, (24, 90), current, t0 + ms(600));
+        sizer.request(PaneId::Claude, current, current, t0 + ms(650));
+        assert!(sizer.take_due(t0 + ms(900)).is_empty());
+    }
+}
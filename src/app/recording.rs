@@ -0,0 +1,51 @@
+//! asciicast recording of the focused terminal pane (Shift+F5, `Ctrl+B r`
+//! in the Terminal pane). Recordings play back in the Preview pane.
+
+use std::time::{Instant, SystemTime};
+
+use crate::types::PaneId;
+
+use super::App;
+
+impl App {
+    /// Start recording the focused terminal pane, or stop its recording
+    pub(super) fn toggle_recording(&mut self) {
+        let pane = self.active_pane;
+        let Some(pty) = self.terminals.get(&pane) else {
+            self.set_clipboard_error_flash(
+                "Recording works in the Claude, LazyGit and Terminal panes".to_string(),
+            );
+            return;
+        };
+
+        let message = match pty.stop_recording() {
+            Some(Ok(path)) => format!("Recording saved: {}", path.display()),
+            Some(Err(e)) => {
+                self.set_clipboard_error_flash(format!("Recording failed: {:#}", e));
+                return;
+            }
+            None => {
+                let (name, title) = match pane {
+                    PaneId::Claude => ("claude", "Claude Code"),
+                    PaneId::LazyGit => ("lazygit", "LazyGit"),
+                    _ => ("terminal", "Terminal"),
+                };
+                let stamp = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs());
+                let cfg = &self.config.pty.recording;
+                let path = cfg
+                    .dir_path(&self.file_browser.root_dir)
+                    .join(format!("{}-{}.cast", name, stamp));
+                if let Err(e) = pty.start_recording(&path, title, cfg.record_input) {
+                    self.set_clipboard_error_flash(format!("Cannot record: {:#}", e));
+                    return;
+                }
+                format!("Recording {} (Shift+F5 stops)", path.display())
+            }
+        };
+        self.copy_flash_message = Some(message);
+        self.copy_flash_lines = 0;
+        self.last_copy_time = Some(Instant::now());
+    }
+}
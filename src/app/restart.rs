@@ -0,0 +1,229 @@
+//! Crash-aware restart of exited PTY panes.
+//!
+//! Each pane gets a [`RestartTracker`] that applies its [`RestartPolicy`]
+//! with exponential backoff and gives up after `max_restarts` restarts
+//! within `window_secs` (crash loop). The pane then stays stopped until the
+//! user presses Enter.
+
+use std::time::{Duration, Instant};
+
+use crate::config::RestartConfig;
+use crate::types::{PaneId, RestartPolicy};
+
+use super::App;
+
+/// Upper bound for the backoff delay
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RestartDecision {
+    /// Policy says no restart — leave the pane stopped
+    Stay,
+    /// Restart scheduled, backoff still running
+    Wait,
+    /// Restart now
+    Restart,
+    /// Crash-loop limit reached — stop restarting
+    GiveUp,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RestartTracker {
+    /// When the current exit was first noticed
+    exited_at: Option<Instant>,
+    /// Automatic restarts within the crash window
+    restarts: Vec<Instant>,
+    /// Time of the scheduled restart (for "Restarting in Ns…")
+    pub restart_at: Option<Instant>,
+    /// Restart count when the crash-loop limit was hit
+    pub gave_up: Option<usize>,
+}
+
+impl RestartTracker {
+    /// The process is running (again): forget the previous exit
+    pub fn alive(&mut self) {
+        self.exited_at = None;
+        self.restart_at = None;
+    }
+
+    /// Decide what to do with an exited pane. `success` is `None` while the
+    /// exit status is not yet known (treated as a failure).
+    pub fn decide(
+        &mut self,
+        policy: RestartPolicy,
+        config: &RestartConfig,
+        success: Option<bool>,
+        now: Instant,
+    ) -> RestartDecision {
+        if self.gave_up.is_some() {
+            return RestartDecision::Stay;
+        }
+        if !policy.restarts_after(success.unwrap_or(false)) {
+            self.restart_at = None;
+            return RestartDecision::Stay;
+        }
+
+        let window = Duration::from_secs(config.window_secs);
+        self.restarts
+            .retain(|at| now.saturating_duration_since(*at) < window);
+        if config.max_restarts > 0 && self.restarts.len() >= config.max_restarts as usize {
+            self.gave_up = Some(self.restarts.len());
+            self.restart_at = None;
+            return RestartDecision::GiveUp;
+        }
+
+        let exited_at = *self.exited_at.get_or_insert(now);
+        let restart_at = exited_at + backoff(config.backoff_ms, self.restarts.len());
+        if now < restart_at {
+            self.restart_at = Some(restart_at);
+            return RestartDecision::Wait;
+        }
+
+        self.restarts.push(now);
+        self.alive();
+        RestartDecision::Restart
+    }
+}
+
+/// `base_ms` doubled for every recent restart, capped at [`MAX_BACKOFF`]
+fn backoff(base_ms: u64, recent: usize) -> Duration {
+    let factor = 1u64 << recent.min(16);
+    Duration::from_millis(base_ms.saturating_mul(factor)).min(MAX_BACKOFF)
+}
+
+impl App {
+    /// Apply the per-pane restart policies to exited PTYs (called every loop)
+    pub(super) fn check_and_restart_exited_ptys(&mut self) {
+        let now = Instant::now();
+        let panes: Vec<PaneId> = self.terminals.keys().copied().collect();
+
+        for pane_id in panes {
+            let Some(pty) = self.terminals.get(&pane_id) else {
+                continue;
+            };
+            if !pty.has_exited() {
+                if let Some(tracker) = self.restart_trackers.get_mut(&pane_id) {
+                    tracker.alive();
+                }
+                continue;
+            }
+
+            // Hidden lazy-init panes: leave the dead PTY removed so the slot
+            // stays empty until the user toggles the pane visible again.
+            if !self.is_pane_visible(pane_id) {
+                self.terminals.remove(&pane_id);
+                self.restart_trackers.remove(&pane_id);
+                continue;
+            }
+
+            let success = pty.exit_status().map(|status| status.success());
+            let policy = self.config.pty.restart.policy(pane_id);
+            let decision = self.restart_trackers.entry(pane_id).or_default().decide(
+                policy,
+                &self.config.pty.restart,
+                success,
+                now,
+            );
+            if decision == RestartDecision::Restart {
+                self.respawn_pty(pane_id);
+            }
+        }
+    }
+
+    /// Time left until the scheduled restart of an exited pane
+    pub(crate) fn pending_restart(&self, pane_id: PaneId) -> Option<Duration> {
+        self.restart_trackers
+            .get(&pane_id)
+            .and_then(|tracker| tracker.restart_at)
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Number of restarts after which the crash-loop limit stopped the pane
+    pub(crate) fn restart_gave_up(&self, pane_id: PaneId) -> Option<usize> {
+        self.restart_trackers
+            .get(&pane_id)
+            .and_then(|tracker| tracker.gave_up)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RestartConfig {
+        RestartConfig {
+            backoff_ms: 100,
+            max_restarts: 2,
+            window_secs: 60,
+            ..RestartConfig::default()
+        }
+    }
+
+    #[test]
+    fn on_failure_ignores_clean_exit() {
+        let mut tracker = RestartTracker::default();
+        let now = Instant::now();
+        let decision = tracker.decide(RestartPolicy::OnFailure, &config(), Some(true), now);
+        assert_eq!(decision, RestartDecision::Stay);
+        let decision = tracker.decide(RestartPolicy::Never, &config(), Some(false), now);
+        assert_eq!(decision, RestartDecision::Stay);
+    }
+
+    #[test]
+    fn backoff_doubles_then_gives_up() {
+        let config = config();
+        let mut tracker = RestartTracker::default();
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+
+        let policy = RestartPolicy::OnFailure;
+        assert_eq!(
+            tracker.decide(policy, &config, Some(false), t0),
+            RestartDecision::Wait
+        );
+        assert_eq!(tracker.restart_at, Some(t0 + ms(100)));
+        assert_eq!(
+            tracker.decide(policy, &config, Some(false), t0 + ms(100)),
+            RestartDecision::Restart
+        );
+
+        // Second crash: delay doubled
+        let t1 = t0 + ms(200);
+        assert_eq!(
+            tracker.decide(policy, &config, None, t1),
+            RestartDecision::Wait
+        );
+        assert_eq!(tracker.restart_at, Some(t1 + ms(200)));
+        assert_eq!(
+            tracker.decide(policy, &config, None, t1 + ms(200)),
+            RestartDecision::Restart
+        );
+
+        // Third crash within the window: crash loop
+        let t2 = t1 + ms(500);
+        assert_eq!(
+            tracker.decide(policy, &config, Some(false), t2),
+            RestartDecision::GiveUp
+        );
+        assert_eq!(tracker.gave_up, Some(2));
+        assert_eq!(
+            tracker.decide(policy, &config, Some(false), t2),
+            RestartDecision::Stay
+        );
+    }
+
+    #[test]
+    fn restarts_outside_window_are_forgotten() {
+        let config = config();
+        let mut tracker = RestartTracker::default();
+        let t0 = Instant::now();
+        let policy = RestartPolicy::Always;
+        for i in 0..5u64 {
+            let t = t0 + Duration::from_secs(61 * i);
+            tracker.decide(policy, &config, Some(true), t);
+            let decision = tracker.decide(policy, &config, Some(true), t + Duration::from_secs(1));
+            assert_eq!(decision, RestartDecision::Restart);
+        }
+        assert_eq!(tracker.gave_up, None);
+    }
+}
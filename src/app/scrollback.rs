@@ -0,0 +1,101 @@
+//! Save the scrollback of the focused terminal pane to a file (Alt+F9,
+//! `Ctrl+B w` in the Terminal pane). The extension picks the format: `.txt`
+//! plain text, `.ansi` with colors, `.html` a styled page.
+
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+use crate::scrollback::ScrollbackFormat;
+use crate::types::PaneId;
+use crate::ui::dialog::{DialogAction, DialogType};
+
+use super::App;
+
+impl App {
+    /// Ask for the target file, prefilled with a name in the data directory
+    pub(super) fn open_save_scrollback_dialog(&mut self) {
+        let pane = self.active_pane;
+        if !self.terminals.contains_key(&pane) {
+            self.set_clipboard_error_flash(
+                "Saving scrollback works in the Claude, LazyGit and Terminal panes".to_string(),
+            );
+            return;
+        }
+        let name = match pane {
+            PaneId::Claude => "claude",
+            PaneId::LazyGit => "lazygit",
+            _ => "terminal",
+        };
+        let stamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let dir = crate::config::data_dir().map_or_else(
+            || self.file_browser.root_dir.clone(),
+            |d| d.join("scrollback"),
+        );
+        let value = dir
+            .join(format!("{}-scrollback-{}.txt", name, stamp))
+            .display()
+            .to_string();
+        self.dialog.dialog_type = DialogType::Input {
+            title: "Save scrollback (.txt · .ansi colors · .html)".to_string(),
+            cursor: value.chars().count(),
+            value,
+            action: DialogAction::SaveScrollback { pane },
+        };
+    }
+
+    /// Save the scrollback of `pane` to `target`, asking before an existing
+    /// file is replaced
+    pub(super) fn save_scrollback(&mut self, pane: PaneId, target: &str) {
+        let target = target.trim();
+        if target.is_empty() {
+            return;
+        }
+        let path = match target.strip_prefix('~') {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest.trim_start_matches('/')))
+                .unwrap_or_else(|| PathBuf::from(target)),
+            None => PathBuf::from(target),
+        };
+        if path.exists() {
+            self.dialog.dialog_type = DialogType::Confirm {
+                title: "File exists".to_string(),
+                message: format!("Overwrite {}?", path.display()),
+                action: DialogAction::OverwriteScrollback { pane, path },
+            };
+            return;
+        }
+        self.write_scrollback(pane, &path);
+    }
+
+    /// Write the scrollback of `pane` to `path` in the format of its extension
+    pub(super) fn write_scrollback(&mut self, pane: PaneId, path: &Path) {
+        let Some(pty) = self.terminals.get(&pane) else {
+            return;
+        };
+        let title = match pane {
+            PaneId::Claude => "Claude Code",
+            PaneId::LazyGit => "LazyGit",
+            _ => "Terminal",
+        };
+        let content = pty.export_scrollback(
+            ScrollbackFormat::from_path(path),
+            title,
+            &self.config.document,
+        );
+        let result = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, content));
+        if let Err(e) = result {
+            self.set_clipboard_error_flash(format!("Cannot save {}: {}", path.display(), e));
+            return;
+        }
+        self.copy_flash_message = Some(format!("Scrollback saved: {}", path.display()));
+        self.copy_flash_lines = 0;
+        self.last_copy_time = Some(Instant::now());
+        self.file_browser.refresh();
+    }
+}
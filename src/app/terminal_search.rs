@@ -0,0 +1,248 @@
+//! Scrollback search in the terminal panes (Ctrl+F, `Ctrl+B /` in the User
+//! Terminal): incremental plain-text or regex search over the whole vt100
+//! buffer, jumping between hits by moving the scrollback offset.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use regex::RegexBuilder;
+use unicode_width::UnicodeWidthStr;
+
+use super::App;
+
+/// Find all non-empty hits of `query` in `lines` (as returned by
+/// `PseudoTerminal::extract_all_text`) as `(line, start_col, end_col)`,
+/// columns counted in terminal cells (wide characters take two).
+pub(crate) fn find_matches(
+    lines: &[String],
+    query: &str,
+    regex: bool,
+    case_sensitive: bool,
+) -> Result<Vec<(usize, usize, usize)>, regex::Error> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = if regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let re = RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()?;
+
+    let mut matches = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        for m in re.find_iter(line).filter(|m| !m.is_empty()) {
+            let start = line[..m.start()].width();
+            let end = start + m.as_str().width();
+            matches.push((idx, start, end));
+        }
+    }
+    Ok(matches)
+}
+
+impl App {
+    /// Open the search bar for the active terminal pane
+    pub(super) fn open_terminal_search(&mut self) {
+        if self.terminals.contains_key(&self.active_pane) {
+            self.terminal_search.open(self.active_pane);
+        }
+    }
+
+    /// Keys while the search bar is open. Returns false for keys that should
+    /// keep their normal meaning (F-keys), true when consumed.
+    pub(super) fn handle_terminal_search_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::F(_) => return false,
+            KeyCode::Esc => self.close_terminal_search(),
+            // In copy mode Enter confirms like vim's `/` (then `n` / `N`)
+            KeyCode::Enter if self.copy_mode.pane.is_some() => self.close_terminal_search(),
+            // Older hit (upwards)
+            KeyCode::Enter | KeyCode::Up => self.step_terminal_match(false),
+            KeyCode::Char('p') if ctrl => self.step_terminal_match(false),
+            // Newer hit (downwards)
+            KeyCode::Down => self.step_terminal_match(true),
+            KeyCode::Char('n') if ctrl => self.step_terminal_match(true),
+            KeyCode::Char('r') if ctrl => {
+                self.terminal_search.regex = !self.terminal_search.regex;
+                self.run_terminal_search();
+            }
+            KeyCode::Char('i') if ctrl => self.toggle_terminal_search_case(),
+            KeyCode::Tab | KeyCode::Char('\x09') => self.toggle_terminal_search_case(),
+            KeyCode::Left => self.terminal_search.search.cursor_left(),
+            KeyCode::Right => self.terminal_search.search.cursor_right(),
+            KeyCode::Home => self.terminal_search.search.cursor_home(),
+            KeyCode::End => self.terminal_search.search.cursor_end(),
+            KeyCode::Backspace => {
+                self.terminal_search.search.delete_char_before();
+                self.run_terminal_search();
+            }
+            KeyCode::Delete => {
+                self.terminal_search.search.delete_char_at();
+                self.run_terminal_search();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.terminal_search.search.insert_char(c);
+                self.run_terminal_search();
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Close the search bar; in copy mode the query is kept for `n` / `N`
+    fn close_terminal_search(&mut self) {
+        let state = &self.terminal_search;
+        if self.copy_mode.pane.is_some() && !state.search.query.is_empty() {
+            self.copy_mode.last_search = Some((
+                state.search.query.clone(),
+                state.regex,
+                state.search.case_sensitive,
+            ));
+        }
+        self.terminal_search.close();
+    }
+
+    fn toggle_terminal_search_case(&mut self) {
+        let search = &mut self.terminal_search.search;
+        search.case_sensitive = !search.case_sensitive;
+        self.run_terminal_search();
+    }
+
+    /// Re-run the query against the current buffer and jump to the newest hit
+    fn run_terminal_search(&mut self) {
+        if self.update_terminal_matches() {
+            let state = &mut self.terminal_search.search;
+            state.current_match = state.matches.len().saturating_sub(1);
+            self.jump_to_terminal_match();
+        }
+    }
+
+    /// Called every frame: when new output (or a resize) changed the buffer,
+    /// the stored match lines are stale, since lines shift once the
+    /// scrollback is full. Searches again and selects the newest hit at or
+    /// above the bottom of the view, without scrolling.
+    pub(super) fn refresh_terminal_search(&mut self) {
+        let Some(pty) = self
+            .terminal_search
+            .pane
+            .and_then(|pane| self.terminals.get(&pane))
+        else {
+            return;
+        };
+        if pty.content_generation() == self.terminal_search.generation {
+            return;
+        }
+        let (top, _) = pty.view_origin();
+        let bottom = top + pty.rows() as usize;
+        if self.update_terminal_matches() {
+            let search = &mut self.terminal_search.search;
+            search.current_match = search
+                .matches
+                .iter()
+                .rposition(|(line, _, _)| *line < bottom)
+                .unwrap_or(0);
+        }
+    }
+
+    /// Search the current buffer of the search pane; false (and the bar
+    /// closed) when the pane is gone
+    fn update_terminal_matches(&mut self) -> bool {
+        let Some(pane) = self.terminal_search.pane else {
+            return false;
+        };
+        let Some(pty) = self.terminals.get(&pane) else {
+            self.terminal_search.close();
+            return false;
+        };
+        let generation = pty.content_generation();
+        let lines = pty.extract_all_text();
+        let state = &mut self.terminal_search;
+        state.generation = generation;
+        match find_matches(
+            &lines,
+            &state.search.query,
+            state.regex,
+            state.search.case_sensitive,
+        ) {
+            Ok(matches) => {
+                state.search.matches = matches;
+                state.error = None;
+            }
+            Err(e) => {
+                state.search.matches.clear();
+                state.error = Some(e.to_string());
+            }
+        }
+        true
+    }
+
+    /// Move to the next newer (`forward`) or older hit, wrapping around
+    fn step_terminal_match(&mut self, forward: bool) {
+        if forward {
+            self.terminal_search.search.next_match();
+        } else {
+            self.terminal_search.search.prev_match();
+        }
+        self.jump_to_terminal_match();
+    }
+
+    fn jump_to_terminal_match(&mut self) {
+        let Some(pane) = self.terminal_search.pane else {
+            return;
+        };
+        if let (Some((line, start, _)), Some(pty)) = (
+            self.terminal_search.search.get_current_match(),
+            self.terminals.get(&pane),
+        ) {
+            pty.scroll_to_line(line);
+            if self.copy_mode.pane == Some(pane) {
+                self.copy_mode.cursor = (line, start);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn plain_search_is_literal_and_case_insensitive() {
+        let buf = lines("error: a.b failed\nall good\nERROR again: axb");
+        let hits = find_matches(&buf, "error", false, false).unwrap();
+        assert_eq!(hits, vec![(0, 0, 5), (2, 0, 5)]);
+        assert_eq!(find_matches(&buf, "error", false, true).unwrap().len(), 1);
+        // '.' is literal in plain mode
+        assert_eq!(
+            find_matches(&buf, "a.b", false, false).unwrap(),
+            vec![(0, 7, 10)]
+        );
+    }
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        let buf = lines("日本 error: ok\n✓ error");
+        let hits = find_matches(&buf, "error", false, false).unwrap();
+        assert_eq!(hits, vec![(0, 5, 10), (1, 2, 7)]);
+        // The match itself may contain wide characters
+        assert_eq!(
+            find_matches(&buf, "日本", false, true).unwrap(),
+            vec![(0, 0, 4)]
+        );
+    }
+
+    #[test]
+    fn regex_search_counts_columns_in_chars() {
+        let buf = lines("äöü code=42\nno code");
+        let hits = find_matches(&buf, r"code=\d+", true, true).unwrap();
+        assert_eq!(hits, vec![(0, 4, 11)]);
+        // Empty matches are skipped, invalid patterns are reported
+        assert!(find_matches(&buf, "x*", true, true).unwrap().is_empty());
+        assert!(find_matches(&buf, "(", true, true).is_err());
+    }
+}
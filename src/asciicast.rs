@@ -0,0 +1,335 @@
+//! asciicast v2 recordings of the terminal panes and the player for the
+//! Preview pane. A `.cast` file is a JSON header line followed by one
+//! `[seconds, kind, data]` line per event: `o` output, `i` input and `r`
+//! resize (`"COLSxROWS"`). See <https://docs.asciinema.org/manual/asciicast/v2/>.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// Decode the complete UTF-8 prefix of `pending` and keep an incomplete
+/// trailing sequence for the next chunk (PTY reads split characters).
+/// Invalid bytes become U+FFFD.
+fn take_utf8(pending: &mut Vec<u8>) -> String {
+    let mut text = String::new();
+    let mut rest = &pending[..];
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    // Incomplete sequence at the end: wait for more bytes
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    *pending = rest.to_vec();
+    text
+}
+
+/// Writes one pane's session to a `.cast` file
+pub struct Recorder {
+    file: BufWriter<File>,
+    path: PathBuf,
+    start: Instant,
+    record_input: bool,
+    /// Output bytes of a character split across reads
+    pending: Vec<u8>,
+}
+
+impl Recorder {
+    /// Create `path` and write the header for a `cols`×`rows` terminal
+    pub fn create(
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        title: &str,
+        record_input: bool,
+    ) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Cannot create {}", dir.display()))?;
+        }
+        let file =
+            File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": timestamp,
+            "title": title,
+            "env": {
+                "TERM": "xterm-256color",
+                "SHELL": std::env::var("SHELL").unwrap_or_default(),
+            },
+        });
+        let mut file = BufWriter::new(file);
+        writeln!(file, "{}", header)?;
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            start: Instant::now(),
+            record_input,
+            pending: Vec::new(),
+        })
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> std::io::Result<()> {
+        let secs = self.start.elapsed().as_micros() as f64 / 1_000_000.0;
+        let line = serde_json::to_string(&(secs, kind, data)).map_err(std::io::Error::other)?;
+        writeln!(self.file, "{}", line)
+    }
+
+    /// Output of the child, as read from the PTY
+    pub fn output(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.pending.extend_from_slice(data);
+        let text = take_utf8(&mut self.pending);
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.event("o", &text)
+    }
+
+    /// Keys and pastes sent to the child; dropped unless input recording
+    /// is enabled (it may contain passwords)
+    pub fn input(&mut self, data: &[u8]) -> std::io::Result<()> {
+        if !self.record_input {
+            return Ok(());
+        }
+        self.event("i", &String::from_utf8_lossy(data))
+    }
+
+    pub fn resize(&mut self, cols: u16, rows: u16) -> std::io::Result<()> {
+        self.event("r", &format!("{}x{}", cols, rows))
+    }
+
+    /// Flush buffered events and close the file
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.file
+            .flush()
+            .with_context(|| format!("Cannot write {}", self.path.display()))?;
+        Ok(self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CastEvent {
+    Output(String),
+    Resize { cols: u16, rows: u16 },
+}
+
+/// A parsed recording; input events are dropped
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast {
+    pub width: u16,
+    pub height: u16,
+    /// Events with their time in seconds since the start, in file order
+    pub events: Vec<(f64, CastEvent)>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+    width: u16,
+    height: u16,
+}
+
+impl Cast {
+    /// Parse an asciicast v2 file. Malformed event lines (e.g. the last
+    /// line of a recording that was cut off) are skipped.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut lines = content.lines();
+        let header: Header = serde_json::from_str(lines.next().unwrap_or_default())
+            .context("Not an asciicast file: invalid header")?;
+        if header.version != 2 {
+            bail!("Unsupported asciicast version {}", header.version);
+        }
+        let events = lines
+            .filter_map(|line| serde_json::from_str::<(f64, String, String)>(line).ok())
+            .filter_map(|(time, kind, data)| {
+                let event = match kind.as_str() {
+                    "o" => CastEvent::Output(data),
+                    "r" => {
+                        let (cols, rows) = data.split_once('x')?;
+                        CastEvent::Resize {
+                            cols: cols.parse().ok()?,
+                            rows: rows.parse().ok()?,
+                        }
+                    }
+                    _ => return None,
+                };
+                Some((time, event))
+            })
+            .collect();
+        Ok(Self {
+            width: header.width,
+            height: header.height,
+            events,
+        })
+    }
+
+    /// Time of the last event
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |(time, _)| *time)
+    }
+}
+
+/// Replays a [`Cast`] through a `vt100::Parser`
+pub struct CastPlayer {
+    cast: Cast,
+    parser: vt100::Parser,
+    /// Index of the next event to apply
+    next: usize,
+    /// Playback position in recording seconds
+    position: f64,
+    pub playing: bool,
+    pub speed: f64,
+    last_tick: Option<Instant>,
+}
+
+impl std::fmt::Debug for CastPlayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CastPlayer")
+            .field("position", &self.position)
+            .field("duration", &self.duration())
+            .field("playing", &self.playing)
+            .field("speed", &self.speed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CastPlayer {
+    pub fn new(cast: Cast) -> Self {
+        let parser = vt100::Parser::new(cast.height, cast.width, 0);
+        Self {
+            cast,
+            parser,
+            next: 0,
+            position: 0.0,
+            playing: true,
+            speed: 1.0,
+            last_tick: None,
+        }
+    }
+
+    pub fn screen(&self) -> &vt100::Screen {
+        self.parser.screen()
+    }
+
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.cast.duration()
+    }
+
+    /// Advance by the time since the last tick; called once per frame
+    pub fn tick(&mut self, now: Instant) {
+        let elapsed = self
+            .last_tick
+            .map_or(0.0, |t| now.saturating_duration_since(t).as_secs_f64());
+        self.last_tick = Some(now);
+        if self.playing {
+            self.seek(self.position + elapsed * self.speed);
+            if self.position >= self.duration() {
+                self.playing = false;
+            }
+        }
+    }
+
+    /// Jump to `target` seconds; seeking backwards replays from the start
+    pub fn seek(&mut self, target: f64) {
+        let target = target.clamp(0.0, self.duration());
+        if target < self.position {
+            self.parser = vt100::Parser::new(self.cast.height, self.cast.width, 0);
+            self.next = 0;
+        }
+        while let Some((time, event)) = self.cast.events.get(self.next) {
+            if *time > target {
+                break;
+            }
+            match event {
+                CastEvent::Output(data) => self.parser.process(data.as_bytes()),
+                CastEvent::Resize { cols, rows } => self.parser.screen_mut().set_size(*rows, *cols),
+            }
+            self.next += 1;
+        }
+        self.position = target;
+    }
+
+    /// Space: pause, or restart a finished recording
+    pub fn toggle_pause(&mut self) {
+        if !self.playing && self.position >= self.duration() {
+            self.seek(0.0);
+        }
+        self.playing = !self.playing;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_utf8_sequences_wait_for_the_rest() {
+        let mut pending = "aä".as_bytes()[..2].to_vec();
+        assert_eq!(take_utf8(&mut pending), "a");
+        assert_eq!(pending, vec![0xc3]);
+        pending.extend_from_slice(&[0xa4, 0xff, b'b']);
+        assert_eq!(take_utf8(&mut pending), "ä\u{fffd}b");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn recording_round_trip_and_seek() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rec").join("t.cast");
+        let mut rec = Recorder::create(&path, 20, 3, "Terminal", false).unwrap();
+        rec.output(b"$ echo hi\r\nhi\r\n").unwrap();
+        rec.input(b"secret").unwrap();
+        rec.resize(30, 4).unwrap();
+        rec.output("\u{1b}[2J\u{1b}[Hclear".as_bytes()).unwrap();
+        let path = rec.finish().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret"));
+        let cast = Cast::parse(&format!("{}[1.0, \"o\", \"trunc", content)).unwrap();
+        assert_eq!((cast.width, cast.height), (20, 3));
+        assert_eq!(cast.events.len(), 3);
+        assert_eq!(cast.events[1].1, CastEvent::Resize { cols: 30, rows: 4 });
+
+        let mut player = CastPlayer::new(cast);
+        player.seek(player.duration());
+        assert_eq!(player.screen().size(), (4, 30));
+        assert!(player.screen().contents().starts_with("clear"));
+        // Backwards: replayed from the start
+        player.seek(0.0);
+        player.seek(player.cast.events[0].0);
+        assert_eq!(player.screen().size(), (3, 20));
+        assert!(player.screen().contents().contains("hi"));
+
+        assert!(Cast::parse("{\"version\": 1, \"width\": 1, \"height\": 1}").is_err());
+    }
+}
@@ -0,0 +1,326 @@
+//! Book export: all Markdown files of a folder merged into one PDF.
+//!
+//! The chapter order comes from `_order.yaml` (a list of files, or a map with
+//! `title` and `chapters`), from the links in `SUMMARY.md` (mdBook style), or
+//! falls back to the alphabetical file order. Links between chapters are
+//! rewritten to internal PDF anchors by the Typst renderer.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::browser::pdf_export::{ExportOptions, ExportOutput};
+use crate::config::DocumentConfig;
+
+/// Chapter order file (takes precedence over `SUMMARY.md`)
+pub const ORDER_FILE: &str = "_order.yaml";
+/// mdBook-style table of contents
+pub const SUMMARY_FILE: &str = "SUMMARY.md";
+
+/// A book: title and chapter files relative to the book root ("guide/intro.md").
+#[derive(Debug, Clone, PartialEq)]
+pub struct Book {
+    pub root: PathBuf,
+    pub title: Option<String>,
+    pub chapters: Vec<String>,
+}
+
+impl Book {
+    /// Anchor label of chapter `index` (Typst label, e.g. `ch1`)
+    pub fn chapter_label(index: usize) -> String {
+        format!("ch{}", index + 1)
+    }
+
+    /// Link context for rendering chapter `index`.
+    #[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+    pub(crate) fn chapter_context(&self, index: usize) -> ChapterContext {
+        let rel = &self.chapters[index];
+        let rel_dir = rel.rfind('/').map_or("", |i| &rel[..=i]).to_string();
+        ChapterContext {
+            label_prefix: Self::chapter_label(index),
+            rel_dir,
+            chapters: self
+                .chapters
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (c.clone(), Self::chapter_label(i)))
+                .collect(),
+        }
+    }
+}
+
+/// Per-chapter rendering context: label namespace and cross-document link targets.
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChapterContext {
+    /// Label of the chapter start; heading labels become `{prefix}-{slug}`
+    pub label_prefix: String,
+    /// Chapter directory relative to the book root ("" or "guide/")
+    pub rel_dir: String,
+    /// Chapter path relative to the book root → chapter label
+    pub chapters: HashMap<String, String>,
+}
+
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+impl ChapterContext {
+    /// Label for a heading slug inside this chapter
+    pub fn heading_label(&self, slug: &str) -> String {
+        format!("{}-{}", self.label_prefix, slug)
+    }
+
+    /// Resolve a relative `.md` link to a label in the book
+    /// (`None` if it points outside the book).
+    pub fn resolve_link(&self, href: &str) -> Option<String> {
+        let (path, fragment) = crate::browser::markdown::split_relative_md_link(href)?;
+        let target = normalize_rel_path(&self.rel_dir, path)?;
+        let label = self.chapters.get(&target)?;
+        let slug = crate::browser::slugify(fragment);
+        Some(if slug.is_empty() {
+            label.clone()
+        } else {
+            format!("{}-{}", label, slug)
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrderFile {
+    List(Vec<String>),
+    Map {
+        #[serde(default)]
+        title: Option<String>,
+        chapters: Vec<String>,
+    },
+}
+
+/// Determine title and chapter order for the book in `root`.
+pub fn load_book(root: &Path) -> Result<Book> {
+    let (title, chapters) = if root.join(ORDER_FILE).is_file() {
+        let path = root.join(ORDER_FILE);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        match serde_yaml_ng::from_str(&content)
+            .with_context(|| format!("Invalid {}", ORDER_FILE))?
+        {
+            OrderFile::List(chapters) => (None, chapters),
+            OrderFile::Map { title, chapters } => (title, chapters),
+        }
+    } else if root.join(SUMMARY_FILE).is_file() {
+        let content = std::fs::read_to_string(root.join(SUMMARY_FILE))?;
+        parse_summary(&content)
+    } else {
+        (None, alphabetical_chapters(root))
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    let mut ordered = Vec::new();
+    for chapter in chapters {
+        let rel = normalize_rel_path("", chapter.trim())
+            .with_context(|| format!("Chapter outside the book folder: {}", chapter))?;
+        if !root.join(&rel).is_file() {
+            anyhow::bail!("Chapter not found: {}", rel);
+        }
+        if seen.insert(rel.clone()) {
+            ordered.push(rel);
+        }
+    }
+    if ordered.is_empty() {
+        anyhow::bail!("No chapters found in {}", root.display());
+    }
+
+    Ok(Book {
+        root: root.to_path_buf(),
+        title,
+        chapters: ordered,
+    })
+}
+
+/// Title (first `# ` heading) and linked chapters of a `SUMMARY.md`.
+fn parse_summary(content: &str) -> (Option<String>, Vec<String>) {
+    let title = content
+        .lines()
+        .find_map(|l| l.strip_prefix("# "))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new(content));
+    let chapters = crate::browser::markdown::collect_md_links(&html)
+        .iter()
+        .filter_map(|href| crate::browser::markdown::split_relative_md_link(href))
+        .map(|(path, _)| path.to_string())
+        .collect();
+    (title, chapters)
+}
+
+/// Top-level Markdown files sorted by name (without `SUMMARY.md`).
+fn alphabetical_chapters(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|x| x == "md"))
+        .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(String::from))
+        .filter(|n| n != SUMMARY_FILE)
+        .collect();
+    names.sort();
+    names
+}
+
+/// Join `href` onto `base_dir` (both `/`-separated, relative to the book
+/// root) and resolve `.`/`..`. `None` if the result leaves the root.
+pub(crate) fn normalize_rel_path(base_dir: &str, href: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in base_dir.split('/').chain(href.split('/')) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            p => parts.push(p),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// The file defining the book in `root`: `_order.yaml` or `SUMMARY.md`, else
+/// (alphabetical order) where `_order.yaml` would be
+fn book_file(root: &Path) -> PathBuf {
+    [ORDER_FILE, SUMMARY_FILE]
+        .into_iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| root.join(ORDER_FILE))
+}
+
+/// Export the book in `root` as a single PDF with a global table of contents.
+pub fn export_book(
+    root: &Path,
+    target: &Path,
+    options: &ExportOptions,
+    doc: &DocumentConfig,
+) -> Result<ExportOutput> {
+    let book = load_book(root)?;
+    let mut options = options.clone();
+    if let Some(title) = &book.title {
+        options.title = title.clone();
+    }
+    // Path rules see the book file (a folder alone does not match `dir/**`),
+    // so one profile applies to every chapter
+    let mut doc = doc.resolve_profile(options.profile.as_deref(), &book_file(root))?;
+    doc.template.toc = true;
+
+    #[cfg(feature = "pdf-export")]
+    {
+        crate::browser::typst_pdf::export_book_to_pdf(&book, target, &options, &doc)
+    }
+    #[cfg(not(feature = "pdf-export"))]
+    {
+        let _ = (target, options, doc);
+        Err(anyhow::anyhow!(
+            "PDF export is not available: rebuild with `--features pdf-export`"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_order_file_list_and_map() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(tmp.path(), "a.md", "# A");
+        write(tmp.path(), "b.md", "# B");
+        write(tmp.path(), ORDER_FILE, "- b.md\n- ./a.md\n");
+        let book = load_book(tmp.path()).unwrap();
+        assert_eq!(book.chapters, vec!["b.md", "a.md"]);
+        assert_eq!(book.title, None);
+
+        write(
+            tmp.path(),
+            ORDER_FILE,
+            "title: Handbuch\nchapters: [a.md, b.md]\n",
+        );
+        let book = load_book(tmp.path()).unwrap();
+        assert_eq!(book.title.as_deref(), Some("Handbuch"));
+        assert_eq!(book.chapters, vec!["a.md", "b.md"]);
+    }
+
+    #[test]
+    fn test_summary_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(tmp.path(), "intro.md", "# Intro");
+        write(tmp.path(), "guide/setup.md", "# Setup");
+        write(
+            tmp.path(),
+            SUMMARY_FILE,
+            "# Betriebshandbuch\n\n- [Intro](intro.md)\n  - [Setup](guide/setup.md#install)\n- [Web](https://example.com)\n",
+        );
+        let book = load_book(tmp.path()).unwrap();
+        assert_eq!(book.title.as_deref(), Some("Betriebshandbuch"));
+        assert_eq!(book.chapters, vec!["intro.md", "guide/setup.md"]);
+        assert_eq!(book_file(tmp.path()), tmp.path().join(SUMMARY_FILE));
+    }
+
+    #[test]
+    fn test_alphabetical_fallback_and_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load_book(tmp.path()).is_err());
+        assert_eq!(book_file(tmp.path()), tmp.path().join(ORDER_FILE));
+        write(tmp.path(), "b.md", "# B");
+        write(tmp.path(), "a.md", "# A");
+        assert_eq!(
+            load_book(tmp.path()).unwrap().chapters,
+            vec!["a.md", "b.md"]
+        );
+
+        write(tmp.path(), ORDER_FILE, "- missing.md\n");
+        assert!(load_book(tmp.path()).is_err());
+        write(tmp.path(), ORDER_FILE, "- ../outside.md\n");
+        assert!(load_book(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn test_normalize_rel_path() {
+        assert_eq!(
+            normalize_rel_path("guide/", "../a.md").as_deref(),
+            Some("a.md")
+        );
+        assert_eq!(
+            normalize_rel_path("guide/", "./b.md").as_deref(),
+            Some("guide/b.md")
+        );
+        assert_eq!(normalize_rel_path("", "../a.md"), None);
+    }
+
+    #[test]
+    fn test_chapter_links_resolve_to_labels() {
+        let book = Book {
+            root: PathBuf::from("/book"),
+            title: None,
+            chapters: vec!["intro.md".to_string(), "guide/setup.md".to_string()],
+        };
+        let ctx = book.chapter_context(1);
+        assert_eq!(ctx.rel_dir, "guide/");
+        assert_eq!(ctx.resolve_link("../intro.md").as_deref(), Some("ch1"));
+        assert_eq!(
+            ctx.resolve_link("setup.md#Erste Schritte").as_deref(),
+            Some("ch2-erste-schritte")
+        );
+        assert_eq!(ctx.resolve_link("other.md"), None);
+        assert_eq!(ctx.resolve_link("https://example.com/x.md"), None);
+        assert_eq!(ctx.heading_label("intro"), "ch2-intro");
+    }
+}
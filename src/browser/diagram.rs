@@ -0,0 +1,377 @@
+//! Diagram rendering for Markdown exports (Mermaid, PlantUML).
+//!
+//! Fenced ` ```mermaid ` / ` ```plantuml ` blocks are passed to a configurable
+//! local renderer command (`mmdc`, `plantuml`) and embedded as SVG by the HTML
+//! and Typst exporters. Rendered SVGs are cached on disk, keyed by a hash of
+//! the renderer command and the block source, so unchanged diagrams are not
+//! re-rendered on every export.
+//!
+//! Every failure (renderer not installed, non-zero exit, timeout, output that
+//! is not SVG) yields `None` — callers then keep the original code block.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::config::DiagramConfig;
+
+/// Diagram language of a fenced code block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiagramKind {
+    Mermaid,
+    PlantUml,
+}
+
+impl DiagramKind {
+    /// Map a fenced code block info string (`mermaid`, `plantuml {title}`) to a kind.
+    pub(crate) fn from_info(info: &str) -> Option<Self> {
+        let lang = info.split_whitespace().next().unwrap_or("");
+        match lang.to_lowercase().as_str() {
+            "mermaid" => Some(Self::Mermaid),
+            "plantuml" | "puml" => Some(Self::PlantUml),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Mermaid => "mermaid",
+            Self::PlantUml => "plantuml",
+        }
+    }
+
+    fn source_extension(&self) -> &'static str {
+        match self {
+            Self::Mermaid => ".mmd",
+            Self::PlantUml => ".puml",
+        }
+    }
+
+    fn command<'a>(&self, cfg: &'a DiagramConfig) -> &'a [String] {
+        match self {
+            Self::Mermaid => &cfg.mermaid_command,
+            Self::PlantUml => &cfg.plantuml_command,
+        }
+    }
+}
+
+/// A successfully rendered diagram
+#[derive(Debug, Clone)]
+pub(crate) struct RenderedDiagram {
+    /// Stable content hash (hex) — usable as a file name
+    #[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+    pub hash: String,
+    /// SVG document, starting at the `<svg` element (XML prolog stripped)
+    pub svg: String,
+}
+
+/// Render a diagram block to SVG, using the on-disk cache when enabled.
+/// Returns `None` when rendering is disabled or the renderer fails.
+pub(crate) fn render_diagram(
+    kind: DiagramKind,
+    source: &str,
+    cfg: &DiagramConfig,
+) -> Option<RenderedDiagram> {
+    if !cfg.enabled {
+        return None;
+    }
+    let command = kind.command(cfg);
+    let hash = block_hash(kind, command, source);
+
+    let cache_path = cfg
+        .cache
+        .then(|| cache_dir().map(|d| d.join(format!("{hash}.svg"))))
+        .flatten();
+    if let Some(path) = &cache_path {
+        if let Ok(svg) = std::fs::read_to_string(path) {
+            if let Some(svg) = extract_svg(&svg) {
+                return Some(RenderedDiagram { hash, svg });
+            }
+        }
+    }
+
+    let output = run_renderer(kind, command, source, cfg.timeout_secs).ok()?;
+    let svg = extract_svg(&output)?;
+
+    if let Some(path) = &cache_path {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(path, &svg);
+    }
+
+    Some(RenderedDiagram { hash, svg })
+}
+
+/// Cache directory for rendered diagrams: ~/.cache/claude-workbench/diagrams/
+fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("claude-workbench").join("diagrams"))
+}
+
+/// FNV-1a (64 bit) over kind, renderer argv and source.
+///
+/// Deliberately not `DefaultHasher`: its output is not guaranteed to be stable
+/// across Rust releases, which would silently invalidate the cache.
+fn block_hash(kind: DiagramKind, command: &[String], source: &str) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for b in bytes {
+            hash ^= u64::from(*b);
+            hash = hash.wrapping_mul(PRIME);
+        }
+        // Field separator so ("ab", "c") and ("a", "bc") hash differently
+        hash ^= 0xff;
+        hash = hash.wrapping_mul(PRIME);
+    };
+    feed(kind.name().as_bytes());
+    for arg in command {
+        feed(arg.as_bytes());
+    }
+    feed(source.as_bytes());
+    format!("{hash:016x}")
+}
+
+/// Trim renderer output to the `<svg …>…</svg>` element.
+/// Returns `None` if the output does not contain an SVG document.
+fn extract_svg(output: &str) -> Option<String> {
+    let start = output.find("<svg")?;
+    let end = output.rfind("</svg>")? + "</svg>".len();
+    (end > start).then(|| output[start..end].to_string())
+}
+
+/// Run the renderer command for one diagram and return its SVG output.
+fn run_renderer(
+    kind: DiagramKind,
+    command: &[String],
+    source: &str,
+    timeout_secs: u64,
+) -> Result<String> {
+    let program = command
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Empty {} renderer command", kind.name()))?;
+    crate::browser::opener::validate_program(program)?;
+
+    let uses_input = command.iter().any(|a| a.contains("{input}"));
+    let uses_output = command.iter().any(|a| a.contains("{output}"));
+
+    // Temp files are removed on drop, after the renderer has finished.
+    let input_file = if uses_input {
+        let mut f = tempfile::Builder::new()
+            .prefix("cwb-diagram-")
+            .suffix(kind.source_extension())
+            .tempfile()?;
+        f.write_all(source.as_bytes())?;
+        f.flush()?;
+        Some(f)
+    } else {
+        None
+    };
+    let output_file = if uses_output {
+        Some(
+            tempfile::Builder::new()
+                .prefix("cwb-diagram-")
+                .suffix(".svg")
+                .tempfile()?,
+        )
+    } else {
+        None
+    };
+
+    let substitute = |arg: &String| -> String {
+        let mut arg = arg.clone();
+        if let Some(f) = &input_file {
+            arg = arg.replace("{input}", &f.path().to_string_lossy());
+        }
+        if let Some(f) = &output_file {
+            arg = arg.replace("{output}", &f.path().to_string_lossy());
+        }
+        arg
+    };
+    let args: Vec<String> = command[1..].iter().map(substitute).collect();
+
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(if uses_input {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Feed stdin and drain stdout on helper threads so a chatty renderer can
+    // never block on a full pipe while we wait for it to exit.
+    if let Some(mut stdin) = child.stdin.take() {
+        let data = source.as_bytes().to_vec();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&data);
+        });
+    }
+    let stdout_reader = child.stdout.take().map(|mut stdout| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stdout.read_to_end(&mut buf);
+            buf
+        })
+    });
+
+    let status = wait_with_timeout(&mut child, Duration::from_secs(timeout_secs))?;
+    let stdout = stdout_reader
+        .and_then(|h| h.join().ok())
+        .unwrap_or_default();
+    if !status.success() {
+        anyhow::bail!("{} renderer exited with {}", kind.name(), status);
+    }
+
+    match &output_file {
+        Some(f) => Ok(std::fs::read_to_string(f.path())?),
+        None => Ok(String::from_utf8_lossy(&stdout).into_owned()),
+    }
+}
+
+/// Wait for the child, killing it once `timeout` has elapsed.
+fn wait_with_timeout(
+    child: &mut std::process::Child,
+    timeout: Duration,
+) -> Result<std::process::ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("renderer timed out after {}s", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(25));
+    }
+}
+
+/// Encode an SVG as a `data:` URI for use in `<img src>`.
+///
+/// Images are used instead of inline `<svg>` because mermaid-cli gives every
+/// diagram the same element id, and inline copies would share (and clobber)
+/// each other's scoped `<style>` rules.
+pub(crate) fn svg_data_uri(svg: &str) -> String {
+    let mut uri = String::with_capacity(svg.len() + 32);
+    uri.push_str("data:image/svg+xml;charset=utf-8,");
+    for c in svg.chars() {
+        match c {
+            '%' => uri.push_str("%25"),
+            '#' => uri.push_str("%23"),
+            '"' => uri.push_str("%22"),
+            '<' => uri.push_str("%3C"),
+            '>' => uri.push_str("%3E"),
+            '\n' => uri.push_str("%0A"),
+            '\r' => {}
+            _ => uri.push(c),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(command: &[&str]) -> DiagramConfig {
+        DiagramConfig {
+            enabled: true,
+            mermaid_command: command.iter().map(|s| s.to_string()).collect(),
+            plantuml_command: command.iter().map(|s| s.to_string()).collect(),
+            timeout_secs: 5,
+            cache: false,
+        }
+    }
+
+    #[test]
+    fn test_from_info_detects_languages() {
+        assert_eq!(
+            DiagramKind::from_info("mermaid"),
+            Some(DiagramKind::Mermaid)
+        );
+        assert_eq!(
+            DiagramKind::from_info("PlantUML title=x"),
+            Some(DiagramKind::PlantUml)
+        );
+        assert_eq!(DiagramKind::from_info("puml"), Some(DiagramKind::PlantUml));
+        assert_eq!(DiagramKind::from_info("rust"), None);
+        assert_eq!(DiagramKind::from_info(""), None);
+    }
+
+    #[test]
+    fn test_block_hash_is_stable_and_input_sensitive() {
+        let cmd = vec!["mmdc".to_string()];
+        let a = block_hash(DiagramKind::Mermaid, &cmd, "graph TD; A-->B");
+        assert_eq!(a, block_hash(DiagramKind::Mermaid, &cmd, "graph TD; A-->B"));
+        assert_ne!(a, block_hash(DiagramKind::Mermaid, &cmd, "graph TD; A-->C"));
+        assert_ne!(
+            a,
+            block_hash(DiagramKind::PlantUml, &cmd, "graph TD; A-->B")
+        );
+        assert_eq!(a.len(), 16);
+    }
+
+    #[test]
+    fn test_extract_svg_strips_prolog() {
+        let out = "<?xml version=\"1.0\"?>\n<svg width=\"1\"></svg>\n";
+        assert_eq!(extract_svg(out).as_deref(), Some("<svg width=\"1\"></svg>"));
+        assert_eq!(extract_svg("Error: not found"), None);
+    }
+
+    #[test]
+    fn test_svg_data_uri_escapes_reserved_chars() {
+        let uri = svg_data_uri("<svg fill=\"#fff\">50%</svg>");
+        let (prefix, body) = uri.split_once(',').expect("data URI has a comma");
+        assert_eq!(prefix, "data:image/svg+xml;charset=utf-8");
+        assert_eq!(body, "%3Csvg fill=%22%23fff%22%3E50%25%3C/svg%3E");
+    }
+
+    #[test]
+    fn test_missing_renderer_returns_none() {
+        let cfg = test_config(&["claude-workbench-no-such-renderer"]);
+        assert!(render_diagram(DiagramKind::Mermaid, "graph TD", &cfg).is_none());
+    }
+
+    #[test]
+    fn test_disabled_returns_none() {
+        let mut cfg = test_config(&["cat"]);
+        cfg.enabled = false;
+        assert!(render_diagram(DiagramKind::Mermaid, "<svg></svg>", &cfg).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stdin_renderer_roundtrip() {
+        // `cat` echoes the piped source back, standing in for `plantuml -pipe`.
+        let cfg = test_config(&["cat"]);
+        let rendered =
+            render_diagram(DiagramKind::PlantUml, "<svg><g/></svg>", &cfg).expect("rendered");
+        assert_eq!(rendered.svg, "<svg><g/></svg>");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_renderer_roundtrip() {
+        // `cp {input} {output}` stands in for `mmdc -i {input} -o {output}`.
+        let cfg = test_config(&["cp", "{input}", "{output}"]);
+        let rendered =
+            render_diagram(DiagramKind::Mermaid, "<svg><g/></svg>", &cfg).expect("rendered");
+        assert_eq!(rendered.svg, "<svg><g/></svg>");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_svg_output_returns_none() {
+        let cfg = test_config(&["cat"]);
+        assert!(render_diagram(DiagramKind::Mermaid, "graph TD; A-->B", &cfg).is_none());
+    }
+}
@@ -0,0 +1,875 @@
+//! Markdown to DOCX (Office Open XML) export.
+//!
+//! Pure Rust: the pulldown-cmark event stream is translated to WordprocessingML
+//! and packed into a zip container. Branding (fonts, colors, sizes, page
+//! setup, company footer) comes from `DocumentConfig`, mirroring the PDF
+//! template: title in the page header, company/date/page numbers in the footer.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::browser::front_matter::skip_metadata_blocks;
+use crate::browser::pdf_export::ExportOptions;
+use crate::config::DocumentConfig;
+
+/// Export a Markdown file as `.docx`.
+pub fn export_markdown_to_docx(
+    source: &Path,
+    target: &Path,
+    options: &ExportOptions,
+    doc: &DocumentConfig,
+) -> Result<PathBuf> {
+    let md = std::fs::read_to_string(source)?;
+    let body = DocxRenderer::run(&md, doc);
+
+    let file = std::fs::File::create(target)
+        .with_context(|| format!("Cannot create {}", target.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let opts = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let parts: [(&str, String); 9] = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELS.to_string()),
+        ("docProps/core.xml", core_properties(options)),
+        ("word/document.xml", document_xml(&body.xml, doc)),
+        ("word/styles.xml", styles_xml(doc)),
+        ("word/header1.xml", header_xml(options, doc)),
+        ("word/footer1.xml", footer_xml(options, doc)),
+        ("word/settings.xml", SETTINGS.to_string()),
+        ("word/_rels/document.xml.rels", document_rels(&body.links)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, opts)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+
+    Ok(target.to_path_buf())
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+<Override PartName="/word/settings.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.settings+xml"/>
+<Override PartName="/word/header1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"/>
+<Override PartName="/word/footer1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml"/>
+<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+</Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+</Relationships>"#;
+
+const SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:updateFields w:val="true"/>
+<w:defaultTabStop w:val="708"/>
+</w:settings>"#;
+
+const W_NS: &str = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#;
+
+fn core_properties(options: &ExportOptions) -> String {
+    let meta = &options.meta;
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/">
+<dc:title>{}</dc:title>
+<dc:subject>{}</dc:subject>
+<dc:creator>{}</dc:creator>
+<cp:keywords>{}</cp:keywords>
+<cp:contentStatus>{}</cp:contentStatus>
+</cp:coreProperties>"#,
+        xml_escape(&options.title),
+        xml_escape(&meta.subtitle),
+        xml_escape(&options.author),
+        xml_escape(&meta.tags.join(", ")),
+        xml_escape(&meta.confidentiality),
+    )
+}
+
+/// Relationships: fixed parts plus one external target per hyperlink (`rIdLink{n}`).
+fn document_rels(links: &[String]) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rIdStyles" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+<Relationship Id="rIdSettings" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/settings" Target="settings.xml"/>
+<Relationship Id="rIdHeader" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Target="header1.xml"/>
+<Relationship Id="rIdFooter" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer" Target="footer1.xml"/>
+"#,
+    );
+    for (i, url) in links.iter().enumerate() {
+        out.push_str(&format!(
+            "<Relationship Id=\"rIdLink{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"{}\" TargetMode=\"External\"/>\n",
+            i + 1,
+            xml_escape(url)
+        ));
+    }
+    out.push_str("</Relationships>");
+    out
+}
+
+fn document_xml(body: &str, doc: &DocumentConfig) -> String {
+    let (width, height) = page_size_twips(&doc.pdf.page_size);
+    let (top, right, bottom, left) = doc.pdf.resolved_margins();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document {ns}><w:body>{body}<w:sectPr><w:headerReference w:type="default" r:id="rIdHeader"/><w:footerReference w:type="default" r:id="rIdFooter"/><w:pgSz w:w="{width}" w:h="{height}"/><w:pgMar w:top="{top}" w:right="{right}" w:bottom="{bottom}" w:left="{left}" w:header="567" w:footer="567" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        ns = W_NS,
+        body = body,
+        width = width,
+        height = height,
+        top = length_twips(&top).unwrap_or(1417),
+        right = length_twips(&right).unwrap_or(1417),
+        bottom = length_twips(&bottom).unwrap_or(1417),
+        left = length_twips(&left).unwrap_or(1417),
+    )
+}
+
+/// Header: title (with subtitle) left, confidentiality right.
+fn header_xml(options: &ExportOptions, doc: &DocumentConfig) -> String {
+    let meta = &options.meta;
+    let title = if meta.subtitle.trim().is_empty() {
+        options.title.clone()
+    } else {
+        format!("{} — {}", options.title, meta.subtitle.trim())
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:hdr {ns}><w:p><w:pPr><w:pStyle w:val="Header"/><w:pBdr><w:bottom w:val="single" w:sz="4" w:space="1" w:color="{border}"/></w:pBdr><w:tabs><w:tab w:val="right" w:pos="9072"/></w:tabs></w:pPr><w:r><w:t xml:space="preserve">{title}</w:t></w:r><w:r><w:tab/><w:t xml:space="preserve">{confidentiality}</w:t></w:r></w:p></w:hdr>"#,
+        ns = W_NS,
+        border = docx_color(&doc.colors.header_border).unwrap_or_else(|| "999999".into()),
+        title = xml_escape(&title),
+        confidentiality = xml_escape(meta.confidentiality.trim()),
+    )
+}
+
+/// Footer: company text left, date centered, localized page label right — like the PDF.
+fn footer_xml(options: &ExportOptions, doc: &DocumentConfig) -> String {
+    let field = |instr: &str| {
+        format!(
+            r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> {} </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+            instr
+        )
+    };
+    let text = |t: &str| {
+        if t.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r>"#,
+                xml_escape(t)
+            )
+        }
+    };
+
+    let version = doc.template.version.trim();
+    let footer_date = if version.is_empty() {
+        options.date.clone()
+    } else {
+        format!("{} · Version {}", options.date, version)
+    };
+
+    // "Seite {page} von {total}" → text runs + PAGE / NUMPAGES fields
+    let label = doc.template.resolved_page_label();
+    let mut page_label = String::new();
+    for (i, part) in label.split("{page}").enumerate() {
+        if i > 0 {
+            page_label.push_str(&field("PAGE"));
+        }
+        for (k, piece) in part.split("{total}").enumerate() {
+            if k > 0 {
+                page_label.push_str(&field("NUMPAGES"));
+            }
+            page_label.push_str(&text(piece));
+        }
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:ftr {ns}><w:p><w:pPr><w:pStyle w:val="Footer"/><w:pBdr><w:top w:val="single" w:sz="4" w:space="1" w:color="{border}"/></w:pBdr><w:tabs><w:tab w:val="center" w:pos="4536"/><w:tab w:val="right" w:pos="9072"/></w:tabs></w:pPr><w:r><w:t xml:space="preserve">{company}</w:t></w:r><w:r><w:tab/><w:t xml:space="preserve">{date}</w:t></w:r><w:r><w:tab/></w:r>{page_label}</w:p></w:ftr>"#,
+        ns = W_NS,
+        border = docx_color(&doc.colors.header_border).unwrap_or_else(|| "999999".into()),
+        company = xml_escape(&doc.resolved_footer_text()),
+        date = xml_escape(&footer_date),
+        page_label = page_label,
+    )
+}
+
+fn styles_xml(doc: &DocumentConfig) -> String {
+    let body_font = xml_escape(&first_font(&doc.fonts.body, "Calibri"));
+    let code_font = xml_escape(&first_font(&doc.fonts.code, "Consolas"));
+    let color = |c: &str, fallback: &str| docx_color(c).unwrap_or_else(|| fallback.to_string());
+    let size = |s: &str, fallback: u32| half_points(s).unwrap_or(fallback);
+
+    let heading = |id: u8, name: &str, sz: u32, border: bool| {
+        let pbdr = if border {
+            format!(
+                r#"<w:pBdr><w:bottom w:val="single" w:sz="4" w:space="1" w:color="{}"/></w:pBdr>"#,
+                color(&doc.colors.heading_separator, "EEEEEE")
+            )
+        } else {
+            String::new()
+        };
+        format!(
+            r#"<w:style w:type="paragraph" w:styleId="Heading{id}"><w:name w:val="{name}"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/>{pbdr}<w:outlineLvl w:val="{lvl}"/></w:pPr><w:rPr><w:b/><w:sz w:val="{sz}"/></w:rPr></w:style>"#,
+            id = id,
+            name = name,
+            pbdr = pbdr,
+            lvl = id - 1,
+            sz = sz,
+        )
+    };
+
+    let mut headings = String::new();
+    headings.push_str(&heading(1, "heading 1", size(&doc.sizes.title, 32), true));
+    headings.push_str(&heading(2, "heading 2", size(&doc.sizes.h1, 28), false));
+    headings.push_str(&heading(3, "heading 3", size(&doc.sizes.h2, 24), false));
+    let h3 = size(&doc.sizes.h3, 22);
+    for level in 4..=6u8 {
+        headings.push_str(&heading(level, &format!("heading {}", level), h3, false));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles {ns}>
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="{body_font}" w:hAnsi="{body_font}" w:cs="{body_font}"/><w:color w:val="{text}"/><w:sz w:val="{body}"/><w:lang w:val="{lang}"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>
+{headings}
+<w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:pPr><w:shd w:val="clear" w:color="auto" w:fill="{code_bg}"/><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:rPr><w:rFonts w:ascii="{code_font}" w:hAnsi="{code_font}" w:cs="{code_font}"/><w:sz w:val="{code}"/></w:rPr></w:style>
+<w:style w:type="character" w:styleId="CodeChar"><w:name w:val="Inline Code"/><w:rPr><w:rFonts w:ascii="{code_font}" w:hAnsi="{code_font}" w:cs="{code_font}"/><w:shd w:val="clear" w:color="auto" w:fill="{code_bg}"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:pPr><w:pBdr><w:left w:val="single" w:sz="18" w:space="8" w:color="{quote_border}"/></w:pBdr><w:ind w:left="360"/></w:pPr><w:rPr><w:color w:val="{quote_text}"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/></w:pPr></w:style>
+<w:style w:type="paragraph" w:styleId="TableText"><w:name w:val="Table Text"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0"/></w:pPr><w:rPr><w:sz w:val="{table}"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Header"><w:name w:val="header"/><w:basedOn w:val="Normal"/><w:rPr><w:color w:val="{header_border}"/><w:sz w:val="{header}"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Footer"><w:name w:val="footer"/><w:basedOn w:val="Normal"/><w:rPr><w:color w:val="{footer_color}"/><w:sz w:val="{footer}"/></w:rPr></w:style>
+<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="{link}"/><w:u w:val="single"/></w:rPr></w:style>
+</w:styles>"#,
+        ns = W_NS,
+        body_font = body_font,
+        code_font = code_font,
+        lang = doc.template.resolved_language(),
+        text = color(&doc.colors.body_text, "1A1A1A"),
+        body = size(&doc.sizes.body, 22),
+        headings = headings,
+        code_bg = color(&doc.colors.code_bg, "F4F4F4"),
+        code = size(&doc.sizes.code, 18),
+        quote_border = color(&doc.colors.blockquote_border, "DDDDDD"),
+        quote_text = color(&doc.colors.blockquote_text, "666666"),
+        table = size(&doc.sizes.table, 20),
+        header_border = color(&doc.colors.header_border, "999999"),
+        header = size(&doc.sizes.header, 18),
+        footer_color = color(&doc.colors.footer, "999999"),
+        footer = size(&doc.sizes.footer, 16),
+        link = color(&doc.colors.link, "0366D6"),
+    )
+}
+
+/// Rendered document body plus the external hyperlink targets it references.
+struct DocxBody {
+    xml: String,
+    links: Vec<String>,
+}
+
+/// One table cell/row under construction.
+#[derive(Default)]
+struct TableState {
+    alignments: Vec<pulldown_cmark::Alignment>,
+    rows: String,
+    row: String,
+    cell: Option<String>,
+    column: usize,
+    in_head: bool,
+}
+
+/// Translates pulldown-cmark events to WordprocessingML paragraphs and runs.
+struct DocxRenderer {
+    body: String,
+    links: Vec<String>,
+    para_open: bool,
+    heading: Option<u8>,
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    in_link: bool,
+    quote_depth: usize,
+    /// One entry per open list: `Some(next number)` for ordered lists
+    lists: Vec<Option<u64>>,
+    /// Pending list marker for the item's first paragraph
+    item_marker: Option<String>,
+    code_block: Option<String>,
+    image_alt: Option<String>,
+    table: Option<TableState>,
+    /// Table header shading and border color (from `DocumentConfig.colors`)
+    header_fill: String,
+    border_color: String,
+}
+
+impl DocxRenderer {
+    fn run(md: &str, doc: &DocumentConfig) -> DocxBody {
+        use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+        let opts = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+        let mut r = DocxRenderer {
+            body: String::new(),
+            links: Vec::new(),
+            para_open: false,
+            heading: None,
+            bold: 0,
+            italic: 0,
+            strike: 0,
+            in_link: false,
+            quote_depth: 0,
+            lists: Vec::new(),
+            item_marker: None,
+            code_block: None,
+            image_alt: None,
+            table: None,
+            header_fill: docx_color(&doc.colors.table_header_bg).unwrap_or_else(|| "D5E8F0".into()),
+            border_color: docx_color(&doc.colors.table_border).unwrap_or_else(|| "999999".into()),
+        };
+
+        for event in skip_metadata_blocks(Parser::new_ext(md, opts)) {
+            if let Some(alt) = r.image_alt.as_mut() {
+                match event {
+                    Event::End(TagEnd::Image) => {
+                        let alt = r.image_alt.take().unwrap_or_default();
+                        r.italic += 1;
+                        r.push_text(&format!("[Bild: {}]", alt));
+                        r.italic -= 1;
+                    }
+                    Event::Text(t) | Event::Code(t) => alt.push_str(&t),
+                    _ => {}
+                }
+                continue;
+            }
+            if let Some(code) = r.code_block.as_mut() {
+                match event {
+                    Event::End(TagEnd::CodeBlock) => {
+                        let code = r.code_block.take().unwrap_or_default();
+                        r.close_para();
+                        for line in code.trim_end_matches('\n').split('\n') {
+                            let xml = format!(
+                                r#"<w:p><w:pPr><w:pStyle w:val="Code"/></w:pPr><w:r><w:t xml:space="preserve">{}</w:t></w:r></w:p>"#,
+                                xml_escape(line)
+                            );
+                            r.sink().push_str(&xml);
+                        }
+                        // Spacing after the block
+                        r.sink().push_str("<w:p/>");
+                    }
+                    Event::Text(t) => code.push_str(&t),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match event {
+                Event::Start(Tag::Paragraph) => {
+                    // Tight list items carry text directly; loose ones wrap it
+                    // in a paragraph that continues the item's marker paragraph.
+                    if !r.para_open {
+                        r.open_para();
+                    }
+                }
+                Event::End(TagEnd::Paragraph) => r.close_para(),
+                Event::Start(Tag::Heading { level, .. }) => {
+                    r.close_para();
+                    r.heading = Some(level as u8);
+                    r.open_para();
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    r.close_para();
+                    r.heading = None;
+                }
+                Event::Start(Tag::BlockQuote(_)) => {
+                    r.close_para();
+                    r.quote_depth += 1;
+                }
+                Event::End(TagEnd::BlockQuote(_)) => {
+                    r.close_para();
+                    r.quote_depth = r.quote_depth.saturating_sub(1);
+                }
+                Event::Start(Tag::CodeBlock(_)) => r.code_block = Some(String::new()),
+                Event::Start(Tag::List(start)) => {
+                    r.close_para();
+                    r.lists.push(start);
+                }
+                Event::End(TagEnd::List(_)) => {
+                    r.close_para();
+                    r.lists.pop();
+                }
+                Event::Start(Tag::Item) => {
+                    r.close_para();
+                    let marker = match r.lists.last_mut() {
+                        Some(Some(n)) => {
+                            let m = format!("{}.", n);
+                            *n += 1;
+                            m
+                        }
+                        _ => "\u{2022}".to_string(),
+                    };
+                    r.item_marker = Some(marker);
+                }
+                Event::End(TagEnd::Item) => r.close_para(),
+                Event::TaskListMarker(checked) => {
+                    r.push_text(if checked { "\u{2612} " } else { "\u{2610} " })
+                }
+                Event::Start(Tag::Emphasis) => r.italic += 1,
+                Event::End(TagEnd::Emphasis) => r.italic = r.italic.saturating_sub(1),
+                Event::Start(Tag::Strong) => r.bold += 1,
+                Event::End(TagEnd::Strong) => r.bold = r.bold.saturating_sub(1),
+                Event::Start(Tag::Strikethrough) => r.strike += 1,
+                Event::End(TagEnd::Strikethrough) => r.strike = r.strike.saturating_sub(1),
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    let external = dest_url.starts_with("http://")
+                        || dest_url.starts_with("https://")
+                        || dest_url.starts_with("mailto:");
+                    if external {
+                        r.ensure_para();
+                        r.links.push(dest_url.to_string());
+                        let open = format!("<w:hyperlink r:id=\"rIdLink{}\">", r.links.len());
+                        r.sink().push_str(&open);
+                        r.in_link = true;
+                    }
+                }
+                Event::End(TagEnd::Link) => {
+                    if r.in_link {
+                        r.sink().push_str("</w:hyperlink>");
+                        r.in_link = false;
+                    }
+                }
+                Event::Start(Tag::Image { .. }) => r.image_alt = Some(String::new()),
+                Event::Start(Tag::Table(alignments)) => {
+                    r.close_para();
+                    r.table = Some(TableState {
+                        alignments,
+                        ..Default::default()
+                    });
+                }
+                Event::Start(Tag::TableHead) => {
+                    if let Some(t) = r.table.as_mut() {
+                        t.in_head = true;
+                        t.column = 0;
+                    }
+                }
+                Event::Start(Tag::TableRow) => {
+                    if let Some(t) = r.table.as_mut() {
+                        t.column = 0;
+                    }
+                }
+                Event::Start(Tag::TableCell) => {
+                    if let Some(t) = r.table.as_mut() {
+                        t.cell = Some(String::new());
+                    }
+                    r.open_para();
+                }
+                Event::End(TagEnd::TableCell) => {
+                    r.close_para();
+                    r.end_table_cell();
+                }
+                Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                    if let Some(t) = r.table.as_mut() {
+                        let header = if t.in_head {
+                            "<w:trPr><w:tblHeader/></w:trPr>"
+                        } else {
+                            ""
+                        };
+                        t.rows
+                            .push_str(&format!("<w:tr>{}{}</w:tr>", header, t.row));
+                        t.row.clear();
+                        t.in_head = false;
+                    }
+                }
+                Event::End(TagEnd::Table) => {
+                    if let Some(t) = r.table.take() {
+                        r.body.push_str(&format!(
+                            r#"<w:tbl><w:tblPr><w:tblW w:w="5000" w:type="pct"/><w:tblBorders>{b}</w:tblBorders><w:tblCellMar><w:left w:w="80" w:type="dxa"/><w:right w:w="80" w:type="dxa"/></w:tblCellMar></w:tblPr>{rows}</w:tbl><w:p/>"#,
+                            b = table_borders(&r.border_color),
+                            rows = t.rows
+                        ));
+                    }
+                }
+                Event::Text(t) => r.push_text(&t),
+                Event::Code(t) => r.push_run(&t, Some("CodeChar")),
+                Event::InlineMath(t) | Event::DisplayMath(t) => r.push_run(&t, Some("CodeChar")),
+                Event::SoftBreak => r.push_text(" "),
+                Event::HardBreak => {
+                    r.ensure_para();
+                    r.sink().push_str("<w:r><w:br/></w:r>");
+                }
+                Event::Rule => {
+                    r.close_para();
+                    r.sink().push_str(r#"<w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="EEEEEE"/></w:pBdr></w:pPr></w:p>"#);
+                }
+                Event::FootnoteReference(name) => r.push_text(&format!("[{}]", name)),
+                _ => {}
+            }
+        }
+        r.close_para();
+
+        DocxBody {
+            xml: r.body,
+            links: r.links,
+        }
+    }
+
+    /// Output target: the open table cell, otherwise the document body.
+    fn sink(&mut self) -> &mut String {
+        match self.table.as_mut().and_then(|t| t.cell.as_mut()) {
+            Some(cell) => cell,
+            None => &mut self.body,
+        }
+    }
+
+    fn open_para(&mut self) {
+        let mut ppr = String::new();
+        if let Some(level) = self.heading {
+            ppr.push_str(&format!("<w:pStyle w:val=\"Heading{}\"/>", level));
+        } else if let Some(t) = self.table.as_ref().filter(|t| t.cell.is_some()) {
+            ppr.push_str("<w:pStyle w:val=\"TableText\"/>");
+            let jc = match t.alignments.get(t.column) {
+                Some(pulldown_cmark::Alignment::Center) => "center",
+                Some(pulldown_cmark::Alignment::Right) => "right",
+                _ => "left",
+            };
+            ppr.push_str(&format!("<w:jc w:val=\"{}\"/>", jc));
+        } else if !self.lists.is_empty() {
+            // Hanging indent so wrapped lines align with the text after the marker
+            let left = 360 * self.lists.len();
+            ppr.push_str(&format!(
+                "<w:pStyle w:val=\"ListParagraph\"/><w:ind w:left=\"{}\" w:hanging=\"360\"/>",
+                left
+            ));
+        } else if self.quote_depth > 0 {
+            ppr.push_str("<w:pStyle w:val=\"Quote\"/>");
+        }
+        let xml = format!("<w:p><w:pPr>{}</w:pPr>", ppr);
+        self.sink().push_str(&xml);
+        self.para_open = true;
+
+        if let Some(marker) = self.item_marker.take() {
+            let run = format!(
+                "<w:r><w:t xml:space=\"preserve\">{}</w:t></w:r><w:r><w:tab/></w:r>",
+                xml_escape(&marker)
+            );
+            self.sink().push_str(&run);
+        } else if !self.lists.is_empty() && self.heading.is_none() {
+            // Continuation paragraph of an item: align with the item text
+            self.sink().push_str("<w:r><w:tab/></w:r>");
+        }
+    }
+
+    fn ensure_para(&mut self) {
+        if !self.para_open {
+            self.open_para();
+        }
+    }
+
+    fn close_para(&mut self) {
+        if self.para_open {
+            if self.in_link {
+                self.sink().push_str("</w:hyperlink>");
+                self.in_link = false;
+            }
+            self.sink().push_str("</w:p>");
+            self.para_open = false;
+        }
+    }
+
+    fn end_table_cell(&mut self) {
+        let header_fill = self.header_fill.clone();
+        if let Some(t) = self.table.as_mut() {
+            let content = t.cell.take().unwrap_or_default();
+            let tcpr = if t.in_head {
+                format!(
+                    "<w:tcPr><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{}\"/></w:tcPr>",
+                    header_fill
+                )
+            } else {
+                String::new()
+            };
+            t.row.push_str(&format!("<w:tc>{}{}</w:tc>", tcpr, content));
+            t.column += 1;
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let style = if self.in_link {
+            Some("Hyperlink")
+        } else {
+            None
+        };
+        self.push_run(text, style);
+    }
+
+    fn push_run(&mut self, text: &str, char_style: Option<&str>) {
+        self.ensure_para();
+        let mut rpr = String::new();
+        if let Some(style) = char_style {
+            rpr.push_str(&format!("<w:rStyle w:val=\"{}\"/>", style));
+        }
+        if self.bold > 0 || self.table.as_ref().is_some_and(|t| t.in_head) {
+            rpr.push_str("<w:b/>");
+        }
+        if self.italic > 0 {
+            rpr.push_str("<w:i/>");
+        }
+        if self.strike > 0 {
+            rpr.push_str("<w:strike/>");
+        }
+        let rpr = if rpr.is_empty() {
+            String::new()
+        } else {
+            format!("<w:rPr>{}</w:rPr>", rpr)
+        };
+        let xml = format!(
+            "<w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r>",
+            rpr,
+            xml_escape(text)
+        );
+        self.sink().push_str(&xml);
+    }
+}
+
+fn table_borders(color: &str) -> String {
+    ["top", "left", "bottom", "right", "insideH", "insideV"]
+        .iter()
+        .map(|side| {
+            format!(
+                "<w:{} w:val=\"single\" w:sz=\"4\" w:color=\"{}\"/>",
+                side, color
+            )
+        })
+        .collect()
+}
+
+/// First concrete family from a CSS font list (`"'SF Mono', Monaco, monospace"` → `SF Mono`).
+fn first_font(css_fonts: &str, fallback: &str) -> String {
+    css_fonts
+        .split(',')
+        .map(|s| s.trim().trim_matches('\'').trim_matches('"'))
+        .find(|s| {
+            !s.is_empty()
+                && !s.starts_with('-')
+                && !matches!(
+                    *s,
+                    "serif" | "sans-serif" | "monospace" | "system-ui" | "BlinkMacSystemFont"
+                )
+        })
+        .unwrap_or(fallback)
+        .to_string()
+}
+
+/// `#0366d6` / `#eee` → `0366D6` / `EEEEEE` (WordprocessingML hex colors).
+fn docx_color(css: &str) -> Option<String> {
+    let hex = css.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        6 => Some(hex.to_ascii_uppercase()),
+        3 => Some(
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .collect::<String>()
+                .to_ascii_uppercase(),
+        ),
+        _ => None,
+    }
+}
+
+/// CSS length → twentieths of a point (twips).
+fn length_twips(len: &str) -> Option<u32> {
+    let len = len.trim();
+    let split = len.find(|c: char| c.is_ascii_alphabetic())?;
+    let value: f64 = len[..split].trim().parse().ok()?;
+    let factor = match &len[split..] {
+        "pt" => 20.0,
+        "cm" => 566.93,
+        "mm" => 56.693,
+        "in" => 1440.0,
+        "px" => 15.0,
+        _ => return None,
+    };
+    Some((value * factor).round() as u32)
+}
+
+/// CSS font size → half-points (`w:sz`).
+fn half_points(size: &str) -> Option<u32> {
+    length_twips(size).map(|twips| (twips + 5) / 10)
+}
+
+fn page_size_twips(name: &str) -> (u32, u32) {
+    match name.to_ascii_lowercase().as_str() {
+        "us-letter" | "letter" => (12240, 15840),
+        "us-legal" | "legal" => (12240, 20160),
+        "a5" => (8391, 11906),
+        "a3" => (16838, 23811),
+        _ => (11906, 16838), // A4
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    // Drop control characters that are invalid in XML 1.0
+    s.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(md: &str) -> String {
+        DocxRenderer::run(md, &DocumentConfig::default()).xml
+    }
+
+    #[test]
+    fn test_headings_use_heading_styles() {
+        let xml = body("# Title\n\n### Sub");
+        assert!(xml.contains("<w:pStyle w:val=\"Heading1\"/>"));
+        assert!(xml.contains("<w:pStyle w:val=\"Heading3\"/>"));
+        assert!(xml.contains(">Title</w:t>"));
+    }
+
+    #[test]
+    fn test_front_matter_not_rendered() {
+        let xml = body("---\ntitle: Intern\n---\n# Hallo");
+        assert!(!xml.contains("title:"));
+        assert!(xml.contains(">Hallo</w:t>"));
+    }
+
+    #[test]
+    fn test_metadata_in_core_and_header() {
+        let mut options = ExportOptions {
+            title: "Handbuch".to_string(),
+            author: "Jane Doe".to_string(),
+            date: "01.01.2026".to_string(),
+            format: crate::browser::pdf_export::ExportFormat::Docx,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        };
+        options.meta.subtitle = "Teil 1".to_string();
+        options.meta.tags = vec!["ops".to_string(), "intern".to_string()];
+        options.meta.confidentiality = "Vertraulich".to_string();
+
+        let core = core_properties(&options);
+        assert!(core.contains("<cp:keywords>ops, intern</cp:keywords>"));
+        assert!(core.contains("<dc:subject>Teil 1</dc:subject>"));
+        let header = header_xml(&options, &DocumentConfig::default());
+        assert!(header.contains(">Handbuch — Teil 1</w:t>"));
+        assert!(header.contains(">Vertraulich</w:t>"));
+    }
+
+    #[test]
+    fn test_inline_formatting_runs() {
+        let xml = body("**fett** _kursiv_ `code`");
+        assert!(xml.contains("<w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">fett</w:t>"));
+        assert!(xml.contains("<w:rPr><w:i/></w:rPr><w:t xml:space=\"preserve\">kursiv</w:t>"));
+        assert!(xml.contains("<w:rStyle w:val=\"CodeChar\"/>"));
+    }
+
+    #[test]
+    fn test_code_block_one_paragraph_per_line() {
+        let xml = body("```\nfn a() {}\nlet x = 1 < 2;\n```");
+        assert_eq!(xml.matches("<w:pStyle w:val=\"Code\"/>").count(), 2);
+        assert!(xml.contains("let x = 1 &lt; 2;"));
+    }
+
+    #[test]
+    fn test_table_with_header_row() {
+        let xml = body("| A | B |\n|---|--:|\n| 1 | 2 |");
+        assert!(xml.contains("<w:tbl>"));
+        assert_eq!(xml.matches("<w:tr>").count(), 2);
+        assert_eq!(xml.matches("<w:tc>").count(), 4);
+        assert!(xml.contains("<w:tblHeader/>"));
+        assert!(xml.contains("<w:jc w:val=\"right\"/>"));
+    }
+
+    #[test]
+    fn test_lists_get_markers() {
+        let xml = body("- eins\n- zwei\n\n3. drei\n4. vier");
+        assert_eq!(xml.matches(">\u{2022}</w:t>").count(), 2);
+        assert!(xml.contains(">3.</w:t>"));
+        assert!(xml.contains(">4.</w:t>"));
+    }
+
+    #[test]
+    fn test_external_links_become_hyperlinks() {
+        let r = DocxRenderer::run(
+            "[Site](https://example.com) und [intern](#abschnitt)",
+            &DocumentConfig::default(),
+        );
+        assert_eq!(r.links, vec!["https://example.com".to_string()]);
+        assert!(r.xml.contains("<w:hyperlink r:id=\"rIdLink1\">"));
+        assert_eq!(r.xml.matches("<w:hyperlink").count(), 1);
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(docx_color("#0366d6").as_deref(), Some("0366D6"));
+        assert_eq!(docx_color("#eee").as_deref(), Some("EEEEEE"));
+        assert_eq!(docx_color("red"), None);
+        assert_eq!(length_twips("2.5cm"), Some(1417));
+        assert_eq!(half_points("11pt"), Some(22));
+        assert_eq!(
+            first_font("-apple-system, 'Segoe UI', sans-serif", "Calibri"),
+            "Segoe UI"
+        );
+    }
+
+    #[test]
+    fn test_export_writes_docx_package() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("doc.md");
+        std::fs::write(&src, "# Hallo\n\nText").unwrap();
+        let target = dir.path().join("doc.docx");
+        let options = ExportOptions {
+            title: "Hallo".to_string(),
+            author: "Musterfirma".to_string(),
+            date: "01.01.2026".to_string(),
+            format: crate::browser::pdf_export::ExportFormat::Docx,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        };
+        let doc = DocumentConfig::default();
+        export_markdown_to_docx(&src, &target, &options, &doc).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&target).unwrap()).unwrap();
+        let mut document = String::new();
+        archive
+            .by_name("word/document.xml")
+            .unwrap()
+            .read_to_string(&mut document)
+            .unwrap();
+        assert!(document.contains("Hallo"));
+        let mut footer = String::new();
+        archive
+            .by_name("word/footer1.xml")
+            .unwrap()
+            .read_to_string(&mut footer)
+            .unwrap();
+        assert!(footer.contains("Generated by Musterfirma"));
+        assert!(footer.contains("NUMPAGES"));
+    }
+}
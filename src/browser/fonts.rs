@@ -0,0 +1,144 @@
+//! Font resolution for PDF export.
+//!
+//! `DocFontConfig` holds CSS font-family lists (shared with the HTML
+//! templates). The PDF exporter resolves them against the installed fonts
+//! (system fontconfig dirs + `~/.config/claude-workbench/fonts`) and reports
+//! which family was actually used and which glyphs fell back to other fonts.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Bundled body font used when no configured family is installed
+pub const BUNDLED_BODY_FONT: &str = "Carlito";
+
+/// Maximum number of fallback characters listed per font in the summary
+const MAX_LISTED_CHARS: usize = 8;
+
+/// Font family names of a CSS `font-family` list, without quotes and
+/// generic keywords (`sans-serif`, `monospace`, `-apple-system`, ...).
+pub fn css_font_families(css: &str) -> Vec<String> {
+    css.split(',')
+        .map(|s| s.trim().trim_matches('\'').trim_matches('"').trim())
+        .filter(|s| !s.is_empty() && !is_generic_family(s))
+        .map(String::from)
+        .collect()
+}
+
+fn is_generic_family(name: &str) -> bool {
+    matches!(
+        name.to_lowercase().as_str(),
+        "serif"
+            | "sans-serif"
+            | "monospace"
+            | "cursive"
+            | "fantasy"
+            | "system-ui"
+            | "ui-monospace"
+            | "ui-sans-serif"
+            | "ui-serif"
+            | "-apple-system"
+            | "blinkmacsystemfont"
+    )
+}
+
+/// First family of `css` for which `is_installed` returns true.
+pub fn resolve_font_family(css: &str, is_installed: impl Fn(&str) -> bool) -> Option<String> {
+    css_font_families(css)
+        .into_iter()
+        .find(|family| is_installed(family))
+}
+
+/// Fonts used by a PDF export.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontReport {
+    /// First configured body family (what the user asked for)
+    pub body_requested: Option<String>,
+    /// Body family actually used
+    pub body: String,
+    /// Code family actually used, including the fallbacks appended to the
+    /// configured list (`None` = none of them installed)
+    pub code: Option<String>,
+    /// Characters rendered with a font other than the body/code font, by family
+    pub fallbacks: BTreeMap<String, BTreeSet<char>>,
+}
+
+impl FontReport {
+    /// True if the requested body font was used and nothing fell back
+    pub fn is_clean(&self) -> bool {
+        self.body_requested.as_deref() == Some(self.body.as_str()) && self.fallbacks.is_empty()
+    }
+
+    /// One-line summary, e.g.
+    /// `Font: Carlito (instead of Calibri) · Code: DejaVu Sans Mono · Fallback DejaVu Sans: → ✓`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        match &self.body_requested {
+            Some(requested) if *requested != self.body => {
+                parts.push(format!("Font: {} (instead of {})", self.body, requested))
+            }
+            _ => parts.push(format!("Font: {}", self.body)),
+        }
+        if let Some(code) = &self.code {
+            parts.push(format!("Code: {}", code));
+        }
+        for (family, chars) in &self.fallbacks {
+            let mut listed: String = chars
+                .iter()
+                .take(MAX_LISTED_CHARS)
+                .flat_map(|c| [*c, ' '])
+                .collect();
+            listed.pop();
+            if chars.len() > MAX_LISTED_CHARS {
+                listed.push_str(&format!(" (+{})", chars.len() - MAX_LISTED_CHARS));
+            }
+            parts.push(format!("Fallback {}: {}", family, listed));
+        }
+        parts.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_font_families_drop_generics() {
+        assert_eq!(
+            css_font_families("Calibri, -apple-system, 'Segoe UI', \"Open Sans\", sans-serif"),
+            vec!["Calibri", "Segoe UI", "Open Sans"]
+        );
+        assert!(css_font_families("monospace").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_font_family_picks_first_installed() {
+        let installed = |f: &str| f == "Roboto" || f == "Ubuntu";
+        assert_eq!(
+            resolve_font_family("Calibri, Roboto, Ubuntu", installed).as_deref(),
+            Some("Roboto")
+        );
+        assert_eq!(resolve_font_family("Calibri, Arial", installed), None);
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let mut report = FontReport {
+            body_requested: Some("Calibri".to_string()),
+            body: "Carlito".to_string(),
+            code: Some("DejaVu Sans Mono".to_string()),
+            fallbacks: BTreeMap::new(),
+        };
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.summary(),
+            "Font: Carlito (instead of Calibri) · Code: DejaVu Sans Mono"
+        );
+
+        report.body = "Calibri".to_string();
+        assert!(report.is_clean());
+        report
+            .fallbacks
+            .insert("DejaVu Sans".to_string(), ['✓', '→'].into_iter().collect());
+        assert!(!report.is_clean());
+        assert!(report.summary().ends_with("Fallback DejaVu Sans: → ✓"));
+    }
+}
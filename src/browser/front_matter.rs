@@ -0,0 +1,387 @@
+//! YAML front matter for Markdown exports.
+//!
+//! A leading `---` … `---` block supplies document metadata (title, subtitle,
+//! author, version, date, tags, confidentiality) and per-file overrides of
+//! `DocumentConfig` (page size, margins, template, language, cover page, TOC)
+//! and may pick a branding profile.
+//! The renderers strip the block from the body via pulldown-cmark's metadata
+//! block support; this module only reads it.
+
+use std::path::Path;
+
+use serde_yaml_ng::Value;
+
+use crate::browser::pdf_export::ExportOptions;
+use crate::config::{is_known_page_size, is_valid_length, DocumentConfig};
+
+/// Parsed front matter. Every field is optional; unknown keys are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    pub confidentiality: Option<String>,
+    // --- DocumentConfig overrides ---
+    pub page_size: Option<String>,
+    pub margin: Option<String>,
+    pub margin_top: Option<String>,
+    pub margin_right: Option<String>,
+    pub margin_bottom: Option<String>,
+    pub margin_left: Option<String>,
+    pub template: Option<String>,
+    pub profile: Option<String>,
+    pub language: Option<String>,
+    pub cover_page: Option<bool>,
+    pub toc: Option<bool>,
+    pub logo: Option<String>,
+}
+
+/// Metadata from front matter that has no home in `DocumentConfig`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentMeta {
+    pub subtitle: String,
+    pub tags: Vec<String>,
+    pub confidentiality: String,
+}
+
+/// Split a leading YAML front matter block from Markdown source.
+/// Returns the raw YAML and the remaining body; `None` if there is no block.
+pub fn split_front_matter(md: &str) -> Option<(&str, &str)> {
+    let md = md.strip_prefix('\u{feff}').unwrap_or(md);
+    let rest = md
+        .strip_prefix("---\n")
+        .or_else(|| md.strip_prefix("---\r\n"))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return Some((yaml, body));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Parse front matter from Markdown source. Malformed YAML yields `None`
+/// (the block is then simply exported without overrides).
+pub fn parse_front_matter(md: &str) -> Option<FrontMatter> {
+    let (yaml, _) = split_front_matter(md)?;
+    let value: Value = serde_yaml_ng::from_str(yaml).ok()?;
+    let map = value.as_mapping()?;
+    let get = |key: &str| map.get(key).and_then(scalar_string);
+    let get_bool = |key: &str| map.get(key).and_then(Value::as_bool);
+
+    let tags = match map.get("tags").or_else(|| map.get("keywords")) {
+        Some(Value::Sequence(items)) => items.iter().filter_map(scalar_string).collect(),
+        Some(other) => scalar_string(other)
+            .map(|s| {
+                s.split(',')
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    Some(FrontMatter {
+        title: get("title"),
+        subtitle: get("subtitle"),
+        author: get("author"),
+        version: get("version"),
+        date: get("date"),
+        tags,
+        confidentiality: get("confidentiality").or_else(|| get("classification")),
+        page_size: get("page_size"),
+        margin: get("margin"),
+        margin_top: get("margin_top"),
+        margin_right: get("margin_right"),
+        margin_bottom: get("margin_bottom"),
+        margin_left: get("margin_left"),
+        template: get("template"),
+        profile: get("profile"),
+        language: get("language").or_else(|| get("lang")),
+        cover_page: get_bool("cover_page"),
+        toc: get_bool("toc"),
+        logo: get("logo"),
+    })
+}
+
+/// Read front matter from a Markdown file (`None` if unreadable or absent).
+pub fn read_front_matter(path: &Path) -> Option<FrontMatter> {
+    let md = std::fs::read_to_string(path).ok()?;
+    parse_front_matter(&md)
+}
+
+/// Drop front matter (metadata block) events from a pulldown-cmark stream.
+/// Parsers must enable `Options::ENABLE_YAML_STYLE_METADATA_BLOCKS`.
+pub(crate) fn skip_metadata_blocks<'a>(
+    events: impl Iterator<Item = pulldown_cmark::Event<'a>>,
+) -> impl Iterator<Item = pulldown_cmark::Event<'a>> {
+    use pulldown_cmark::{Event, Tag, TagEnd};
+
+    let mut inside = false;
+    events.filter(move |event| match event {
+        Event::Start(Tag::MetadataBlock(_)) => {
+            inside = true;
+            false
+        }
+        Event::End(TagEnd::MetadataBlock(_)) => {
+            inside = false;
+            false
+        }
+        _ => !inside,
+    })
+}
+
+/// Render a YAML scalar as text (numbers like `version: 1.2` included).
+fn scalar_string(value: &Value) -> Option<String> {
+    let s = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        _ => return None,
+    };
+    let s = s.trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+impl FrontMatter {
+    /// Apply metadata and per-file overrides to export options and settings.
+    /// A relative `logo` is relative to `md_dir`, the Markdown file's folder.
+    /// Page size and margins end up in the Typst source, so invalid values are
+    /// rejected: the configured value stays and a warning is returned.
+    pub fn apply(
+        &self,
+        options: &mut ExportOptions,
+        doc: &mut DocumentConfig,
+        md_dir: &Path,
+    ) -> Vec<String> {
+        let set = |target: &mut String, value: &Option<String>| {
+            if let Some(v) = value {
+                *target = v.clone();
+            }
+        };
+
+        set(&mut options.title, &self.title);
+        set(&mut options.author, &self.author);
+        set(&mut options.date, &self.date);
+        set(&mut options.meta.subtitle, &self.subtitle);
+        set(&mut options.meta.confidentiality, &self.confidentiality);
+        if !self.tags.is_empty() {
+            options.meta.tags = self.tags.clone();
+        }
+        if let Some(template) = &self.template {
+            // "default"/"standard" selects the built-in layout explicitly
+            options.template = match template.to_ascii_lowercase().as_str() {
+                "default" | "standard" | "builtin" => None,
+                _ => Some(template.clone()),
+            };
+        }
+
+        set(&mut doc.template.version, &self.version);
+        set(&mut doc.template.language, &self.language);
+        if let Some(logo) = &self.logo {
+            let path = Path::new(logo);
+            doc.template.logo = if logo.starts_with('~') || path.is_absolute() {
+                logo.clone()
+            } else {
+                md_dir.join(path).to_string_lossy().into_owned()
+            };
+        }
+        if let Some(cover) = self.cover_page {
+            doc.template.cover_page = cover;
+        }
+        if let Some(toc) = self.toc {
+            doc.template.toc = toc;
+        }
+
+        let mut warnings = Vec::new();
+        let mut checked =
+            |target: &mut String, key: &str, value: &Option<String>, valid: fn(&str) -> bool| {
+                match value {
+                    Some(v) if valid(v) => *target = v.trim().to_string(),
+                    Some(v) => warnings.push(format!("Front matter {}: \"{}\" ignored", key, v)),
+                    None => {}
+                }
+            };
+        let pdf = &mut doc.pdf;
+        checked(
+            &mut pdf.page_size,
+            "page_size",
+            &self.page_size,
+            is_known_page_size,
+        );
+        checked(&mut pdf.margin, "margin", &self.margin, is_valid_length);
+        checked(
+            &mut pdf.margin_top,
+            "margin_top",
+            &self.margin_top,
+            is_valid_length,
+        );
+        checked(
+            &mut pdf.margin_right,
+            "margin_right",
+            &self.margin_right,
+            is_valid_length,
+        );
+        checked(
+            &mut pdf.margin_bottom,
+            "margin_bottom",
+            &self.margin_bottom,
+            is_valid_length,
+        );
+        checked(
+            &mut pdf.margin_left,
+            "margin_left",
+            &self.margin_left,
+            is_valid_length,
+        );
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::pdf_export::ExportFormat;
+
+    const SAMPLE: &str = "---\ntitle: Betriebshandbuch\nsubtitle: Teil 1\nauthor: Jane Doe\nversion: 1.2\ndate: 2026-03-01\ntags: [intern, ops]\nconfidentiality: Vertraulich\npage_size: Letter\nmargin: 2cm\ntemplate: firma\ntoc: true\n---\n# Body\n";
+
+    fn options() -> ExportOptions {
+        ExportOptions {
+            title: "file-stem".to_string(),
+            author: "Musterfirma".to_string(),
+            date: "01.01.2026".to_string(),
+            format: ExportFormat::Pdf,
+            template: None,
+            meta: DocumentMeta::default(),
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn test_split_front_matter() {
+        let (yaml, body) = split_front_matter(SAMPLE).unwrap();
+        assert!(yaml.starts_with("title: Betriebshandbuch"));
+        assert_eq!(body, "# Body\n");
+        assert!(split_front_matter("# No front matter\n---\n").is_none());
+        assert!(split_front_matter("---\nunterminated: true\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_front_matter_fields() {
+        let fm = parse_front_matter(SAMPLE).unwrap();
+        assert_eq!(fm.title.as_deref(), Some("Betriebshandbuch"));
+        assert_eq!(fm.version.as_deref(), Some("1.2"));
+        assert_eq!(fm.date.as_deref(), Some("2026-03-01"));
+        assert_eq!(fm.tags, vec!["intern".to_string(), "ops".to_string()]);
+        assert_eq!(fm.toc, Some(true));
+        assert_eq!(fm.cover_page, None);
+    }
+
+    #[test]
+    fn test_tags_as_comma_string() {
+        let fm = parse_front_matter("---\ntags: a, b ,c\n---\n").unwrap();
+        assert_eq!(fm.tags, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_malformed_yaml_is_ignored() {
+        assert!(parse_front_matter("---\n: [unclosed\n---\n").is_none());
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let fm = parse_front_matter(SAMPLE).unwrap();
+        let mut opts = options();
+        let mut doc = DocumentConfig::default();
+        assert!(fm.apply(&mut opts, &mut doc, Path::new("docs")).is_empty());
+
+        assert_eq!(opts.title, "Betriebshandbuch");
+        assert_eq!(opts.author, "Jane Doe");
+        assert_eq!(opts.date, "2026-03-01");
+        assert_eq!(opts.template.as_deref(), Some("firma"));
+        assert_eq!(opts.meta.subtitle, "Teil 1");
+        assert_eq!(opts.meta.confidentiality, "Vertraulich");
+        assert_eq!(doc.template.version, "1.2");
+        assert!(doc.template.toc);
+        assert_eq!(doc.pdf.page_size, "Letter");
+        assert_eq!(doc.pdf.margin, "2cm");
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_page_values() {
+        let fm = parse_front_matter(
+            "---\npage_size: 'a4\") #eval(\"1\")'\nmargin: 2 cm\nmargin_top: 12.5mm\n---\n",
+        )
+        .unwrap();
+        let mut opts = options();
+        let mut doc = DocumentConfig::default();
+        let warnings = fm.apply(&mut opts, &mut doc, Path::new("."));
+
+        assert_eq!(doc.pdf.page_size, "A4");
+        assert_eq!(doc.pdf.margin, "2.5cm");
+        assert_eq!(doc.pdf.margin_top, "12.5mm");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("Front matter page_size:"));
+        assert_eq!(warnings[1], "Front matter margin: \"2 cm\" ignored");
+    }
+
+    #[test]
+    fn test_relative_logo_resolves_against_markdown_folder() {
+        let mut opts = options();
+        let mut doc = DocumentConfig::default();
+        let fm = parse_front_matter(
+            "---
+logo: img/logo.png
+---
+",
+        )
+        .unwrap();
+        fm.apply(&mut opts, &mut doc, Path::new("/work/docs"));
+        assert_eq!(
+            doc.template.logo_path(),
+            Some(std::path::PathBuf::from("/work/docs/img/logo.png"))
+        );
+
+        let fm = parse_front_matter(
+            "---
+logo: ~/logo.png
+---
+",
+        )
+        .unwrap();
+        fm.apply(&mut opts, &mut doc, Path::new("/work/docs"));
+        assert_eq!(doc.template.logo, "~/logo.png");
+    }
+
+    #[test]
+    fn test_skip_metadata_blocks() {
+        use pulldown_cmark::{Event, Options, Parser};
+
+        let parser = Parser::new_ext(SAMPLE, Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+        let texts: Vec<String> = skip_metadata_blocks(parser)
+            .filter_map(|e| match e {
+                Event::Text(t) => Some(t.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts, vec!["Body".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_keeps_unset_values() {
+        let fm = parse_front_matter("---\ntitle: Nur Titel\n---\n").unwrap();
+        let mut opts = options();
+        let mut doc = DocumentConfig::default();
+        fm.apply(&mut opts, &mut doc, Path::new("."));
+        assert_eq!(opts.author, "Musterfirma");
+        assert_eq!(doc.pdf.page_size, "A4");
+    }
+}
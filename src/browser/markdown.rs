@@ -17,6 +17,7 @@ fn build_html_template(doc: &DocumentConfig) -> String {
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>{{title}}</title>
+{{head_meta}}
     <style>
         :root {{
             color-scheme: light dark;
@@ -26,6 +27,8 @@ fn build_html_template(doc: &DocumentConfig) -> String {
         {code}
         {blockquote}
         img {{ max-width: 100%; height: auto; }}
+        .diagram {{ text-align: center; margin: 1em 0; }}
+        math[display="block"] {{ margin: 1em 0; overflow-x: auto; }}
         {table}
         {link}
         ul, ol {{ padding-left: 2em; }}
@@ -54,6 +57,24 @@ fn build_html_template(doc: &DocumentConfig) -> String {
     )
 }
 
+/// Render Markdown source to an HTML fragment (extensions, heading ids,
+/// diagrams and math applied). Image paths are left as written.
+fn render_markdown_fragment(md_content: &str, doc: &DocumentConfig) -> String {
+    use pulldown_cmark::{html, Event, Options, Parser};
+
+    // Enable all markdown extensions
+    let options = Options::all();
+    let parser = Parser::new_ext(md_content, options);
+    let events: Vec<Event> = parser.collect();
+    let events = inject_heading_ids(events);
+    let events = render_diagram_blocks(events, &doc.diagrams);
+    let events = render_math(events);
+
+    let mut html_content = String::new();
+    html::push_html(&mut html_content, events.into_iter());
+    html_content
+}
+
 /// Convert a single markdown file to an HTML string without writing to disk.
 /// Returns (html_string, secure_temp_file).
 /// The `NamedTempFile` is created with O_EXCL but not yet written; content is
@@ -63,36 +84,14 @@ fn convert_single_md(
     doc: &DocumentConfig,
     project_name: &str,
 ) -> Result<(String, tempfile::NamedTempFile)> {
-    use pulldown_cmark::{html, Event, Options, Parser};
-
-    let md_content = std::fs::read_to_string(md_path)?;
-    let title = md_path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("Preview");
-
     // Get the directory of the markdown file for resolving relative paths
     let md_dir = md_path
         .parent()
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-    // Enable all markdown extensions
-    let options = Options::all();
-    let parser = Parser::new_ext(&md_content, options);
-    let events: Vec<Event> = parser.collect();
-    let events = inject_heading_ids(events);
-
-    let mut html_content = String::new();
-    html::push_html(&mut html_content, events.into_iter());
-
     // Convert relative image paths to absolute file:// URLs
-    let html_content = fix_image_paths(&html_content, &md_dir);
-
-    let template = build_html_template(doc);
-    let html = template
-        .replace("{title}", title)
-        .replace("{content}", &html_content);
+    let html = render_markdown_page(md_path, doc, |content| fix_image_paths(content, &md_dir))?;
 
     // Create secure temp file (O_EXCL) — content written after link rewriting
     let tmp = crate::browser::pdf_export::default_preview_file(md_path, project_name)?;
@@ -100,9 +99,104 @@ fn convert_single_md(
     Ok((html, tmp))
 }
 
+/// Render a Markdown file as a complete HTML page. `rewrite` post-processes
+/// the body fragment (e.g. image paths); relative links are kept as written.
+pub(crate) fn render_markdown_page(
+    md_path: &Path,
+    doc: &DocumentConfig,
+    rewrite: impl FnOnce(&str) -> String,
+) -> Result<String> {
+    let md_content = std::fs::read_to_string(md_path)?;
+    let fm = crate::browser::front_matter::parse_front_matter(&md_content);
+    // Branding profile: front matter, else path rules
+    let doc = &doc.resolve_profile(fm.as_ref().and_then(|fm| fm.profile.as_deref()), md_path)?;
+    let html_content = rewrite(&render_markdown_fragment(&md_content, doc));
+
+    // Front matter title wins over the file name
+    let title = fm.and_then(|fm| fm.title).unwrap_or_else(|| {
+        md_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Preview")
+            .to_string()
+    });
+
+    Ok(build_html_template(doc)
+        .replace("{title}", &html_escape(&title))
+        .replace("{head_meta}", "")
+        .replace("{content}", &html_content))
+}
+
+/// Export a Markdown file as a single self-contained HTML document.
+///
+/// Uses the preview template (branding from `DocumentConfig`), but local images
+/// are embedded as base64 data URIs so the file can be mailed or archived
+/// without its source folder.
+pub fn export_markdown_to_html(
+    source: &Path,
+    target: &Path,
+    options: &crate::browser::pdf_export::ExportOptions,
+    doc: &DocumentConfig,
+) -> Result<PathBuf> {
+    let md_content = std::fs::read_to_string(source)?;
+    let md_dir = source
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+
+    let html_content = render_markdown_fragment(&md_content, doc);
+    let html_content = inline_image_paths(&html_content, &md_dir);
+
+    let html = build_html_template(doc)
+        .replace("{title}", &html_escape(&options.title))
+        .replace("{head_meta}", &head_meta(options))
+        .replace("{content}", &html_content);
+    std::fs::write(target, html)?;
+    Ok(target.to_path_buf())
+}
+
+/// `<meta>` tags for author, subtitle and keywords of an exported document.
+fn head_meta(options: &crate::browser::pdf_export::ExportOptions) -> String {
+    let meta = &options.meta;
+    [
+        ("author", options.author.clone()),
+        ("description", meta.subtitle.clone()),
+        ("keywords", meta.tags.join(", ")),
+    ]
+    .iter()
+    .filter(|(_, value)| !value.trim().is_empty())
+    .map(|(name, value)| {
+        format!(
+            "    <meta name=\"{}\" content=\"{}\">\n",
+            name,
+            html_escape(value.trim())
+        )
+    })
+    .collect()
+}
+
+/// Split a relative `.md` link into path and fragment (without `#`).
+/// Returns `None` for absolute URLs/paths and non-Markdown targets.
+/// Shared by the browser preview link rewriting and the PDF book export.
+pub(crate) fn split_relative_md_link(href: &str) -> Option<(&str, &str)> {
+    // Skip absolute URLs and absolute paths
+    if href.starts_with("http://")
+        || href.starts_with("https://")
+        || href.starts_with("file://")
+        || href.starts_with('/')
+    {
+        return None;
+    }
+    let (path, fragment) = href.split_once('#').unwrap_or((href, ""));
+    if !path.ends_with(".md") {
+        return None;
+    }
+    Some((path, fragment))
+}
+
 /// Collect relative `.md` link hrefs from rendered HTML.
 /// Returns raw href values like `"USAGE.md"`, `"./INSTALL.md#section"`.
-fn collect_md_links(html: &str) -> Vec<String> {
+pub(crate) fn collect_md_links(html: &str) -> Vec<String> {
     use regex::Regex;
     use std::sync::LazyLock;
 
@@ -110,20 +204,11 @@ fn collect_md_links(html: &str) -> Vec<String> {
         Regex::new(r#"<a\s[^>]*?href="([^"]*\.md(?:#[^"]*)?)"[^>]*>"#).expect("valid regex")
     });
 
-    let mut links = Vec::new();
-    for cap in LINK_RE.captures_iter(html) {
-        let href = &cap[1];
-        // Skip absolute URLs and absolute paths
-        if href.starts_with("http://")
-            || href.starts_with("https://")
-            || href.starts_with("file://")
-            || href.starts_with('/')
-        {
-            continue;
-        }
-        links.push(href.to_string());
-    }
-    links
+    LINK_RE
+        .captures_iter(html)
+        .map(|cap| cap[1].to_string())
+        .filter(|href| split_relative_md_link(href).is_some())
+        .collect()
 }
 
 /// Rewrite relative `.md` links in HTML to point to converted temp HTML files.
@@ -144,11 +229,7 @@ fn fix_md_links(html: &str, link_map: &HashMap<String, PathBuf>) -> String {
             let suffix = &caps[4];
 
             // Skip absolute URLs
-            if md_href.starts_with("http://")
-                || md_href.starts_with("https://")
-                || md_href.starts_with("file://")
-                || md_href.starts_with('/')
-            {
+            if split_relative_md_link(md_href).is_none() {
                 return caps[0].to_string();
             }
 
@@ -299,6 +380,151 @@ fn fix_image_paths(html: &str, base_dir: &Path) -> String {
         .to_string()
 }
 
+/// Replace local image `src` attributes with base64 data URIs.
+/// Remote, missing or out-of-tree images keep their original reference.
+fn inline_image_paths(html: &str, base_dir: &Path) -> String {
+    use base64::Engine;
+    use regex::Regex;
+    use std::sync::LazyLock;
+
+    static IMG_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"<img\s+([^>]*?)src="([^"]+)"([^>]*)>"#).expect("valid regex")
+    });
+
+    let canonical_base = base_dir
+        .canonicalize()
+        .unwrap_or_else(|_| base_dir.to_path_buf());
+
+    IMG_RE
+        .replace_all(html, |caps: &regex::Captures| {
+            let src = &caps[2];
+            if src.contains("://") || src.starts_with("data:") {
+                return caps[0].to_string();
+            }
+            let Some(mime) = image_mime_type(src) else {
+                return caps[0].to_string();
+            };
+            // Same path traversal guard as fix_image_paths
+            let bytes = base_dir
+                .join(src)
+                .canonicalize()
+                .ok()
+                .filter(|p| p.starts_with(&canonical_base))
+                .and_then(|p| std::fs::read(p).ok());
+            match bytes {
+                Some(bytes) => format!(
+                    r#"<img {}src="data:{};base64,{}"{}>"#,
+                    &caps[1],
+                    mime,
+                    base64::engine::general_purpose::STANDARD.encode(bytes),
+                    &caps[3]
+                ),
+                None => caps[0].to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// MIME type for an image path, by extension.
+pub(crate) fn image_mime_type(path: &str) -> Option<&'static str> {
+    let ext = Path::new(path).extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        _ => return None,
+    })
+}
+
+/// Escape text for an HTML text node / attribute.
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replace `$…$` / `$$…$$` math with MathML, which browsers render natively
+/// (no MathJax/KaTeX download). Expressions the translator cannot parse keep
+/// pulldown-cmark's default `<span class="math …">` output.
+fn render_math(events: Vec<pulldown_cmark::Event<'_>>) -> Vec<pulldown_cmark::Event<'_>> {
+    use crate::browser::math::latex_to_mathml;
+    use pulldown_cmark::Event;
+
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::InlineMath(text) => match latex_to_mathml(&text, false) {
+                Some(mathml) => Event::InlineHtml(mathml.into()),
+                None => Event::InlineMath(text),
+            },
+            Event::DisplayMath(text) => match latex_to_mathml(&text, true) {
+                Some(mathml) => Event::InlineHtml(mathml.into()),
+                None => Event::DisplayMath(text),
+            },
+            other => other,
+        })
+        .collect()
+}
+
+/// Replace fenced Mermaid/PlantUML code blocks with rendered SVG images.
+///
+/// Blocks whose renderer is unavailable or fails are left untouched, so they
+/// still appear as ordinary code blocks in the output.
+fn render_diagram_blocks<'a>(
+    events: Vec<pulldown_cmark::Event<'a>>,
+    cfg: &crate::config::DiagramConfig,
+) -> Vec<pulldown_cmark::Event<'a>> {
+    use crate::browser::diagram::{render_diagram, svg_data_uri, DiagramKind};
+    use pulldown_cmark::{CodeBlockKind, CowStr, Event, Tag, TagEnd};
+
+    if !cfg.enabled {
+        return events;
+    }
+
+    let mut out = Vec::with_capacity(events.len());
+    let mut iter = events.into_iter();
+    while let Some(event) = iter.next() {
+        let kind = match &event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                DiagramKind::from_info(info)
+            }
+            _ => None,
+        };
+        let Some(kind) = kind else {
+            out.push(event);
+            continue;
+        };
+
+        // Buffer the block so it can be emitted unchanged on failure
+        let mut block = vec![event];
+        let mut source = String::new();
+        for inner in iter.by_ref() {
+            let is_end = matches!(inner, Event::End(TagEnd::CodeBlock));
+            if let Event::Text(t) = &inner {
+                source.push_str(t);
+            }
+            block.push(inner);
+            if is_end {
+                break;
+            }
+        }
+
+        match render_diagram(kind, &source, cfg) {
+            Some(diagram) => out.push(Event::Html(CowStr::from(format!(
+                "<div class=\"diagram\"><img src=\"{}\" alt=\"diagram\"></div>\n",
+                svg_data_uri(&diagram.svg)
+            )))),
+            None => out.extend(block),
+        }
+    }
+    out
+}
+
 /// Inject `id` attributes into heading events so that internal anchor links work.
 ///
 /// pulldown-cmark does not auto-generate `id` on headings — it only sets `id`
@@ -443,6 +669,18 @@ mod tests {
         assert_eq!(links, vec!["./USAGE.md"]);
     }
 
+    #[test]
+    fn test_split_relative_md_link() {
+        assert_eq!(
+            split_relative_md_link("./guide/a.md#setup"),
+            Some(("./guide/a.md", "setup"))
+        );
+        assert_eq!(split_relative_md_link("a.md"), Some(("a.md", "")));
+        assert_eq!(split_relative_md_link("https://x.org/a.md"), None);
+        assert_eq!(split_relative_md_link("/abs/a.md"), None);
+        assert_eq!(split_relative_md_link("image.png"), None);
+    }
+
     // --- Tests for fix_md_links ---
 
     #[test]
@@ -500,4 +738,65 @@ mod tests {
             result
         );
     }
+
+    // --- Tests for render_math ---
+
+    fn math_fragment(md: &str) -> String {
+        use pulldown_cmark::{html, Event, Options, Parser};
+
+        let events: Vec<Event> = Parser::new_ext(md, Options::all()).collect();
+        let mut out = String::new();
+        html::push_html(&mut out, render_math(events).into_iter());
+        out
+    }
+
+    #[test]
+    fn test_math_rendered_as_mathml() {
+        let out = math_fragment("Inline $x^2$ and\n\n$$\\sqrt{2}$$");
+        assert!(out.contains("<math><msup><mi>x</mi><mn>2</mn></msup></math>"));
+        assert!(out.contains("<math display=\"block\"><msqrt><mn>2</mn></msqrt></math>"));
+    }
+
+    #[test]
+    fn test_unparseable_math_keeps_default_markup() {
+        let out = math_fragment("$\\left( x$");
+        assert!(!out.contains("<math"));
+        assert!(out.contains("math-inline"));
+    }
+
+    // --- Tests for render_diagram_blocks ---
+
+    fn diagram_fragment(md: &str, command: &[&str]) -> String {
+        use pulldown_cmark::{html, Event, Options, Parser};
+
+        let cfg = crate::config::DiagramConfig {
+            mermaid_command: command.iter().map(|s| s.to_string()).collect(),
+            cache: false,
+            ..Default::default()
+        };
+        let events: Vec<Event> = Parser::new_ext(md, Options::all()).collect();
+        let events = render_diagram_blocks(events, &cfg);
+        let mut out = String::new();
+        html::push_html(&mut out, events.into_iter());
+        out
+    }
+
+    #[test]
+    fn test_diagram_falls_back_to_code_block() {
+        let md = "```mermaid\ngraph TD; A-->B\n```\n";
+        let html = diagram_fragment(md, &["claude-workbench-no-such-renderer"]);
+        assert!(html.contains("language-mermaid"), "got: {}", html);
+        assert!(html.contains("graph TD"), "got: {}", html);
+        assert!(!html.contains("class=\"diagram\""), "got: {}", html);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_diagram_rendered_as_image() {
+        let md = "Intro\n\n```mermaid\n<svg><g/></svg>\n```\n\nOutro";
+        let html = diagram_fragment(md, &["cat"]);
+        assert!(html.contains("<div class=\"diagram\"><img src=\"data:image/svg+xml"));
+        assert!(!html.contains("<pre>"), "got: {}", html);
+        assert!(html.contains("Outro"), "got: {}", html);
+    }
 }
@@ -0,0 +1,1315 @@
+//! LaTeX math translation for Markdown exports.
+//!
+//! pulldown-cmark hands `$…$` / `$$…$$` through as raw LaTeX. This module
+//! parses the common subset used in specs (fractions, roots, scripts, Greek
+//! letters and operators, `\left…\right`, accents, font styles, `\text`,
+//! matrices/cases/aligned environments) into a small AST and emits either:
+//!
+//! - Typst math markup for the PDF exporter (Typst typesets math natively)
+//! - MathML for the HTML exporter — rendered by the browser itself, so the
+//!   preview stays offline (no MathJax/KaTeX CDN fetch)
+//!
+//! Input the parser cannot make sense of (unbalanced braces, missing
+//! `\right`/`\end`) yields `None`; callers then fall back to literal text.
+
+/// Parsed math node
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// Single (italic) identifier: letter or Greek symbol
+    Ident(String),
+    Number(String),
+    /// Operator, relation or punctuation
+    Op(String),
+    /// Big operator with limits (∑, ∏, ∫ …)
+    LargeOp(String),
+    Prime,
+    Text(String),
+    /// Upright operator name (sin, lim, \operatorname{…})
+    Func(String),
+    Group(Vec<Node>),
+    Frac(Box<Node>, Box<Node>),
+    Binom(Box<Node>, Box<Node>),
+    Sqrt(Option<Box<Node>>, Box<Node>),
+    Scripts {
+        base: Box<Node>,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+    Accent(Accent, Box<Node>),
+    Style(Style, Box<Node>),
+    Fenced {
+        open: String,
+        body: Vec<Node>,
+        close: String,
+    },
+    Space(Space),
+    /// Environment body: rows → cells → nodes
+    Env(Env, Vec<Vec<Vec<Node>>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Accent {
+    Hat,
+    Bar,
+    Vec,
+    Dot,
+    DDot,
+    Tilde,
+    Underline,
+    Overbrace,
+    Underbrace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Bold,
+    Upright,
+    Italic,
+    DoubleStruck,
+    Calligraphic,
+    Fraktur,
+    Sans,
+    Mono,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Space {
+    Thin,
+    Medium,
+    Thick,
+    Quad,
+    QQuad,
+    Negative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Env {
+    /// Matrix with the given delimiters (`None` = bare `matrix`/`array`)
+    Matrix(Option<(&'static str, &'static str)>),
+    Cases,
+    /// aligned / align / gathered / split, and top-level `\\` line breaks
+    Aligned,
+}
+
+/// Translate LaTeX math to Typst math markup, including the `$` delimiters.
+/// `display` produces a block equation (`$ … $`).
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+pub(crate) fn latex_to_typst(src: &str, display: bool) -> Option<String> {
+    let nodes = parse(src)?;
+    let body = typst_seq(&nodes);
+    Some(if display {
+        format!("$ {} $", body)
+    } else {
+        format!("${}$", body)
+    })
+}
+
+/// Translate LaTeX math to a MathML `<math>` element.
+pub(crate) fn latex_to_mathml(src: &str, display: bool) -> Option<String> {
+    let nodes = parse(src)?;
+    let mut out = String::new();
+    if display {
+        out.push_str("<math display=\"block\">");
+    } else {
+        out.push_str("<math>");
+    }
+    mathml_seq(&nodes, Style::Italic, &mut out);
+    out.push_str("</math>");
+    Some(out)
+}
+
+// --- Parser ---
+
+fn parse(src: &str) -> Option<Vec<Node>> {
+    let mut p = Parser {
+        src: src.chars().collect(),
+        pos: 0,
+    };
+    let rows = p.parse_rows()?;
+    p.skip_ws();
+    if p.pos < p.src.len() {
+        // Stray `}` or `\right`/`\end` without opener
+        return None;
+    }
+    // Top-level `\\` or `&` → implicit aligned block
+    if rows.len() > 1 || rows.first().is_some_and(|r| r.len() > 1) {
+        return Some(vec![Node::Env(Env::Aligned, rows)]);
+    }
+    Some(
+        rows.into_iter()
+            .next()
+            .and_then(|r| r.into_iter().next())
+            .unwrap_or_default(),
+    )
+}
+
+struct Parser {
+    src: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Name of the command at the cursor (without consuming it).
+    /// Letter commands are read greedily; otherwise the single next char.
+    fn peek_command(&self) -> Option<(String, usize)> {
+        if self.peek() != Some('\\') {
+            return None;
+        }
+        let mut end = self.pos + 1;
+        while self.src.get(end).is_some_and(|c| c.is_ascii_alphabetic()) {
+            end += 1;
+        }
+        if end == self.pos + 1 {
+            let c = *self.src.get(end)?;
+            return Some((c.to_string(), end + 1));
+        }
+        Some((self.src[self.pos + 1..end].iter().collect(), end))
+    }
+
+    fn at_terminator(&self) -> bool {
+        match self.peek() {
+            None | Some('}') | Some('&') => true,
+            Some('\\') => matches!(
+                self.peek_command().map(|(n, _)| n).as_deref(),
+                Some("\\" | "right" | "end")
+            ),
+            _ => false,
+        }
+    }
+
+    /// Parse `cell & cell \\ cell & cell` until a non-row terminator.
+    fn parse_rows(&mut self) -> Option<Vec<Vec<Vec<Node>>>> {
+        let mut rows = vec![vec![self.parse_seq()?]];
+        loop {
+            self.skip_ws();
+            if self.peek() == Some('&') {
+                self.pos += 1;
+                let cell = self.parse_seq()?;
+                rows.last_mut()?.push(cell);
+            } else if matches!(self.peek_command(), Some((ref n, _)) if n == "\\") {
+                self.pos += 2;
+                // Optional spacing argument: \\[2pt]
+                self.skip_bracket_arg();
+                rows.push(vec![self.parse_seq()?]);
+            } else {
+                break;
+            }
+        }
+        // A trailing `\\` leaves an empty last row behind
+        if rows.len() > 1 && rows.last().is_some_and(|r| r.len() == 1 && r[0].is_empty()) {
+            rows.pop();
+        }
+        Some(rows)
+    }
+
+    /// Parse a sequence of atoms (with scripts) up to a terminator.
+    fn parse_seq(&mut self) -> Option<Vec<Node>> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.at_terminator() {
+                return Some(nodes);
+            }
+            // Modifiers without visual effect on our output
+            if let Some((name, end)) = self.peek_command() {
+                if matches!(
+                    name.as_str(),
+                    "limits"
+                        | "nolimits"
+                        | "displaystyle"
+                        | "textstyle"
+                        | "scriptstyle"
+                        | "nonumber"
+                        | "notag"
+                ) {
+                    self.pos = end;
+                    continue;
+                }
+            }
+            let atom = match self.peek() {
+                Some('^') | Some('_') => Node::Group(Vec::new()),
+                _ => self.parse_atom()?,
+            };
+            let node = self.parse_scripts(atom)?;
+            nodes.push(node);
+        }
+    }
+
+    /// Attach any following `^…` / `_…` to `base`.
+    fn parse_scripts(&mut self, base: Node) -> Option<Node> {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_ws();
+            // \limits between operator and scripts
+            if let Some((name, end)) = self.peek_command() {
+                if name == "limits" || name == "nolimits" {
+                    self.pos = end;
+                    continue;
+                }
+            }
+            match self.peek() {
+                Some('^') if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(Box::new(self.parse_arg()?));
+                }
+                Some('_') if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(Box::new(self.parse_arg()?));
+                }
+                _ => break,
+            }
+        }
+        if sub.is_none() && sup.is_none() {
+            Some(base)
+        } else {
+            Some(Node::Scripts {
+                base: Box::new(base),
+                sub,
+                sup,
+            })
+        }
+    }
+
+    /// Mandatory argument: `{…}` group or a single token (`\frac12`).
+    fn parse_arg(&mut self) -> Option<Node> {
+        self.skip_ws();
+        match self.peek()? {
+            '{' => self.parse_atom(),
+            '\\' => self.parse_atom(),
+            c => {
+                self.pos += 1;
+                Some(char_node(c))
+            }
+        }
+    }
+
+    /// Contents of a `{…}` argument as raw text (for \text, \operatorname, \begin).
+    fn parse_raw_arg(&mut self) -> Option<String> {
+        self.skip_ws();
+        if self.peek() != Some('{') {
+            // Single char form: \text x
+            let c = self.peek()?;
+            self.pos += 1;
+            return Some(c.to_string());
+        }
+        self.pos += 1;
+        let mut depth = 0usize;
+        let mut text = String::new();
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => return Some(text),
+                '}' => depth -= 1,
+                '\\' => {
+                    // Escaped char inside text: \{ \} \% \_ …
+                    if let Some(next) = self.peek() {
+                        if !next.is_ascii_alphabetic() {
+                            self.pos += 1;
+                            text.push(next);
+                            continue;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            text.push(c);
+        }
+    }
+
+    /// Skip an optional `[…]` argument, returning its contents.
+    fn skip_bracket_arg(&mut self) -> Option<String> {
+        self.skip_ws();
+        if self.peek() != Some('[') {
+            return None;
+        }
+        let start = self.pos + 1;
+        let end = (start..self.src.len()).find(|&i| self.src[i] == ']')?;
+        self.pos = end + 1;
+        Some(self.src[start..end].iter().collect())
+    }
+
+    /// A delimiter after \left, \right, \big …
+    fn parse_delimiter(&mut self) -> Option<String> {
+        self.skip_ws();
+        if let Some((name, end)) = self.peek_command() {
+            self.pos = end;
+            return match name.as_str() {
+                "{" | "lbrace" => Some("{".into()),
+                "}" | "rbrace" => Some("}".into()),
+                "|" | "Vert" | "lVert" | "rVert" => Some("‖".into()),
+                "vert" | "lvert" | "rvert" | "mid" => Some("|".into()),
+                "langle" => Some("⟨".into()),
+                "rangle" => Some("⟩".into()),
+                "lfloor" => Some("⌊".into()),
+                "rfloor" => Some("⌋".into()),
+                "lceil" => Some("⌈".into()),
+                "rceil" => Some("⌉".into()),
+                _ => None,
+            };
+        }
+        let c = self.peek()?;
+        self.pos += 1;
+        match c {
+            '.' => Some(String::new()),
+            '(' | ')' | '[' | ']' | '|' | '/' => Some(c.to_string()),
+            _ => None,
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<Node> {
+        self.skip_ws();
+        let c = self.peek()?;
+        match c {
+            '{' => {
+                self.pos += 1;
+                let inner = self.parse_seq()?;
+                if !self.eat('}') {
+                    return None;
+                }
+                Some(Node::Group(inner))
+            }
+            '\\' => self.parse_command(),
+            '0'..='9' | '.' => {
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    let next_is_digit =
+                        self.src.get(self.pos + 1).is_some_and(char::is_ascii_digit);
+                    if c.is_ascii_digit() || (c == '.' && next_is_digit) {
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if self.pos == start {
+                    // Lone '.'
+                    self.pos += 1;
+                    return Some(Node::Op(".".into()));
+                }
+                Some(Node::Number(self.src[start..self.pos].iter().collect()))
+            }
+            _ => {
+                self.pos += 1;
+                Some(char_node(c))
+            }
+        }
+    }
+
+    fn parse_command(&mut self) -> Option<Node> {
+        let (name, end) = self.peek_command()?;
+        self.pos = end;
+        let node = match name.as_str() {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let num = self.parse_arg()?;
+                let den = self.parse_arg()?;
+                Node::Frac(Box::new(num), Box::new(den))
+            }
+            "binom" | "dbinom" | "tbinom" => {
+                let n = self.parse_arg()?;
+                let k = self.parse_arg()?;
+                Node::Binom(Box::new(n), Box::new(k))
+            }
+            "sqrt" => {
+                let index = self.skip_bracket_arg().and_then(|s| parse(&s));
+                let radicand = self.parse_arg()?;
+                Node::Sqrt(index.map(|n| Box::new(Node::Group(n))), Box::new(radicand))
+            }
+            "text" | "textrm" | "textit" | "textbf" | "textsf" | "texttt" | "mbox" | "hbox" => {
+                Node::Text(self.parse_raw_arg()?)
+            }
+            "operatorname" => Node::Func(self.parse_raw_arg()?),
+            "left" => {
+                let open = self.parse_delimiter()?;
+                let body = self.parse_seq()?;
+                match self.peek_command() {
+                    Some((n, e)) if n == "right" => self.pos = e,
+                    _ => return None,
+                }
+                let close = self.parse_delimiter()?;
+                Node::Fenced { open, body, close }
+            }
+            "big" | "Big" | "bigg" | "Bigg" | "bigl" | "bigr" | "Bigl" | "Bigr" | "biggl"
+            | "biggr" | "Biggl" | "Biggr" | "middle" => Node::Op(self.parse_delimiter()?),
+            "begin" => {
+                let env_name = self.parse_raw_arg()?;
+                let env = env_kind(&env_name);
+                // array column spec: \begin{array}{cc}
+                if env_name == "array" {
+                    self.parse_raw_arg()?;
+                }
+                let rows = self.parse_rows()?;
+                match self.peek_command() {
+                    Some((n, e)) if n == "end" => self.pos = e,
+                    _ => return None,
+                }
+                let end_name = self.parse_raw_arg()?;
+                if end_name != env_name {
+                    return None;
+                }
+                Node::Env(env, rows)
+            }
+            "," | "thinspace" => Node::Space(Space::Thin),
+            ":" | ">" | "medspace" => Node::Space(Space::Medium),
+            ";" | " " | "thickspace" => Node::Space(Space::Thick),
+            "!" => Node::Space(Space::Negative),
+            "quad" => Node::Space(Space::Quad),
+            "qquad" => Node::Space(Space::QQuad),
+            "{" | "lbrace" => Node::Op("{".into()),
+            "}" | "rbrace" => Node::Op("}".into()),
+            "|" => Node::Op("‖".into()),
+            "_" | "#" | "$" | "%" | "&" => Node::Op(name.clone()),
+            _ => {
+                if let Some(kind) = accent_kind(&name) {
+                    Node::Accent(kind, Box::new(self.parse_arg()?))
+                } else if let Some(style) = style_kind(&name) {
+                    Node::Style(style, Box::new(self.parse_arg()?))
+                } else if is_function_name(&name) {
+                    Node::Func(if name == "bmod" { "mod".into() } else { name })
+                } else if let Some((sym, class)) = symbol(&name) {
+                    match class {
+                        SymbolClass::Ident => Node::Ident(sym.into()),
+                        SymbolClass::Op => Node::Op(sym.into()),
+                        SymbolClass::Large => Node::LargeOp(sym.into()),
+                    }
+                } else {
+                    // Unknown command: keep it visible rather than dropping it
+                    Node::Text(format!("\\{}", name))
+                }
+            }
+        };
+        Some(node)
+    }
+}
+
+fn char_node(c: char) -> Node {
+    if c.is_alphabetic() {
+        Node::Ident(c.to_string())
+    } else if c.is_ascii_digit() {
+        Node::Number(c.to_string())
+    } else if c == '\'' {
+        Node::Prime
+    } else if c == '~' {
+        Node::Space(Space::Thick)
+    } else {
+        Node::Op(c.to_string())
+    }
+}
+
+fn env_kind(name: &str) -> Env {
+    match name.trim_end_matches('*') {
+        "pmatrix" => Env::Matrix(Some(("(", ")"))),
+        "bmatrix" => Env::Matrix(Some(("[", "]"))),
+        "Bmatrix" => Env::Matrix(Some(("{", "}"))),
+        "vmatrix" => Env::Matrix(Some(("|", "|"))),
+        "Vmatrix" => Env::Matrix(Some(("‖", "‖"))),
+        "matrix" | "smallmatrix" | "array" => Env::Matrix(None),
+        "cases" | "dcases" => Env::Cases,
+        _ => Env::Aligned,
+    }
+}
+
+fn accent_kind(name: &str) -> Option<Accent> {
+    Some(match name {
+        "hat" | "widehat" => Accent::Hat,
+        "bar" | "overline" => Accent::Bar,
+        "vec" | "overrightarrow" => Accent::Vec,
+        "dot" => Accent::Dot,
+        "ddot" => Accent::DDot,
+        "tilde" | "widetilde" => Accent::Tilde,
+        "underline" => Accent::Underline,
+        "overbrace" => Accent::Overbrace,
+        "underbrace" => Accent::Underbrace,
+        _ => return None,
+    })
+}
+
+fn style_kind(name: &str) -> Option<Style> {
+    Some(match name {
+        "mathbf" | "boldsymbol" | "bm" => Style::Bold,
+        "mathrm" | "rm" => Style::Upright,
+        "mathit" => Style::Italic,
+        "mathbb" => Style::DoubleStruck,
+        "mathcal" | "mathscr" => Style::Calligraphic,
+        "mathfrak" => Style::Fraktur,
+        "mathsf" => Style::Sans,
+        "mathtt" => Style::Mono,
+        _ => return None,
+    })
+}
+
+/// Operator names that Typst predefines in math mode (`sin`, `lim`, …).
+const TYPST_FUNCTIONS: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "coth", "csc", "deg", "det", "dim",
+    "exp", "gcd", "hom", "inf", "ker", "lg", "lim", "liminf", "limsup", "ln", "log", "max", "min",
+    "mod", "Pr", "sec", "sin", "sinh", "sup", "tan", "tanh",
+];
+
+fn is_function_name(name: &str) -> bool {
+    name == "bmod" || TYPST_FUNCTIONS.contains(&name)
+}
+
+/// Operator names that take their scripts as limits (below/above)
+fn takes_limits(name: &str) -> bool {
+    matches!(
+        name,
+        "lim" | "liminf" | "limsup" | "max" | "min" | "sup" | "inf" | "det" | "gcd" | "Pr"
+    )
+}
+
+enum SymbolClass {
+    Ident,
+    Op,
+    Large,
+}
+
+/// LaTeX symbol command → Unicode character and class.
+/// Both emitters use the Unicode form directly (Typst accepts it in math).
+fn symbol(name: &str) -> Option<(&'static str, SymbolClass)> {
+    use SymbolClass::*;
+    Some(match name {
+        // Greek lowercase
+        "alpha" => ("α", Ident),
+        "beta" => ("β", Ident),
+        "gamma" => ("γ", Ident),
+        "delta" => ("δ", Ident),
+        "epsilon" => ("ϵ", Ident),
+        "varepsilon" => ("ε", Ident),
+        "zeta" => ("ζ", Ident),
+        "eta" => ("η", Ident),
+        "theta" => ("θ", Ident),
+        "vartheta" => ("ϑ", Ident),
+        "iota" => ("ι", Ident),
+        "kappa" => ("κ", Ident),
+        "lambda" => ("λ", Ident),
+        "mu" => ("μ", Ident),
+        "nu" => ("ν", Ident),
+        "xi" => ("ξ", Ident),
+        "pi" => ("π", Ident),
+        "varpi" => ("ϖ", Ident),
+        "rho" => ("ρ", Ident),
+        "varrho" => ("ϱ", Ident),
+        "sigma" => ("σ", Ident),
+        "varsigma" => ("ς", Ident),
+        "tau" => ("τ", Ident),
+        "upsilon" => ("υ", Ident),
+        "phi" => ("ϕ", Ident),
+        "varphi" => ("φ", Ident),
+        "chi" => ("χ", Ident),
+        "psi" => ("ψ", Ident),
+        "omega" => ("ω", Ident),
+        // Greek uppercase
+        "Gamma" => ("Γ", Ident),
+        "Delta" => ("Δ", Ident),
+        "Theta" => ("Θ", Ident),
+        "Lambda" => ("Λ", Ident),
+        "Xi" => ("Ξ", Ident),
+        "Pi" => ("Π", Ident),
+        "Sigma" => ("Σ", Ident),
+        "Upsilon" => ("Υ", Ident),
+        "Phi" => ("Φ", Ident),
+        "Psi" => ("Ψ", Ident),
+        "Omega" => ("Ω", Ident),
+        // Letter-like
+        "infty" => ("∞", Ident),
+        "partial" => ("∂", Ident),
+        "nabla" => ("∇", Ident),
+        "hbar" => ("ℏ", Ident),
+        "ell" => ("ℓ", Ident),
+        "emptyset" | "varnothing" => ("∅", Ident),
+        "aleph" => ("ℵ", Ident),
+        "Re" => ("ℜ", Ident),
+        "Im" => ("ℑ", Ident),
+        "prime" => ("′", Op),
+        "degree" | "circ" => ("∘", Op),
+        // Binary operators
+        "cdot" => ("⋅", Op),
+        "times" => ("×", Op),
+        "div" => ("÷", Op),
+        "pm" => ("±", Op),
+        "mp" => ("∓", Op),
+        "ast" => ("∗", Op),
+        "star" => ("⋆", Op),
+        "cup" => ("∪", Op),
+        "cap" => ("∩", Op),
+        "setminus" => ("∖", Op),
+        "wedge" | "land" => ("∧", Op),
+        "vee" | "lor" => ("∨", Op),
+        "oplus" => ("⊕", Op),
+        "otimes" => ("⊗", Op),
+        "neg" | "lnot" => ("¬", Op),
+        // Relations
+        "leq" | "le" => ("≤", Op),
+        "geq" | "ge" => ("≥", Op),
+        "neq" | "ne" => ("≠", Op),
+        "approx" => ("≈", Op),
+        "equiv" => ("≡", Op),
+        "sim" => ("∼", Op),
+        "simeq" => ("≃", Op),
+        "cong" => ("≅", Op),
+        "propto" => ("∝", Op),
+        "ll" => ("≪", Op),
+        "gg" => ("≫", Op),
+        "in" => ("∈", Op),
+        "notin" => ("∉", Op),
+        "ni" => ("∋", Op),
+        "subset" => ("⊂", Op),
+        "subseteq" => ("⊆", Op),
+        "supset" => ("⊃", Op),
+        "supseteq" => ("⊇", Op),
+        "perp" => ("⊥", Op),
+        "parallel" => ("∥", Op),
+        "mid" => ("∣", Op),
+        "coloneqq" => ("≔", Op),
+        // Arrows
+        "to" | "rightarrow" => ("→", Op),
+        "leftarrow" | "gets" => ("←", Op),
+        "leftrightarrow" => ("↔", Op),
+        "Rightarrow" => ("⇒", Op),
+        "Leftarrow" => ("⇐", Op),
+        "Leftrightarrow" | "iff" => ("⇔", Op),
+        "implies" => ("⟹", Op),
+        "mapsto" => ("↦", Op),
+        "uparrow" => ("↑", Op),
+        "downarrow" => ("↓", Op),
+        // Logic / sets
+        "forall" => ("∀", Op),
+        "exists" => ("∃", Op),
+        "nexists" => ("∄", Op),
+        // Dots
+        "ldots" | "dots" | "dotsc" => ("…", Op),
+        "cdots" | "dotsb" => ("⋯", Op),
+        "vdots" => ("⋮", Op),
+        "ddots" => ("⋱", Op),
+        // Delimiters outside \left/\right
+        "langle" => ("⟨", Op),
+        "rangle" => ("⟩", Op),
+        "lfloor" => ("⌊", Op),
+        "rfloor" => ("⌋", Op),
+        "lceil" => ("⌈", Op),
+        "rceil" => ("⌉", Op),
+        "vert" | "lvert" | "rvert" => ("|", Op),
+        "Vert" | "lVert" | "rVert" => ("‖", Op),
+        // Big operators
+        "sum" => ("∑", Large),
+        "prod" => ("∏", Large),
+        "coprod" => ("∐", Large),
+        "int" => ("∫", Large),
+        "iint" => ("∬", Large),
+        "iiint" => ("∭", Large),
+        "oint" => ("∮", Large),
+        "bigcup" => ("⋃", Large),
+        "bigcap" => ("⋂", Large),
+        "bigoplus" => ("⨁", Large),
+        "bigotimes" => ("⨂", Large),
+        _ => return None,
+    })
+}
+
+// --- Typst emitter ---
+
+/// Emit a node sequence; atoms are space-separated so adjacent letters stay
+/// separate variables (`a b`, not the identifier `ab`) and ASCII operator
+/// pairs never fuse into Typst shorthands (`- >` vs `->`).
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn typst_seq(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        let s = typst_node(node);
+        if s.is_empty() {
+            continue;
+        }
+        if !out.is_empty() && *node != Node::Prime {
+            out.push(' ');
+        }
+        out.push_str(&s);
+    }
+    out
+}
+
+/// Emit an argument for a Typst math function (`frac(…, …)`), never empty.
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn typst_arg(node: &Node) -> String {
+    let s = match node {
+        Node::Group(nodes) => typst_seq(nodes),
+        other => typst_node(other),
+    };
+    if s.is_empty() {
+        "\"\"".to_string()
+    } else {
+        s
+    }
+}
+
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn typst_node(node: &Node) -> String {
+    match node {
+        Node::Ident(s) | Node::Number(s) | Node::LargeOp(s) => s.clone(),
+        Node::Op(s) => typst_escape_op(s),
+        Node::Prime => "'".to_string(),
+        Node::Text(s) => typst_string(s),
+        Node::Func(name) => {
+            if TYPST_FUNCTIONS.contains(&name.as_str()) {
+                name.clone()
+            } else {
+                format!("op({})", typst_string(name))
+            }
+        }
+        Node::Group(nodes) => typst_seq(nodes),
+        Node::Frac(n, d) => format!("frac({}, {})", typst_arg(n), typst_arg(d)),
+        Node::Binom(n, k) => format!("binom({}, {})", typst_arg(n), typst_arg(k)),
+        Node::Sqrt(None, x) => format!("sqrt({})", typst_arg(x)),
+        Node::Sqrt(Some(n), x) => format!("root({}, {})", typst_arg(n), typst_arg(x)),
+        Node::Scripts { base, sub, sup } => {
+            let is_atomic = match base.as_ref() {
+                Node::Group(nodes) => nodes.len() == 1 && is_typst_atom(&nodes[0]),
+                other => is_typst_atom(other),
+            };
+            if is_atomic {
+                let mut s = typst_arg(base);
+                if let Some(sub) = sub {
+                    s.push_str(&format!("_({})", typst_arg(sub)));
+                }
+                if let Some(sup) = sup {
+                    s.push_str(&format!("^({})", typst_arg(sup)));
+                }
+                s
+            } else {
+                // Multi-atom base ({a+b}^2) → explicit attach()
+                let mut s = format!("attach({}", typst_arg(base));
+                if let Some(sub) = sub {
+                    s.push_str(&format!(", br: {}", typst_arg(sub)));
+                }
+                if let Some(sup) = sup {
+                    s.push_str(&format!(", tr: {}", typst_arg(sup)));
+                }
+                s.push(')');
+                s
+            }
+        }
+        Node::Accent(kind, x) => {
+            let f = match kind {
+                Accent::Hat => "hat",
+                Accent::Bar => "overline",
+                Accent::Vec => "arrow",
+                Accent::Dot => "dot",
+                Accent::DDot => "dot.double",
+                Accent::Tilde => "tilde",
+                Accent::Underline => "underline",
+                Accent::Overbrace => "overbrace",
+                Accent::Underbrace => "underbrace",
+            };
+            format!("{}({})", f, typst_arg(x))
+        }
+        Node::Style(style, x) => {
+            let f = match style {
+                Style::Bold => "bold",
+                Style::Upright => "upright",
+                Style::Italic => "italic",
+                Style::DoubleStruck => "bb",
+                Style::Calligraphic => "cal",
+                Style::Fraktur => "frak",
+                Style::Sans => "sans",
+                Style::Mono => "mono",
+            };
+            format!("{}({})", f, typst_arg(x))
+        }
+        Node::Fenced { open, body, close } => {
+            // lr() needs balanced parens in its argument list; escape a
+            // delimiter whose partner is missing (`\left. … \right)`).
+            let balanced = !open.is_empty() && !close.is_empty();
+            let delim = |d: &str| {
+                if balanced && matches!(d, "(" | ")" | "[" | "]") {
+                    d.to_string()
+                } else {
+                    typst_escape_op(d)
+                }
+            };
+            let inner = typst_seq(body);
+            let parts: Vec<String> = [delim(open), inner, delim(close)]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect();
+            format!("lr({})", parts.join(" "))
+        }
+        Node::Space(space) => match space {
+            Space::Thin => "thin",
+            Space::Medium => "med",
+            Space::Thick => "thick",
+            Space::Quad => "quad",
+            Space::QQuad => "wide",
+            Space::Negative => "",
+        }
+        .to_string(),
+        Node::Env(env, rows) => {
+            let cells = |row: &Vec<Vec<Node>>, sep: &str| -> String {
+                row.iter()
+                    .map(|cell| {
+                        let s = typst_seq(cell);
+                        if s.is_empty() {
+                            "\"\"".to_string()
+                        } else {
+                            s
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(sep)
+            };
+            match env {
+                Env::Matrix(delims) => {
+                    let delim = match delims {
+                        Some(("(", _)) => "\"(\"",
+                        Some(("[", _)) => "\"[\"",
+                        Some(("{", _)) => "\"{\"",
+                        Some(("|", _)) => "\"|\"",
+                        Some(("‖", _)) => "\"||\"",
+                        _ => "#none",
+                    };
+                    let body = rows
+                        .iter()
+                        .map(|row| cells(row, ", "))
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    format!("mat(delim: {}, {})", delim, body)
+                }
+                Env::Cases => {
+                    let body = rows
+                        .iter()
+                        .map(|row| cells(row, " & "))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("cases({})", body)
+                }
+                Env::Aligned => rows
+                    .iter()
+                    .map(|row| cells(row, " & "))
+                    .collect::<Vec<_>>()
+                    .join(" \\ "),
+            }
+        }
+    }
+}
+
+/// Whether a node renders as one unit that Typst can attach scripts to.
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn is_typst_atom(node: &Node) -> bool {
+    !matches!(node, Node::Group(_) | Node::Space(_) | Node::Env(..))
+}
+
+/// Escape characters with syntactic meaning in Typst math. A backslash
+/// escapes any following character, so this is always safe.
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn typst_escape_op(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(
+            c,
+            '\\' | '#'
+                | '$'
+                | '"'
+                | '_'
+                | '^'
+                | '&'
+                | '/'
+                | ','
+                | ';'
+                | '<'
+                | '>'
+                | '{'
+                | '}'
+                | '@'
+                | '~'
+                | '%'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Typst string literal (upright text in math)
+#[cfg_attr(not(feature = "pdf-export"), allow(dead_code))]
+fn typst_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// --- MathML emitter ---
+
+fn mathml_seq(nodes: &[Node], style: Style, out: &mut String) {
+    for node in nodes {
+        mathml_node(node, style, out);
+    }
+}
+
+/// Emit a node as exactly one MathML element (wrapping sequences in `<mrow>`).
+fn mathml_arg(node: &Node, style: Style, out: &mut String) {
+    match node {
+        Node::Group(nodes) if nodes.len() == 1 => mathml_node(&nodes[0], style, out),
+        Node::Group(nodes) => {
+            out.push_str("<mrow>");
+            mathml_seq(nodes, style, out);
+            out.push_str("</mrow>");
+        }
+        other => mathml_node(other, style, out),
+    }
+}
+
+fn mathml_node(node: &Node, style: Style, out: &mut String) {
+    match node {
+        Node::Ident(s) => {
+            let styled = styled_text(s, style);
+            if style == Style::Upright || (style != Style::Italic && styled == *s) {
+                out.push_str(&format!(
+                    "<mi mathvariant=\"normal\">{}</mi>",
+                    xml_escape(&styled)
+                ));
+            } else {
+                out.push_str(&format!("<mi>{}</mi>", xml_escape(&styled)));
+            }
+        }
+        Node::Number(s) => {
+            out.push_str(&format!("<mn>{}</mn>", xml_escape(&styled_text(s, style))));
+        }
+        Node::Op(s) => {
+            let op = if s == "-" { "\u{2212}" } else { s.as_str() };
+            out.push_str(&format!("<mo>{}</mo>", xml_escape(op)));
+        }
+        Node::LargeOp(s) => {
+            out.push_str(&format!("<mo largeop=\"true\">{}</mo>", xml_escape(s)));
+        }
+        Node::Prime => out.push_str("<mo>\u{2032}</mo>"),
+        Node::Text(s) => out.push_str(&format!("<mtext>{}</mtext>", xml_escape(s))),
+        Node::Func(name) => out.push_str(&format!("<mi>{}</mi>", xml_escape(name))),
+        Node::Group(nodes) => {
+            out.push_str("<mrow>");
+            mathml_seq(nodes, style, out);
+            out.push_str("</mrow>");
+        }
+        Node::Frac(n, d) => {
+            out.push_str("<mfrac>");
+            mathml_arg(n, style, out);
+            mathml_arg(d, style, out);
+            out.push_str("</mfrac>");
+        }
+        Node::Binom(n, k) => {
+            out.push_str("<mrow><mo>(</mo><mfrac linethickness=\"0\">");
+            mathml_arg(n, style, out);
+            mathml_arg(k, style, out);
+            out.push_str("</mfrac><mo>)</mo></mrow>");
+        }
+        Node::Sqrt(None, x) => {
+            out.push_str("<msqrt>");
+            mathml_arg(x, style, out);
+            out.push_str("</msqrt>");
+        }
+        Node::Sqrt(Some(n), x) => {
+            out.push_str("<mroot>");
+            mathml_arg(x, style, out);
+            mathml_arg(n, style, out);
+            out.push_str("</mroot>");
+        }
+        Node::Scripts { base, sub, sup } => {
+            let limits = match base.as_ref() {
+                Node::LargeOp(_) => true,
+                Node::Func(name) => takes_limits(name),
+                _ => false,
+            };
+            let tag = match (limits, sub.is_some(), sup.is_some()) {
+                (true, true, true) => "munderover",
+                (true, true, false) => "munder",
+                (true, false, true) => "mover",
+                (false, true, true) => "msubsup",
+                (false, true, false) => "msub",
+                _ => "msup",
+            };
+            out.push_str(&format!("<{}>", tag));
+            match base.as_ref() {
+                Node::Group(nodes) if nodes.is_empty() => out.push_str("<mrow></mrow>"),
+                other => mathml_arg(other, style, out),
+            }
+            if let Some(sub) = sub {
+                mathml_arg(sub, style, out);
+            }
+            if let Some(sup) = sup {
+                mathml_arg(sup, style, out);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        Node::Accent(kind, x) => {
+            let (tag, mark) = match kind {
+                Accent::Hat => ("mover", "^"),
+                Accent::Bar => ("mover", "\u{00AF}"),
+                Accent::Vec => ("mover", "\u{2192}"),
+                Accent::Dot => ("mover", "\u{02D9}"),
+                Accent::DDot => ("mover", "\u{00A8}"),
+                Accent::Tilde => ("mover", "~"),
+                Accent::Overbrace => ("mover", "\u{23DE}"),
+                Accent::Underline => ("munder", "_"),
+                Accent::Underbrace => ("munder", "\u{23DF}"),
+            };
+            let attr = if tag == "mover" {
+                "accent"
+            } else {
+                "accentunder"
+            };
+            out.push_str(&format!("<{} {}=\"true\">", tag, attr));
+            mathml_arg(x, style, out);
+            out.push_str(&format!("<mo>{}</mo></{}>", mark, tag));
+        }
+        Node::Style(inner, x) => mathml_arg(x, *inner, out),
+        Node::Fenced { open, body, close } => {
+            out.push_str("<mrow>");
+            if !open.is_empty() {
+                out.push_str(&format!("<mo fence=\"true\">{}</mo>", xml_escape(open)));
+            }
+            mathml_seq(body, style, out);
+            if !close.is_empty() {
+                out.push_str(&format!("<mo fence=\"true\">{}</mo>", xml_escape(close)));
+            }
+            out.push_str("</mrow>");
+        }
+        Node::Space(space) => {
+            let width = match space {
+                Space::Thin => "0.1667em",
+                Space::Medium => "0.2222em",
+                Space::Thick => "0.2778em",
+                Space::Quad => "1em",
+                Space::QQuad => "2em",
+                Space::Negative => "-0.1667em",
+            };
+            out.push_str(&format!("<mspace width=\"{}\"/>", width));
+        }
+        Node::Env(env, rows) => {
+            let (open, close, align) = match env {
+                Env::Matrix(Some((o, c))) => (*o, *c, "center"),
+                Env::Matrix(None) => ("", "", "center"),
+                Env::Cases => ("{", "", "left"),
+                Env::Aligned => ("", "", "right left"),
+            };
+            out.push_str("<mrow>");
+            if !open.is_empty() {
+                out.push_str(&format!("<mo fence=\"true\">{}</mo>", xml_escape(open)));
+            }
+            out.push_str(&format!("<mtable columnalign=\"{}\">", align));
+            for row in rows {
+                out.push_str("<mtr>");
+                for cell in row {
+                    out.push_str("<mtd>");
+                    mathml_seq(cell, style, out);
+                    out.push_str("</mtd>");
+                }
+                out.push_str("</mtr>");
+            }
+            out.push_str("</mtable>");
+            if !close.is_empty() {
+                out.push_str(&format!("<mo fence=\"true\">{}</mo>", xml_escape(close)));
+            }
+            out.push_str("</mrow>");
+        }
+    }
+}
+
+/// Map ASCII letters/digits to the Unicode mathematical alphanumeric block
+/// for `style`. Browsers only honour `mathvariant="normal"` reliably, so the
+/// other styles are expressed through the characters themselves.
+fn styled_text(s: &str, style: Style) -> String {
+    s.chars().map(|c| styled_char(c, style)).collect()
+}
+
+fn styled_char(c: char, style: Style) -> char {
+    // (upper base, lower base, digit base) + holes filled by letterlike symbols
+    let (upper, lower, digit): (u32, Option<u32>, Option<u32>) = match style {
+        Style::Bold => (0x1D400, Some(0x1D41A), Some(0x1D7CE)),
+        Style::DoubleStruck => (0x1D538, Some(0x1D552), Some(0x1D7D8)),
+        Style::Calligraphic => (0x1D49C, None, None),
+        Style::Fraktur => (0x1D504, Some(0x1D51E), None),
+        Style::Sans => (0x1D5A0, Some(0x1D5BA), Some(0x1D7E2)),
+        Style::Mono => (0x1D670, Some(0x1D68A), Some(0x1D7F6)),
+        Style::Upright | Style::Italic => return c,
+    };
+    let hole = match (style, c) {
+        (Style::DoubleStruck, 'C') => Some('ℂ'),
+        (Style::DoubleStruck, 'H') => Some('ℍ'),
+        (Style::DoubleStruck, 'N') => Some('ℕ'),
+        (Style::DoubleStruck, 'P') => Some('ℙ'),
+        (Style::DoubleStruck, 'Q') => Some('ℚ'),
+        (Style::DoubleStruck, 'R') => Some('ℝ'),
+        (Style::DoubleStruck, 'Z') => Some('ℤ'),
+        (Style::Calligraphic, 'B') => Some('ℬ'),
+        (Style::Calligraphic, 'E') => Some('ℰ'),
+        (Style::Calligraphic, 'F') => Some('ℱ'),
+        (Style::Calligraphic, 'H') => Some('ℋ'),
+        (Style::Calligraphic, 'I') => Some('ℐ'),
+        (Style::Calligraphic, 'L') => Some('ℒ'),
+        (Style::Calligraphic, 'M') => Some('ℳ'),
+        (Style::Calligraphic, 'R') => Some('ℛ'),
+        (Style::Fraktur, 'C') => Some('ℭ'),
+        (Style::Fraktur, 'H') => Some('ℌ'),
+        (Style::Fraktur, 'I') => Some('ℑ'),
+        (Style::Fraktur, 'R') => Some('ℜ'),
+        (Style::Fraktur, 'Z') => Some('ℨ'),
+        _ => None,
+    };
+    if let Some(h) = hole {
+        return h;
+    }
+    let code = match c {
+        'A'..='Z' => Some(upper + (c as u32 - 'A' as u32)),
+        'a'..='z' => lower.map(|b| b + (c as u32 - 'a' as u32)),
+        '0'..='9' => digit.map(|b| b + (c as u32 - '0' as u32)),
+        _ => None,
+    };
+    code.and_then(char::from_u32).unwrap_or(c)
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typst(src: &str) -> String {
+        latex_to_typst(src, false).expect("parse")
+    }
+
+    fn mathml(src: &str) -> String {
+        latex_to_mathml(src, false).expect("parse")
+    }
+
+    #[test]
+    fn test_typst_letters_stay_separate_variables() {
+        assert_eq!(typst("ab + c"), "$a b + c$");
+    }
+
+    #[test]
+    fn test_typst_fraction_and_root() {
+        assert_eq!(typst(r"\frac{a+1}{2}"), "$frac(a + 1, 2)$");
+        assert_eq!(typst(r"\frac12"), "$frac(1, 2)$");
+        assert_eq!(typst(r"\sqrt{x}"), "$sqrt(x)$");
+        assert_eq!(typst(r"\sqrt[3]{x}"), "$root(3, x)$");
+    }
+
+    #[test]
+    fn test_typst_scripts_and_big_operators() {
+        assert_eq!(typst("x^2"), "$x^(2)$");
+        assert_eq!(typst("x_{i,j}^{n+1}"), "$x_(i \\, j)^(n + 1)$");
+        assert_eq!(typst(r"\sum_{i=1}^n i"), "$∑_(i = 1)^(n) i$");
+        assert_eq!(typst(r"\lim_{x \to 0}"), "$lim_(x → 0)$");
+        assert_eq!(typst("{a+b}^2"), "$attach(a + b, tr: 2)$");
+    }
+
+    #[test]
+    fn test_typst_greek_text_and_functions() {
+        assert_eq!(typst(r"\alpha \leq \beta"), "$α ≤ β$");
+        assert_eq!(typst(r"\text{if } x"), "$\"if \" x$");
+        assert_eq!(typst(r"\sin x"), "$sin x$");
+        assert_eq!(typst(r"\operatorname{rank} A"), "$op(\"rank\") A$");
+    }
+
+    #[test]
+    fn test_typst_escapes_syntax_chars() {
+        // `/` would be a fraction and `,` an argument separator in Typst
+        assert_eq!(typst("a/b"), "$a \\/ b$");
+        assert_eq!(typst(r"\frac{a,b}{c}"), "$frac(a \\, b, c)$");
+        assert_eq!(typst(r"\{x\}"), "$\\{ x \\}$");
+    }
+
+    #[test]
+    fn test_typst_fenced_and_prime() {
+        assert_eq!(typst(r"\left( x \right)"), "$lr(( x ))$");
+        assert_eq!(typst(r"\left. x \right|"), "$lr(x |)$");
+        assert_eq!(typst("f'(x)"), "$f' ( x )$");
+    }
+
+    #[test]
+    fn test_typst_environments() {
+        assert_eq!(
+            typst(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"),
+            "$mat(delim: \"(\", a, b; c, d)$"
+        );
+        assert_eq!(
+            typst(r"\begin{cases} 1 & x > 0 \\ 0 & \text{sonst} \end{cases}"),
+            "$cases(1 & x \\> 0, 0 & \"sonst\")$"
+        );
+        assert_eq!(typst(r"a &= b \\ c &= d"), "$a & = b \\ c & = d$");
+    }
+
+    #[test]
+    fn test_display_math_uses_block_spacing() {
+        assert_eq!(latex_to_typst("x", true).unwrap(), "$ x $");
+        assert!(latex_to_mathml("x", true)
+            .unwrap()
+            .starts_with("<math display=\"block\">"));
+    }
+
+    #[test]
+    fn test_unbalanced_input_returns_none() {
+        assert!(latex_to_typst(r"\frac{a", false).is_none());
+        assert!(latex_to_typst("a}", false).is_none());
+        assert!(latex_to_typst(r"\left( x", false).is_none());
+        assert!(latex_to_mathml(r"\begin{matrix} a \end{pmatrix}", false).is_none());
+    }
+
+    #[test]
+    fn test_unknown_command_stays_visible() {
+        assert_eq!(typst(r"\foo x"), "$\"\\\\foo\" x$");
+    }
+
+    #[test]
+    fn test_mathml_structure() {
+        assert_eq!(
+            mathml(r"\frac{1}{x}"),
+            "<math><mfrac><mn>1</mn><mi>x</mi></mfrac></math>"
+        );
+        assert_eq!(
+            mathml("x^2 - 1"),
+            "<math><msup><mi>x</mi><mn>2</mn></msup><mo>\u{2212}</mo><mn>1</mn></math>"
+        );
+        assert!(mathml(r"\sum_{i=1}^{n}").contains("<munderover><mo largeop=\"true\">∑</mo>"));
+        assert!(mathml(r"\sqrt[3]{x}").contains("<mroot><mi>x</mi><mn>3</mn></mroot>"));
+    }
+
+    #[test]
+    fn test_mathml_escapes_and_styles() {
+        assert!(mathml("a < b").contains("<mo>&lt;</mo>"));
+        assert!(mathml(r"\mathbb{R}").contains("<mi>ℝ</mi>"));
+        assert!(mathml(r"\mathbf{v}").contains("<mi>\u{1D42F}</mi>"));
+        assert!(mathml(r"\mathrm{d}x").contains("<mi mathvariant=\"normal\">d</mi>"));
+        assert!(mathml(r"\text{a & b}").contains("<mtext>a &amp; b</mtext>"));
+    }
+
+    #[test]
+    fn test_mathml_matrix() {
+        let m = mathml(r"\begin{bmatrix} 1 & 0 \\ 0 & 1 \end{bmatrix}");
+        assert!(m.contains("<mo fence=\"true\">[</mo><mtable"));
+        assert_eq!(m.matches("<mtr>").count(), 2);
+        assert_eq!(m.matches("<mtd>").count(), 4);
+    }
+}
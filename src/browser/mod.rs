@@ -3,9 +3,16 @@
 //! Provides functionality to open files in the system browser
 //! and convert Markdown to HTML for preview.
 
+pub mod book;
+pub mod diagram;
+pub mod docx;
+pub mod fonts;
+pub mod front_matter;
 pub mod markdown;
+pub mod math;
 pub mod opener;
 pub mod pdf_export;
+pub mod preview_server;
 pub mod syntax;
 pub mod template;
 #[cfg(feature = "pdf-export")]
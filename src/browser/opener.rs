@@ -82,7 +82,7 @@ pub fn can_preview_in_browser(path: &Path) -> bool {
 /// Validate that a program name contains only safe characters.
 /// Accepts: ASCII alphanumerics, `_`, `-`, `.`, `/`, `+`.
 /// Rejects: empty strings, spaces, shell metacharacters (`;`, `|`, `&`, `$`, `` ` ``, `(`, `)`, etc.).
-fn validate_program(prog: &str) -> Result<()> {
+pub(crate) fn validate_program(prog: &str) -> Result<()> {
     if prog.is_empty()
         || !prog
             .chars()
@@ -113,6 +113,12 @@ pub fn open_file_with_browser(path: &Path, browser: &str) -> Result<()> {
     }
 }
 
+/// Opens a URL (e.g. the live preview server) with a specific browser or the system default
+pub fn open_url_with_browser(url: &str, browser: &str) -> Result<()> {
+    // Launchers take URLs and paths alike as their argument
+    open_file_with_browser(Path::new(url), browser)
+}
+
 /// Opens a file with an external GUI editor
 pub fn open_file_with_editor(path: &Path, editor: &str) -> Result<()> {
     if editor.is_empty() {
@@ -1,11 +1,14 @@
-//! Markdown to PDF/Markdown export with metadata (date, author, page numbers).
+//! Markdown export (Markdown, PDF, HTML, DOCX) with metadata (date, author, page numbers).
 //!
-//! PDF generation uses native Typst rendering (pure Rust, no external binaries).
+//! PDF generation uses native Typst rendering, DOCX is written directly as
+//! Office Open XML — both pure Rust, no external binaries.
 
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 use tempfile::Builder;
 
+pub use crate::browser::fonts::FontReport;
+pub use crate::browser::front_matter::DocumentMeta;
 use crate::config::DocumentConfig;
 
 /// Export format selection
@@ -13,6 +16,40 @@ use crate::config::DocumentConfig;
 pub enum ExportFormat {
     Markdown,
     Pdf,
+    /// Single self-contained HTML file (images inlined as data URIs)
+    Html,
+    /// Word document (Office Open XML)
+    Docx,
+}
+
+impl ExportFormat {
+    /// All formats in export chooser order
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Markdown,
+        ExportFormat::Pdf,
+        ExportFormat::Html,
+        ExportFormat::Docx,
+    ];
+
+    /// Display name used in dialogs and flash messages
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Pdf => "PDF",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Docx => "DOCX",
+        }
+    }
+
+    /// File extension (without dot)
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Html => "html",
+            ExportFormat::Docx => "docx",
+        }
+    }
 }
 
 /// Options for the export operation
@@ -22,6 +59,32 @@ pub struct ExportOptions {
     pub author: String,
     pub date: String,
     pub format: ExportFormat,
+    /// Custom Typst template (file stem in the templates dir); `None` = built-in
+    pub template: Option<String>,
+    /// Front matter metadata (subtitle, tags, confidentiality)
+    pub meta: DocumentMeta,
+    /// Branding profile from `document.profiles`; `None` = path rules / base settings
+    pub profile: Option<String>,
+}
+
+/// Result of a successful export.
+#[derive(Debug, Clone)]
+pub struct ExportOutput {
+    pub path: PathBuf,
+    /// PDF only: fonts used and glyphs that fell back to other fonts
+    pub fonts: Option<FontReport>,
+    /// Settings that were ignored, e.g. invalid front matter values
+    pub warnings: Vec<String>,
+}
+
+impl From<PathBuf> for ExportOutput {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            path,
+            fonts: None,
+            warnings: Vec::new(),
+        }
+    }
 }
 
 /// Export a Markdown file to the target path in the specified format.
@@ -30,9 +93,40 @@ pub fn export_markdown(
     target: &Path,
     options: &ExportOptions,
     doc: &DocumentConfig,
-) -> Result<PathBuf> {
-    match options.format {
-        ExportFormat::Markdown => export_as_markdown(source, target),
+) -> Result<ExportOutput> {
+    if options.format == ExportFormat::Markdown {
+        return export_as_markdown(source, target).map(ExportOutput::from);
+    }
+
+    // Branding profile (front matter > chooser/CLI > path rules), then front
+    // matter overrides title/author/date and per-file document settings
+    let mut options = options.clone();
+    let fm = crate::browser::front_matter::read_front_matter(source);
+    let profile = fm
+        .as_ref()
+        .and_then(|fm| fm.profile.clone())
+        .or_else(|| options.profile.clone());
+    let mut doc = doc.resolve_profile(profile.as_deref(), source)?;
+    let warnings = match fm {
+        Some(fm) => fm.apply(
+            &mut options,
+            &mut doc,
+            source.parent().unwrap_or(Path::new(".")),
+        ),
+        None => Vec::new(),
+    };
+    let (options, doc) = (&options, &doc);
+
+    let mut output = match options.format {
+        ExportFormat::Markdown => export_as_markdown(source, target).map(ExportOutput::from),
+        ExportFormat::Html => {
+            crate::browser::markdown::export_markdown_to_html(source, target, options, doc)
+                .map(ExportOutput::from)
+        }
+        ExportFormat::Docx => {
+            crate::browser::docx::export_markdown_to_docx(source, target, options, doc)
+                .map(ExportOutput::from)
+        }
         ExportFormat::Pdf => {
             #[cfg(feature = "pdf-export")]
             {
@@ -46,7 +140,48 @@ pub fn export_markdown(
                 ))
             }
         }
+    }?;
+    output.warnings.extend(warnings);
+    Ok(output)
+}
+
+/// Top-level Markdown files in `dir` (case-insensitive extension), sorted.
+pub fn markdown_files_in(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && crate::browser::is_markdown(p))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Names (file stems) of the custom `.typ` templates in the config dir, sorted.
+pub fn available_templates() -> Vec<String> {
+    let Some(dir) = crate::config::export_templates_dir() else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|x| x == "typ"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Path of a custom template by name. Rejects names that could escape the
+/// templates directory.
+pub fn template_path(name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return None;
     }
+    crate::config::export_templates_dir().map(|dir| dir.join(format!("{}.typ", name)))
 }
 
 /// Simple Markdown copy export
@@ -137,10 +272,7 @@ pub fn default_export_filename(source: &Path, format: ExportFormat, project_name
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("export");
-    let ext = match format {
-        ExportFormat::Markdown => "md",
-        ExportFormat::Pdf => "pdf",
-    };
+    let ext = format.extension();
     let date = date_now_dmy();
     if project_name.is_empty() {
         format!("{}-{}.{}", stem, date, ext)
@@ -222,4 +354,73 @@ mod tests {
         assert!(name.starts_with("notes-"));
         assert!(name.ends_with(".md"));
     }
+
+    #[test]
+    fn test_default_export_filename_html_docx() {
+        let src = Path::new("/path/to/spec.md");
+        assert!(default_export_filename(src, ExportFormat::Html, "").ends_with(".html"));
+        assert!(default_export_filename(src, ExportFormat::Docx, "").ends_with(".docx"));
+    }
+
+    #[test]
+    fn test_export_html_is_self_contained() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("logo.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        let src = dir.path().join("doc.md");
+        std::fs::write(&src, "# Titel\n\n![Logo](logo.png)").unwrap();
+        let target = dir.path().join("doc.html");
+        let options = ExportOptions {
+            title: "Doc".to_string(),
+            author: String::new(),
+            date: "01.01.2026".to_string(),
+            format: ExportFormat::Html,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        };
+        export_markdown(&src, &target, &options, &DocumentConfig::default()).unwrap();
+
+        let html = std::fs::read_to_string(&target).unwrap();
+        assert!(html.contains("src=\"data:image/png;base64,iVBORw==\""));
+        assert!(html.contains("Generated by Musterfirma"));
+        assert!(!html.contains("file://"));
+    }
+
+    #[test]
+    fn test_export_applies_front_matter() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("doc.md");
+        std::fs::write(
+            &src,
+            "---\ntitle: Betriebshandbuch\nauthor: Jane Doe\ntags: [ops]\n---\n# Inhalt\n",
+        )
+        .unwrap();
+        let target = dir.path().join("doc.html");
+        let options = ExportOptions {
+            title: "doc".to_string(),
+            author: "Musterfirma".to_string(),
+            date: "01.01.2026".to_string(),
+            format: ExportFormat::Html,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        };
+        export_markdown(&src, &target, &options, &DocumentConfig::default()).unwrap();
+
+        let html = std::fs::read_to_string(&target).unwrap();
+        assert!(html.contains("<title>Betriebshandbuch</title>"));
+        assert!(html.contains("<meta name=\"author\" content=\"Jane Doe\">"));
+        assert!(html.contains("<meta name=\"keywords\" content=\"ops\">"));
+        assert!(!html.contains("tags:"));
+    }
+
+    #[test]
+    fn test_template_path_rejects_traversal() {
+        assert!(template_path("../secret").is_none());
+        assert!(template_path("a/b").is_none());
+        assert!(template_path("").is_none());
+        if let Some(path) = template_path("firma") {
+            assert!(path.ends_with("templates/firma.typ"));
+        }
+    }
 }
@@ -0,0 +1,474 @@
+//! Live browser preview: a localhost HTTP server that renders Markdown and
+//! syntax-highlighted files on every request and reloads the page via
+//! server-sent events when the source file changes.
+//!
+//! Only files below the server root are served, never hidden ones (`.git/`,
+//! `.env`), and only to requests addressed to the loopback host and port, so
+//! a web page cannot reach the server through DNS rebinding. The server
+//! thread stops when the `PreviewServer` is dropped (i.e. with the workbench).
+
+use anyhow::Result;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::DocumentConfig;
+
+/// How often the event stream checks the source file for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// Comment line sent on idle event streams so dead connections are noticed
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// Upper bound for request headers
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Injected into every rendered page: reload when the server reports a change.
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+    var source = new EventSource("/events?path=" + encodeURIComponent(decodeURIComponent(location.pathname)));
+    source.onmessage = function () { location.reload(); };
+})();
+</script>"#;
+
+/// Running preview server. Dropping it shuts the server down.
+pub struct PreviewServer {
+    root: PathBuf,
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PreviewServer {
+    /// Bind to a free port on 127.0.0.1 and serve files below `root`.
+    pub fn start(root: &Path, doc: &DocumentConfig) -> Result<Self> {
+        let root = root.canonicalize()?;
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let ctx = Arc::new(ServerContext {
+            root: root.clone(),
+            port: addr.port(),
+            doc: doc.clone(),
+            shutdown: shutdown.clone(),
+        });
+        let handle = std::thread::spawn(move || accept_loop(listener, ctx));
+
+        Ok(Self {
+            root,
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Preview URL for `path`, or `None` if it lies outside the server root
+    /// or is hidden (the server refuses those).
+    pub fn url_for(&self, path: &Path) -> Option<String> {
+        let canonical = path.canonicalize().ok()?;
+        let rel = canonical.strip_prefix(&self.root).ok()?;
+        if has_hidden_component(rel) {
+            return None;
+        }
+        let encoded: Vec<String> = rel
+            .components()
+            .map(|c| percent_encode(&c.as_os_str().to_string_lossy()))
+            .collect();
+        Some(format!("http://{}/{}", self.addr, encoded.join("/")))
+    }
+}
+
+impl Drop for PreviewServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+struct ServerContext {
+    root: PathBuf,
+    /// Port the server listens on, expected in the `Host` header
+    port: u16,
+    doc: DocumentConfig,
+    shutdown: Arc<AtomicBool>,
+}
+
+fn accept_loop(listener: TcpListener, ctx: Arc<ServerContext>) {
+    while !ctx.shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let ctx = ctx.clone();
+                std::thread::spawn(move || {
+                    let _ = handle_connection(stream, &ctx);
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(_) => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, ctx: &ServerContext) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers: only Host is used
+    let mut host = None;
+    let mut total = request_line.len();
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line)?;
+        total += n;
+        if n == 0 || line == "\r\n" || line == "\n" || total > MAX_REQUEST_BYTES {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let mut stream = stream;
+    if !host.is_some_and(|h| is_allowed_host(&h, ctx.port)) {
+        return respond(&mut stream, 403, "text/plain", b"Forbidden");
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return respond(&mut stream, 400, "text/plain", b"Bad Request");
+    };
+    if method != "GET" {
+        return respond(&mut stream, 405, "text/plain", b"Method Not Allowed");
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path == "/events" {
+        let page = query
+            .split('&')
+            .find_map(|kv| kv.strip_prefix("path="))
+            .map(percent_decode)
+            .unwrap_or_default();
+        return match resolve(&ctx.root, &page) {
+            Some(file) => stream_events(stream, &file, ctx),
+            None => respond(&mut stream, 404, "text/plain", b"Not Found"),
+        };
+    }
+
+    let Some(file) = resolve(&ctx.root, &percent_decode(path)) else {
+        return respond(&mut stream, 404, "text/plain", b"Not Found");
+    };
+    match render(&file, &ctx.doc) {
+        Ok((content_type, body)) => respond(&mut stream, 200, content_type, &body),
+        Err(e) => respond(
+            &mut stream,
+            500,
+            "text/plain; charset=utf-8",
+            format!("Preview failed: {}", e).as_bytes(),
+        ),
+    }
+}
+
+/// Whether a `Host` header names this server: `127.0.0.1:<port>` or
+/// `localhost:<port>`. Anything else is a foreign name pointed at loopback.
+fn is_allowed_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    host_port.parse() == Ok(port) && (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost"))
+}
+
+/// Whether any component of `path` is hidden (starts with a dot), which
+/// also covers `..`
+fn has_hidden_component(path: &Path) -> bool {
+    path.components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// Map a URL path to a file below `root` (traversal-safe, no hidden files).
+fn resolve(root: &Path, url_path: &str) -> Option<PathBuf> {
+    let rel = url_path.trim_start_matches('/');
+    if has_hidden_component(Path::new(rel)) {
+        return None;
+    }
+    let canonical = root.join(rel).canonicalize().ok()?;
+    // Symlinks may point at hidden files as well
+    let inside = canonical.strip_prefix(root).ok()?;
+    (!has_hidden_component(inside) && canonical.is_file()).then_some(canonical)
+}
+
+/// Render `file` for the browser: Markdown and source files as HTML pages
+/// with the reload script, everything else (images, PDFs) as-is.
+fn render(file: &Path, doc: &DocumentConfig) -> Result<(&'static str, Vec<u8>)> {
+    let html = if crate::browser::is_markdown(file) {
+        // Relative image and .md links resolve against the server root
+        Some(crate::browser::markdown::render_markdown_page(
+            file,
+            doc,
+            str::to_string,
+        )?)
+    } else if is_html(file) {
+        Some(std::fs::read_to_string(file)?)
+    } else if crate::browser::can_syntax_highlight(file) {
+        Some(crate::browser::syntax::render_text_page(file, doc)?)
+    } else {
+        None
+    };
+
+    Ok(match html {
+        Some(html) => (
+            "text/html; charset=utf-8",
+            inject_reload(&html).into_bytes(),
+        ),
+        None => {
+            let name = file.to_string_lossy();
+            let mime = if name.to_lowercase().ends_with(".pdf") {
+                "application/pdf"
+            } else {
+                crate::browser::markdown::image_mime_type(&name)
+                    .unwrap_or("application/octet-stream")
+            };
+            (mime, std::fs::read(file)?)
+        }
+    })
+}
+
+fn is_html(file: &Path) -> bool {
+    file.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("html") || e.eq_ignore_ascii_case("htm"))
+}
+
+/// Insert the reload script before `</body>` (or append it).
+fn inject_reload(html: &str) -> String {
+    match html.rfind("</body>") {
+        Some(pos) => format!("{}{}\n{}", &html[..pos], RELOAD_SCRIPT, &html[pos..]),
+        None => format!("{}\n{}", html, RELOAD_SCRIPT),
+    }
+}
+
+/// Server-sent event stream: one `reload` message per change of `file`.
+fn stream_events(mut stream: TcpStream, file: &Path, ctx: &ServerContext) -> std::io::Result<()> {
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    stream.flush()?;
+
+    let mut last = file_stamp(file);
+    let mut last_write = Instant::now();
+    while !ctx.shutdown.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+        let stamp = file_stamp(file);
+        if stamp != last {
+            last = stamp;
+            stream.write_all(b"data: reload\n\n")?;
+            stream.flush()?;
+            last_write = Instant::now();
+        } else if last_write.elapsed() >= KEEPALIVE_INTERVAL {
+            stream.write_all(b": keepalive\n\n")?;
+            stream.flush()?;
+            last_write = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+/// Modification time and size — changes when an editor or Claude rewrites the file.
+fn file_stamp(file: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(file).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// Percent-encode a path segment (RFC 3986 unreserved characters pass through).
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+/// Decode `%XX` escapes; malformed escapes are kept literally.
+pub(crate) fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push(hi << 4 | lo);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(server: &PreviewServer, path: &str) -> String {
+        get_from_host(server, path, &server.addr.to_string())
+    }
+
+    fn get_from_host(server: &PreviewServer, path: &str, host: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, host).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_percent_roundtrip() {
+        assert_eq!(percent_encode("Mein Plan.md"), "Mein%20Plan.md");
+        assert_eq!(percent_decode("/docs/Mein%20Plan.md"), "/docs/Mein Plan.md");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[test]
+    fn test_inject_reload() {
+        let html = inject_reload("<html><body>x</body></html>");
+        assert!(html.contains("EventSource"));
+        assert!(html.find("EventSource").unwrap() < html.find("</body>").unwrap());
+    }
+
+    #[test]
+    fn test_no_url_for_hidden_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join(".notes")).unwrap();
+        std::fs::write(tmp.path().join(".notes/todo.md"), "# Todo").unwrap();
+        std::fs::write(tmp.path().join(".env"), "KEY=1").unwrap();
+
+        let server = PreviewServer::start(tmp.path(), &DocumentConfig::default()).unwrap();
+        assert!(server.url_for(&tmp.path().join(".notes/todo.md")).is_none());
+        assert!(server.url_for(&tmp.path().join(".env")).is_none());
+    }
+
+    #[test]
+    fn test_serves_markdown_and_blocks_traversal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("project");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(root.join("Mein Plan.md"), "# Hallo").unwrap();
+        std::fs::write(tmp.path().join("secret.txt"), "geheim").unwrap();
+
+        let server = PreviewServer::start(&root, &DocumentConfig::default()).unwrap();
+        let url = server.url_for(&root.join("Mein Plan.md")).unwrap();
+        assert!(url.ends_with("/Mein%20Plan.md"));
+        assert!(server.url_for(&tmp.path().join("secret.txt")).is_none());
+
+        let page = get(&server, "/Mein%20Plan.md");
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+        assert!(page.contains("id=\"hallo\""));
+        assert!(page.contains("EventSource"));
+
+        assert!(get(&server, "/../secret.txt").starts_with("HTTP/1.1 404"));
+        assert!(get(&server, "/missing.md").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn test_rejects_foreign_hosts_and_hidden_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("doc.md"), "# Doc").unwrap();
+        std::fs::write(tmp.path().join(".env"), "TOKEN=x").unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join(".git").join("config"), "[core]").unwrap();
+        let server = PreviewServer::start(tmp.path(), &DocumentConfig::default()).unwrap();
+        let port = server.addr.port();
+
+        let local = format!("localhost:{}", port);
+        assert!(get_from_host(&server, "/doc.md", &local).starts_with("HTTP/1.1 200"));
+        // DNS rebinding: a foreign name resolving to 127.0.0.1
+        let foreign = format!("evil.example:{}", port);
+        assert!(get_from_host(&server, "/doc.md", &foreign).starts_with("HTTP/1.1 403"));
+        assert!(get_from_host(&server, "/doc.md", "127.0.0.1:1").starts_with("HTTP/1.1 403"));
+
+        assert!(get(&server, "/.env").starts_with("HTTP/1.1 404"));
+        assert!(get(&server, "/.git/config").starts_with("HTTP/1.1 404"));
+        assert!(get(&server, "/%2Egit/config").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn test_event_stream_reports_change() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("doc.md");
+        std::fs::write(&file, "# Eins").unwrap();
+        let server = PreviewServer::start(tmp.path(), &DocumentConfig::default()).unwrap();
+
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        write!(
+            stream,
+            "GET /events?path=%2Fdoc.md HTTP/1.1\r\nHost: {}\r\n\r\n",
+            server.addr
+        )
+        .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.starts_with("HTTP/1.1 200"));
+
+        // Let the stream take its baseline stamp, then change size and content
+        std::thread::sleep(POLL_INTERVAL * 2);
+        std::fs::write(&file, "# Zwei, jetzt länger").unwrap();
+
+        let mut received = String::new();
+        while !received.contains("data: reload") {
+            line.clear();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                break;
+            }
+            received.push_str(&line);
+        }
+        assert!(received.contains("data: reload"));
+    }
+}
@@ -112,6 +112,17 @@ pub fn text_to_html(
 ) -> Result<tempfile::NamedTempFile> {
     use std::io::Write;
 
+    let html = render_text_page(path, doc)?;
+
+    // Create secure temp file (O_EXCL) and write content
+    let mut tmp = crate::browser::pdf_export::default_preview_file(path, project_name)?;
+    tmp.write_all(html.as_bytes())?;
+    tmp.flush()?;
+    Ok(tmp)
+}
+
+/// Render a text file as a syntax-highlighted HTML page.
+pub(crate) fn render_text_page(path: &Path, doc: &DocumentConfig) -> Result<String> {
     let content = std::fs::read_to_string(path)?;
     let ss = SyntaxSet::load_defaults_newlines();
     let ts = ThemeSet::load_defaults();
@@ -136,19 +147,32 @@ pub fn text_to_html(
 
     // Build final HTML from config-driven template
     let template = build_syntax_template(doc);
-    let html = template
+    Ok(template
         .replace("{title}", filename)
         .replace("{filename}", filename)
         .replace("{language}", &language)
         .replace("{size}", &size)
         .replace("{lines}", &lines.to_string())
-        .replace("{highlighted_code}", &highlighted);
+        .replace("{highlighted_code}", &highlighted))
+}
 
-    // Create secure temp file (O_EXCL) and write content
-    let mut tmp = crate::browser::pdf_export::default_preview_file(path, project_name)?;
-    tmp.write_all(html.as_bytes())?;
-    tmp.flush()?;
-    Ok(tmp)
+/// Render saved terminal output as a page in the same template. `body` is
+/// the HTML-escaped, colored text; `size` and `lines` describe the plain text.
+pub(crate) fn render_terminal_page(
+    title: &str,
+    body: &str,
+    size: usize,
+    lines: usize,
+    doc: &DocumentConfig,
+) -> String {
+    let template = build_syntax_template(doc);
+    template
+        .replace("{title}", title)
+        .replace("{filename}", title)
+        .replace("{language}", "Terminal")
+        .replace("{size}", &format_file_size(size))
+        .replace("{lines}", &lines.to_string())
+        .replace("{highlighted_code}", &format!("<pre>{}</pre>", body))
 }
 
 /// Format file size in human-readable form
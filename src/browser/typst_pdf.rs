@@ -8,22 +8,35 @@
 //! - A4 format with configurable margins
 //! - Table styling with colored headers
 //! - Syntax highlighting via Typst built-in raw blocks
+//! - Mermaid/PlantUML blocks rendered to SVG via `browser::diagram`
+//! - Local SVG/PNG/JPEG images embedded, scaled to page width, titles as captions
 //! - Bundled Carlito font (metric-compatible Calibri replacement)
+//! - Configured fonts resolved against system and user fonts (`browser::fonts`)
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use typst::diag::{FileError, FileResult};
 use typst::foundations::{Bytes, Datetime};
+use typst::layout::{Frame, FrameItem};
 use typst::syntax::{FileId, Source};
 use typst::text::{Font, FontBook};
 use typst::utils::LazyHash;
 use typst::LibraryExt;
 
-use crate::browser::pdf_export::ExportOptions;
+use crate::browser::book::{Book, ChapterContext};
+use crate::browser::diagram::{render_diagram, DiagramKind};
+use crate::browser::fonts::{
+    css_font_families, resolve_font_family, FontReport, BUNDLED_BODY_FONT,
+};
+use crate::browser::front_matter::skip_metadata_blocks;
+use crate::browser::math::latex_to_typst;
+use crate::browser::pdf_export::{ExportOptions, ExportOutput};
+use crate::browser::preview_server::percent_decode;
 use crate::browser::slugify;
-use crate::config::DocumentConfig;
+use crate::config::{DiagramConfig, DocumentConfig};
 
 // --- Bundled Carlito font files (SIL Open Font License) ---
 static CARLITO_REGULAR: &[u8] = include_bytes!("../../assets/fonts/Carlito-Regular.ttf");
@@ -37,12 +50,13 @@ static DEJAVU_SANS_REGULAR: &[u8] = include_bytes!("../../assets/fonts/DejaVuSan
 
 /// The Typst page template with placeholders for config values.
 const TYPST_TEMPLATE: &str = r##"
+{document_meta}
 #set page(
   paper: "{page_size}",
   margin: (left: {margin_left}, right: {margin_right}, top: {margin_top}, bottom: {margin_bottom}),
   header: [
     #set text(font: ("{font_family}", "Carlito", "Liberation Sans", "DejaVu Sans"), size: {header_size}, fill: rgb("{header_border}"))
-    {title}
+    {header_title} #h(1fr) {confidentiality}
     #v(2pt)
     #line(length: 100%, stroke: 0.5pt + rgb("{header_border}"))
   ],
@@ -54,15 +68,15 @@ const TYPST_TEMPLATE: &str = r##"
       columns: (1fr, 1fr, 1fr),
       align: (left, center, right),
       [{company_name}],
-      [{date}],
-      [Seite #context counter(page).display() von #context counter(page).final().first()],
+      [{footer_date}],
+      [{page_label}],
     )
   ],
 )
 
-#set text(font: ("{font_family}", "Carlito", "Liberation Sans", "DejaVu Sans"), size: {body_size}, lang: "de")
-// Hyphenate long German compounds so they break inside narrow table columns
-// (lang "de" above provides the hyphenation patterns).
+#set text(font: ("{font_family}", "Carlito", "Liberation Sans", "DejaVu Sans"), size: {body_size}, lang: "{lang}")
+// Hyphenate long compounds so they break inside narrow table columns
+// (lang above provides the hyphenation patterns).
 #set text(hyphenate: true)
 #set par(justify: true, leading: 0.65em)
 #set heading(numbering: none)
@@ -109,9 +123,34 @@ const TYPST_TEMPLATE: &str = r##"
   text(font: ({code_font_list}), size: 0.92em)[#it],
 )
 
+{cover_page}
+{toc}
 {body}
 "##;
 
+/// Cover page inserted for `{cover_page}` when `document.template.cover_page`
+/// is set. Page numbering restarts after it.
+const COVER_PAGE_TEMPLATE: &str = r##"
+#page(header: none, footer: none)[
+  #set align(center + horizon)
+  {logo}
+  #v(2em)
+  #text(size: 2.2em, weight: "bold")[{title}]
+  {subtitle}
+  {version}
+  #v(1.5em)
+  #text(size: 1.2em)[{author}]
+  #v(0.5em)
+  #text(size: 1.1em)[{date}]
+  {confidentiality}
+]
+#counter(page).update(1)
+"##;
+
+/// Virtual directory for generated assets (rendered diagrams). Paths below it
+/// are served from memory by `WorkbenchWorld::file` and never touch the disk.
+const ASSET_DIR: &str = "/.workbench";
+
 // --- Typst World Implementation ---
 
 /// Minimal Typst World for self-contained document rendering.
@@ -121,6 +160,8 @@ struct WorkbenchWorld {
     library: LazyHash<typst::Library>,
     book: LazyHash<FontBook>,
     fonts: Vec<FontSlot>,
+    /// In-memory files keyed by rooted virtual path (see `ASSET_DIR`)
+    assets: HashMap<PathBuf, Bytes>,
 }
 
 /// Font slot with lazy loading (for system fonts) or pre-loaded (for bundled fonts).
@@ -166,6 +207,9 @@ impl typst::World for WorkbenchWorld {
     fn file(&self, id: FileId) -> FileResult<Bytes> {
         let vpath = id.vpath();
         let path = vpath.as_rooted_path();
+        if let Some(data) = self.assets.get(path) {
+            return Ok(data.clone());
+        }
         let rel = path.strip_prefix("/").unwrap_or(path);
         let full_path = self.source_dir.join(rel);
         match std::fs::read(&full_path) {
@@ -198,8 +242,9 @@ impl typst::World for WorkbenchWorld {
     }
 }
 
-/// Build font book and font slots from bundled + system fonts.
-fn build_fonts(_doc: &DocumentConfig) -> (LazyHash<FontBook>, Vec<FontSlot>) {
+/// Build font book and font slots from bundled, system (fontconfig dirs)
+/// and user fonts (`~/.config/claude-workbench/fonts`).
+fn build_fonts() -> (FontBook, Vec<FontSlot>) {
     let mut book = FontBook::new();
     let mut fonts = Vec::new();
 
@@ -223,8 +268,9 @@ fn build_fonts(_doc: &DocumentConfig) -> (LazyHash<FontBook>, Vec<FontSlot>) {
         }
     }
 
-    // 2. Scan system fonts via typst-kit FontSearcher
-    let system_fonts = typst_kit::fonts::FontSearcher::new().search();
+    // 2. Scan system + user fonts via typst-kit FontSearcher
+    let user_dirs = crate::config::export_fonts_dir().filter(|dir| dir.is_dir());
+    let system_fonts = typst_kit::fonts::FontSearcher::new().search_with(user_dirs);
     // Transfer font info from system_fonts.book to our book,
     // and create slots for each system font
     for (info_idx, slot) in system_fonts.fonts.into_iter().enumerate() {
@@ -247,7 +293,52 @@ fn build_fonts(_doc: &DocumentConfig) -> (LazyHash<FontBook>, Vec<FontSlot>) {
         }
     }
 
-    (LazyHash::new(book), fonts)
+    (book, fonts)
+}
+
+/// Resolve the configured CSS font lists against `book`. The returned
+/// settings name the installed body family (bundled Carlito if none is);
+/// the code list is passed on as a whole (Typst picks per glyph), so the
+/// code font is the first installed one including the appended fallbacks.
+fn resolve_fonts(doc: &DocumentConfig, book: &FontBook) -> (DocumentConfig, FontReport) {
+    let installed = |family: &str| book.contains_family(family);
+    let body = resolve_font_family(&doc.fonts.body, installed)
+        .unwrap_or_else(|| BUNDLED_BODY_FONT.to_string());
+    let code = code_font_families(&doc.fonts.code)
+        .into_iter()
+        .find(|family| installed(family));
+
+    let mut resolved = doc.clone();
+    resolved.fonts.body = body.clone();
+    let report = FontReport {
+        body_requested: css_font_families(&doc.fonts.body).into_iter().next(),
+        body,
+        code,
+        fallbacks: Default::default(),
+    };
+    (resolved, report)
+}
+
+/// Record the characters set in a font other than the body/code font.
+fn collect_fallbacks(frame: &Frame, report: &mut FontReport) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_fallbacks(&group.frame, report),
+            FrameItem::Text(text) => {
+                let family = &text.font.info().family;
+                if *family == report.body || Some(family) == report.code.as_ref() {
+                    continue;
+                }
+                let chars = text.text.chars().filter(|c| !c.is_whitespace());
+                report
+                    .fallbacks
+                    .entry(family.clone())
+                    .or_default()
+                    .extend(chars);
+            }
+            _ => {}
+        }
+    }
 }
 
 // --- Markdown to Typst Renderer ---
@@ -272,6 +363,18 @@ struct TypstRenderer {
     table_size: String,
     table_cell_inset: String,
     code_font_list: String,
+    diagrams: DiagramConfig,
+    /// Generated files referenced from the markup (virtual path → content)
+    assets: HashMap<PathBuf, Bytes>,
+    /// Book export: label namespace and cross-chapter links
+    chapter: Option<ChapterContext>,
+    /// Directory local images resolve against (and must stay inside)
+    image_root: PathBuf,
+    /// Image being rendered: (destination, title); its text events are the alt text
+    image: Option<(String, String)>,
+    image_alt: String,
+    /// Images that could not be embedded, reported with the export result
+    warnings: Vec<String>,
 }
 
 impl TypstRenderer {
@@ -295,17 +398,49 @@ impl TypstRenderer {
             table_size: doc.sizes.table.clone(),
             table_cell_inset: doc.sizes.table_cell_inset.clone(),
             code_font_list: build_code_font_list(&doc.fonts.code),
+            diagrams: doc.diagrams.clone(),
+            assets: HashMap::new(),
+            chapter: None,
+            image_root: PathBuf::from("."),
+            image: None,
+            image_alt: String::new(),
+            warnings: Vec::new(),
         }
     }
 
     /// Render Markdown string to Typst markup.
+    #[cfg(test)]
     fn render(md: &str, doc: &DocumentConfig) -> String {
+        Self::run(md, doc, Path::new(".")).out
+    }
+
+    /// Render Markdown to Typst markup, keeping the generated assets that the
+    /// markup references. The caller hands `assets` to `WorkbenchWorld`.
+    /// Local images resolve against `image_root` (the Markdown file's folder).
+    fn run(md: &str, doc: &DocumentConfig, image_root: &Path) -> Self {
+        Self::run_chapter(md, doc, image_root, None)
+    }
+
+    /// Render one chapter of a book: heading labels are namespaced by the
+    /// chapter and links to other chapters become internal links. Images
+    /// resolve against the chapter folder below `image_root` (the book root).
+    fn run_chapter(
+        md: &str,
+        doc: &DocumentConfig,
+        image_root: &Path,
+        chapter: Option<ChapterContext>,
+    ) -> Self {
         use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 
-        let opts =
-            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
-        let parser = Parser::new_ext(md, opts);
+        let opts = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_MATH
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+        let parser = skip_metadata_blocks(Parser::new_ext(md, opts));
         let mut r = TypstRenderer::new(doc);
+        r.chapter = chapter;
+        r.image_root = image_root.to_path_buf();
 
         for event in parser {
             match event {
@@ -321,7 +456,8 @@ impl TypstRenderer {
                     // Generate a label from heading text for internal link targets
                     let slug = slugify(&r.heading_buf);
                     if !slug.is_empty() {
-                        r.out.push_str(&format!("\n#label(\"{}\")", slug));
+                        let label = r.label_for(&slug);
+                        r.out.push_str(&format!("\n#label(\"{}\")", label));
                     }
                     r.out.push('\n');
                 }
@@ -367,7 +503,14 @@ impl TypstRenderer {
                     let lang = r.code_lang.take();
                     let code = r.code_buf.clone();
                     let code = code.trim_end_matches('\n');
-                    if let Some(lang) = lang {
+                    if let Some(image) = lang
+                        .as_deref()
+                        .and_then(DiagramKind::from_info)
+                        .and_then(|kind| r.register_diagram(kind, code))
+                    {
+                        r.out
+                            .push_str(&format!("\n#align(center, image(\"{}\"))\n", image));
+                    } else if let Some(lang) = lang {
                         r.out.push_str(&format!("\n```{}\n{}\n```\n", lang, code));
                     } else {
                         r.out.push_str(&format!("\n```\n{}\n```\n", code));
@@ -396,8 +539,13 @@ impl TypstRenderer {
                     let url = dest_url.to_string();
                     if let Some(anchor) = url.strip_prefix('#') {
                         // Internal anchor link → Typst label reference
-                        let slug = slugify(anchor);
-                        r.push_to_active(&format!("#link(label(\"{}\"))[", slug));
+                        let label = r.label_for(&slugify(anchor));
+                        r.push_to_active(&format!("#link(label(\"{}\"))[", label));
+                    } else if let Some(label) =
+                        r.chapter.as_ref().and_then(|c| c.resolve_link(&url))
+                    {
+                        // Link to another chapter of the book
+                        r.push_to_active(&format!("#link(label(\"{}\"))[", label));
                     } else {
                         r.push_to_active(&format!("#link(\"{}\")[", url));
                     }
@@ -407,19 +555,18 @@ impl TypstRenderer {
                 }
 
                 // --- Images ---
-                Event::Start(Tag::Image { dest_url, .. }) => {
-                    let url = dest_url.to_string();
-                    if url.starts_with("http://") || url.starts_with("https://") {
-                        // Remote images cannot be loaded by Typst — render as link
-                        r.out.push_str(&format!(
-                            "#text(fill: rgb(\"#666666\"), size: 9pt)[Image: #link(\"{}\")[{}]]",
-                            url, url
-                        ));
-                    } else {
-                        r.out.push_str(&format!("#image(\"{}\")", url));
+                Event::Start(Tag::Image {
+                    dest_url, title, ..
+                }) => {
+                    r.image = Some((dest_url.to_string(), title.to_string()));
+                    r.image_alt.clear();
+                }
+                Event::End(TagEnd::Image) => {
+                    if let Some((url, title)) = r.image.take() {
+                        let markup = r.image_markup(&url, &title);
+                        r.push_to_active(&markup);
                     }
                 }
-                Event::End(TagEnd::Image) => {}
 
                 // --- Lists ---
                 Event::Start(Tag::List(first)) => {
@@ -510,7 +657,9 @@ impl TypstRenderer {
 
                 // --- Text ---
                 Event::Text(text) => {
-                    if r.in_code_block {
+                    if r.image.is_some() {
+                        r.image_alt.push_str(&text);
+                    } else if r.in_code_block {
                         r.code_buf.push_str(&text);
                     } else if r.in_table {
                         // Add break opportunities so long identifiers wrap inside
@@ -568,9 +717,14 @@ impl TypstRenderer {
                 Event::Start(Tag::HtmlBlock) | Event::End(TagEnd::HtmlBlock) => {}
 
                 // --- Math (pulldown-cmark 0.13) ---
-                // Render LaTeX-like math as escaped raw text — no math typesetting.
-                Event::InlineMath(text) | Event::DisplayMath(text) => {
-                    r.push_to_active(&typst_escape(&text));
+                // LaTeX is translated to Typst math; unparseable input stays literal.
+                Event::InlineMath(text) => {
+                    let out = latex_to_typst(&text, false).unwrap_or_else(|| typst_escape(&text));
+                    r.push_to_active(&out);
+                }
+                Event::DisplayMath(text) => {
+                    let out = latex_to_typst(&text, true).unwrap_or_else(|| typst_escape(&text));
+                    r.push_to_active(&out);
                 }
 
                 // pulldown-cmark 0.13 added DefinitionList* and Superscript/Subscript
@@ -580,7 +734,109 @@ impl TypstRenderer {
             }
         }
 
-        r.out
+        r
+    }
+
+    /// Render a diagram block and register the SVG as a virtual asset.
+    /// Returns the Typst path to reference, or `None` to keep the code block.
+    fn register_diagram(&mut self, kind: DiagramKind, source: &str) -> Option<String> {
+        let diagram = render_diagram(kind, source, &self.diagrams)?;
+        let path = format!("{}/diagram-{}.svg", ASSET_DIR, diagram.hash);
+        self.assets
+            .insert(PathBuf::from(&path), Bytes::new(diagram.svg.into_bytes()));
+        Some(path)
+    }
+
+    /// Markup for a Markdown image: local files are embedded (scaled down to
+    /// the available width, title as caption), remote ones become a link and
+    /// missing ones a visible placeholder.
+    fn image_markup(&mut self, url: &str, title: &str) -> String {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            // Remote images cannot be loaded by Typst — render as link
+            return format!(
+                "#text(fill: rgb(\"#666666\"), size: 9pt)[Image: #link({})[{}]]",
+                typst_string(url),
+                typst_escape(url)
+            );
+        }
+        let path = match self.register_image(url) {
+            Ok(path) => path,
+            Err(reason) => {
+                self.warnings.push(format!("Image skipped: {}", reason));
+                let alt = self.image_alt.trim();
+                let label = if alt.is_empty() {
+                    typst_escape(url)
+                } else {
+                    format!("{} ({})", typst_escape(url), typst_escape(alt))
+                };
+                return format!(
+                    "#box(stroke: 0.5pt + rgb(\"#cc0000\"), inset: 4pt)[#text(fill: rgb(\"#cc0000\"), size: 9pt)[Image not found: {}]]",
+                    label
+                );
+            }
+        };
+        // Natural size, but never wider than the page/column
+        let image = format!(
+            "layout(size => {{ let img = image({p}); image({p}, width: calc.min(measure(img).width, size.width)) }})",
+            p = typst_string(&path)
+        );
+        let title = title.trim();
+        if title.is_empty() {
+            format!("#align(center, {})", image)
+        } else {
+            format!("#figure({}, caption: [{}])", image, typst_escape(title))
+        }
+    }
+
+    /// Load a local SVG/PNG/JPEG image as an in-memory asset. The destination
+    /// is percent-decoded first; relative paths resolve against the chapter
+    /// folder below `image_root`, absolute ones are taken as filesystem paths.
+    /// Either way the file must lie inside `image_root`. `Err` says why not.
+    fn register_image(&mut self, url: &str) -> Result<String, String> {
+        use std::hash::{Hash, Hasher};
+
+        let url = percent_decode(url);
+        let ext = Path::new(&url)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !matches!(ext.as_str(), "svg" | "png" | "jpg" | "jpeg") {
+            return Err(format!("{}: unsupported image type", url));
+        }
+        let root = self
+            .image_root
+            .canonicalize()
+            .map_err(|e| format!("{}: {}", url, e))?;
+        let candidate = if Path::new(&url).is_absolute() {
+            PathBuf::from(&url)
+        } else {
+            // Book chapters in subfolders: paths are relative to the chapter
+            let rel_dir = self.chapter.as_ref().map_or("", |c| c.rel_dir.as_str());
+            root.join(rel_dir).join(&url)
+        };
+        let path = candidate
+            .canonicalize()
+            .map_err(|_| format!("{}: file not found", url))?;
+        // Same path traversal guard as the HTML export
+        if !path.starts_with(&root) {
+            return Err(format!("{}: outside the document folder", url));
+        }
+        let data = std::fs::read(&path).map_err(|e| format!("{}: {}", url, e))?;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        path.hash(&mut hasher);
+        let vpath = format!("{}/image-{:016x}.{}", ASSET_DIR, hasher.finish(), ext);
+        self.assets.insert(PathBuf::from(&vpath), Bytes::new(data));
+        Ok(vpath)
+    }
+
+    /// Typst label for a heading slug (namespaced by chapter in book exports)
+    fn label_for(&self, slug: &str) -> String {
+        match &self.chapter {
+            Some(chapter) => chapter.heading_label(slug),
+            None => slug.to_string(),
+        }
     }
 
     /// Push text to the appropriate buffer (cell_buf if in table, out otherwise)
@@ -593,6 +849,16 @@ impl TypstRenderer {
     }
 }
 
+/// Typst `paper` name for a configured page size; Typst spells the US
+/// sizes `us-letter` and `us-legal`.
+fn typst_paper(name: &str) -> String {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "letter" => "us-letter".to_string(),
+        "legal" => "us-legal".to_string(),
+        other => other.to_string(),
+    }
+}
+
 /// Escape special Typst characters in literal text.
 fn typst_escape(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
@@ -684,32 +950,52 @@ fn break_code_token(s: &str) -> String {
     result
 }
 
+/// Code font families of a CSS font-family string, followed by reliable
+/// fallbacks that are not already listed.
+fn code_font_families(css_fonts: &str) -> Vec<String> {
+    let mut fonts = css_font_families(css_fonts);
+    for fallback in ["DejaVu Sans Mono", "DejaVu Sans", "Liberation Mono"] {
+        if !fonts.iter().any(|f| f == fallback) {
+            fonts.push(fallback.to_string());
+        }
+    }
+    fonts
+}
+
 /// Build a Typst-compatible font list from a CSS font-family string.
 ///
 /// Parses entries like `'SF Mono', Monaco, 'Cascadia Code', Consolas, monospace`
 /// into `"SF Mono", "Monaco", "Cascadia Code", "Consolas"` (Typst format).
 /// Filters out generic CSS values like `monospace` and appends reliable fallbacks.
 fn build_code_font_list(css_fonts: &str) -> String {
-    let mut fonts: Vec<String> = css_fonts
-        .split(',')
-        .map(|s| s.trim().trim_matches('\'').trim_matches('"').to_string())
-        .filter(|s| !s.is_empty() && s != "monospace" && s != "serif" && s != "sans-serif")
+    code_font_families(css_fonts)
+        .iter()
         .map(|s| format!("\"{}\"", s))
-        .collect();
-
-    // Append reliable fallbacks if not already present
-    for fallback in &["DejaVu Sans Mono", "DejaVu Sans", "Liberation Mono"] {
-        let quoted = format!("\"{}\"", fallback);
-        if !fonts.iter().any(|f| f == &quoted) {
-            fonts.push(quoted);
-        }
-    }
-
-    fonts.join(", ")
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Build the complete Typst document from template + body.
-fn build_typst_document(body: &str, options: &ExportOptions, doc: &DocumentConfig) -> String {
+///
+/// Uses the custom template named in `options.template` (from the config
+/// templates dir) or the built-in layout. `logo` is the virtual path of the
+/// logo asset, if one was loaded.
+fn build_typst_document(
+    body: &str,
+    options: &ExportOptions,
+    doc: &DocumentConfig,
+    logo: Option<&str>,
+) -> Result<String> {
+    let template = match &options.template {
+        Some(name) => {
+            let path = crate::browser::pdf_export::template_path(name)
+                .ok_or_else(|| anyhow::anyhow!("Invalid template name: {}", name))?;
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Cannot read template {}", path.display()))?
+        }
+        None => TYPST_TEMPLATE.to_string(),
+    };
+
     // Extract font family name (first entry before comma for Typst)
     let font_family = doc
         .fonts
@@ -725,8 +1011,74 @@ fn build_typst_document(body: &str, options: &ExportOptions, doc: &DocumentConfi
 
     let (margin_top, margin_right, margin_bottom, margin_left) = doc.pdf.resolved_margins();
 
-    TYPST_TEMPLATE
-        .replace("{page_size}", &doc.pdf.page_size.to_lowercase())
+    let logo_markup = logo
+        .map(|path| format!("#image(\"{}\", width: 40%)", path))
+        .unwrap_or_default();
+    let title = typst_escape(&options.title);
+    let author = typst_escape(&options.author);
+    let tpl = &doc.template;
+    let meta = &options.meta;
+    let subtitle = typst_escape(meta.subtitle.trim());
+    let confidentiality = typst_escape(meta.confidentiality.trim());
+    let version = typst_escape(tpl.version.trim());
+    let tags = typst_escape(&meta.tags.join(", "));
+
+    let header_title = if subtitle.is_empty() {
+        title.clone()
+    } else {
+        format!("{} — {}", title, subtitle)
+    };
+    let date = typst_escape(&options.date);
+    let footer_date = if version.is_empty() {
+        date.clone()
+    } else {
+        format!("{} · Version {}", date, version)
+    };
+
+    let cover_page = if tpl.cover_page {
+        let cover_version = if version.is_empty() {
+            String::new()
+        } else {
+            format!("#v(0.8em)\n  #text(size: 1.2em)[Version {}]", version)
+        };
+        let cover_subtitle = if subtitle.is_empty() {
+            String::new()
+        } else {
+            format!("#v(0.5em)\n  #text(size: 1.4em)[{}]", subtitle)
+        };
+        let cover_confidentiality = if confidentiality.is_empty() {
+            String::new()
+        } else {
+            format!("#v(1.5em)\n  #text(weight: \"bold\")[{}]", confidentiality)
+        };
+        COVER_PAGE_TEMPLATE
+            .replace("{logo}", &logo_markup)
+            .replace("{subtitle}", &cover_subtitle)
+            .replace("{confidentiality}", &cover_confidentiality)
+            .replace("{version}", &cover_version)
+            .replace("{author}", &author)
+            .replace("{date}", &date)
+            .replace("{title}", &title)
+    } else {
+        String::new()
+    };
+    let toc = if tpl.toc {
+        format!(
+            "#outline(title: [{}], depth: 3, indent: auto)\n#pagebreak()",
+            typst_escape(tpl.toc_title())
+        )
+    } else {
+        String::new()
+    };
+
+    let document = template
+        .replace("{document_meta}", &document_meta_markup(options))
+        .replace("{header_title}", &header_title)
+        .replace("{footer_date}", &footer_date)
+        .replace("{subtitle}", &subtitle)
+        .replace("{confidentiality}", &confidentiality)
+        .replace("{tags}", &tags)
+        .replace("{page_size}", &typst_paper(&doc.pdf.page_size))
         .replace("{margin_top}", &margin_top)
         .replace("{margin_right}", &margin_right)
         .replace("{margin_bottom}", &margin_bottom)
@@ -736,8 +1088,16 @@ fn build_typst_document(body: &str, options: &ExportOptions, doc: &DocumentConfi
         .replace("{footer_color}", &doc.colors.footer)
         .replace("{footer_size}", &doc.sizes.footer)
         .replace("{company_name}", &typst_escape(&doc.resolved_footer_text()))
-        .replace("{date}", &options.date)
-        .replace("{title}", &typst_escape(&options.title))
+        .replace("{date}", &date)
+        .replace("{title}", &title)
+        .replace("{author}", &author)
+        .replace("{version}", &version)
+        .replace("{lang}", &tpl.resolved_language())
+        .replace(
+            "{page_label}",
+            &page_label_markup(&tpl.resolved_page_label()),
+        )
+        .replace("{logo}", &logo_markup)
         .replace("{font_family}", font_family)
         .replace("{code_font_list}", &code_font_list)
         .replace("{body_size}", &doc.sizes.body)
@@ -750,7 +1110,87 @@ fn build_typst_document(body: &str, options: &ExportOptions, doc: &DocumentConfi
         .replace("{heading_separator}", &doc.colors.heading_separator)
         .replace("{code_bg}", &doc.colors.code_bg)
         .replace("{code_block_inset}", &doc.sizes.code_block_inset)
-        .replace("{body}", body)
+        .replace("{cover_page}", &cover_page)
+        .replace("{toc}", &toc)
+        .replace("{body}", body);
+    Ok(document)
+}
+
+/// `#set document(...)` with title, author and keywords for the PDF metadata.
+fn document_meta_markup(options: &ExportOptions) -> String {
+    let mut args = vec![format!("title: {}", typst_string(&options.title))];
+    if !options.author.trim().is_empty() {
+        args.push(format!("author: {}", typst_string(options.author.trim())));
+    }
+    if !options.meta.tags.is_empty() {
+        let keywords: Vec<String> = options.meta.tags.iter().map(|t| typst_string(t)).collect();
+        // Trailing comma keeps a single keyword an array
+        args.push(format!("keywords: ({},)", keywords.join(", ")));
+    }
+    format!("#set document({})", args.join(", "))
+}
+
+/// Quote a value as a Typst string literal.
+fn typst_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Turn a page label like `"Page {page} of {total}"` into Typst markup with
+/// live page counters. The trailing `;` ends each embedded expression so the
+/// following text cannot be parsed as a method call or argument.
+fn page_label_markup(label: &str) -> String {
+    let mut out = String::new();
+    let mut rest = label;
+    while !rest.is_empty() {
+        let next = [
+            ("{page}", "#context counter(page).display();"),
+            ("{total}", "#context counter(page).final().first();"),
+        ]
+        .iter()
+        .filter_map(|(key, markup)| rest.find(key).map(|pos| (pos, *key, *markup)))
+        .min_by_key(|(pos, _, _)| *pos);
+        match next {
+            Some((pos, key, markup)) => {
+                out.push_str(&typst_escape(&rest[..pos]));
+                out.push_str(markup);
+                rest = &rest[pos + key.len()..];
+            }
+            None => {
+                out.push_str(&typst_escape(rest));
+                break;
+            }
+        }
+    }
+    out
+}
+
+/// Load the configured cover logo as an in-memory asset.
+/// Returns `(virtual path, bytes)`; unsupported or unreadable files are skipped.
+fn load_logo(doc: &DocumentConfig) -> Option<(PathBuf, Bytes)> {
+    let path = doc.template.logo_path()?;
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !matches!(
+        ext.as_str(),
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp"
+    ) {
+        return None;
+    }
+    let data = std::fs::read(&path).ok()?;
+    Some((
+        PathBuf::from(format!("{}/logo.{}", ASSET_DIR, ext)),
+        Bytes::new(data),
+    ))
 }
 
 /// Export a Markdown file to PDF using native Typst rendering.
@@ -762,35 +1202,116 @@ pub fn export_markdown_to_pdf(
     target: &Path,
     options: &ExportOptions,
     doc: &DocumentConfig,
-) -> Result<PathBuf> {
+) -> Result<ExportOutput> {
     // 1. Read markdown source
     let md = std::fs::read_to_string(md_source)?;
 
-    // 2. Convert Markdown to Typst markup
-    let body = TypstRenderer::render(&md, doc);
+    // 2. Resolve the configured fonts against the installed ones
+    let (font_book, fonts) = build_fonts();
+    let (doc, report) = resolve_fonts(doc, &font_book);
+    let doc = &doc;
 
-    // 3. Build complete Typst document
-    let typ_source = build_typst_document(&body, options, doc);
-
-    // 4. Build font resolver
-    let (book, fonts) = build_fonts(doc);
-
-    // 5. Create Typst source and world
-    let source = Source::detached(&typ_source);
-    let library = LazyHash::new(typst::Library::default());
+    // 3. Convert Markdown to Typst markup (plus diagram and image assets)
     let source_dir = md_source
         .parent()
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| PathBuf::from("."));
+    let rendered = TypstRenderer::run(&md, doc, &source_dir);
+
+    // 4. Build complete Typst document (template, cover logo as virtual asset)
+    let mut assets = rendered.assets;
+    let logo = load_logo(doc).map(|(path, data)| {
+        let vpath = path.to_string_lossy().to_string();
+        assets.insert(path, data);
+        vpath
+    });
+    let typ_source = build_typst_document(&rendered.out, options, doc, logo.as_deref())?;
+
+    // 5. Compile; remaining relative paths resolve against the Markdown file's folder
+    let mut output = compile_to_pdf(
+        &typ_source,
+        source_dir,
+        assets,
+        (font_book, fonts),
+        report,
+        target,
+    )?;
+    output.warnings = rendered.warnings;
+    Ok(output)
+}
+
+/// Export a book (ordered chapters) as one PDF. Each chapter starts on a new
+/// page with a `ch{n}` anchor; inter-chapter `.md` links point to it.
+pub fn export_book_to_pdf(
+    book: &Book,
+    target: &Path,
+    options: &ExportOptions,
+    doc: &DocumentConfig,
+) -> Result<ExportOutput> {
+    let (font_book, fonts) = build_fonts();
+    let (doc, report) = resolve_fonts(doc, &font_book);
+    let doc = &doc;
+
+    let mut body = String::new();
+    let mut assets = HashMap::new();
+    let mut warnings = Vec::new();
+    for (index, chapter) in book.chapters.iter().enumerate() {
+        let path = book.root.join(chapter);
+        let md = std::fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        let rendered =
+            TypstRenderer::run_chapter(&md, doc, &book.root, Some(book.chapter_context(index)));
+        body.push_str(&format!(
+            "\n#pagebreak(weak: true)\n#metadata(none) <{}>\n",
+            Book::chapter_label(index)
+        ));
+        body.push_str(&rendered.out);
+        assets.extend(rendered.assets);
+        warnings.extend(rendered.warnings);
+    }
+
+    let logo = load_logo(doc).map(|(path, data)| {
+        let vpath = path.to_string_lossy().to_string();
+        assets.insert(path, data);
+        vpath
+    });
+    let typ_source = build_typst_document(&body, options, doc, logo.as_deref())?;
+    let mut output = compile_to_pdf(
+        &typ_source,
+        book.root.clone(),
+        assets,
+        (font_book, fonts),
+        report,
+        target,
+    )?;
+    output.warnings = warnings;
+    Ok(output)
+}
+
+/// Compile a complete Typst document and write the PDF to `target`.
+/// Relative file paths in the markup resolve against `source_dir`; the font
+/// report is completed with the glyphs that fell back to other fonts.
+fn compile_to_pdf(
+    typ_source: &str,
+    source_dir: PathBuf,
+    assets: HashMap<PathBuf, Bytes>,
+    (book, fonts): (FontBook, Vec<FontSlot>),
+    mut report: FontReport,
+    target: &Path,
+) -> Result<ExportOutput> {
+    // 1. Create Typst source and world
+    let source = Source::detached(typ_source);
+    let library = LazyHash::new(typst::Library::default());
     let world = WorkbenchWorld {
         source,
         source_dir,
         library,
-        book,
+        book: LazyHash::new(book),
         fonts,
+        assets,
     };
 
-    // 6. Compile the document
+    // 2. Compile the document
     let result = typst::compile::<typst::layout::PagedDocument>(&world);
     let document = result.output.map_err(|errors| {
         let messages: Vec<String> = errors
@@ -800,7 +1321,11 @@ pub fn export_markdown_to_pdf(
         anyhow::anyhow!("Typst PDF generation failed:\n{}", messages.join("\n"))
     })?;
 
-    // 7. Export to PDF
+    for page in &document.pages {
+        collect_fallbacks(&page.frame, &mut report);
+    }
+
+    // 3. Export to PDF
     let pdf_options = typst_pdf::PdfOptions::default();
     let pdf_bytes = typst_pdf::pdf(&document, &pdf_options).map_err(|errors| {
         let messages: Vec<String> = errors
@@ -810,13 +1335,17 @@ pub fn export_markdown_to_pdf(
         anyhow::anyhow!("PDF export failed:\n{}", messages.join("\n"))
     })?;
 
-    // 8. Write output file
+    // 4. Write output file
     if let Some(parent) = target.parent() {
         std::fs::create_dir_all(parent)?;
     }
     std::fs::write(target, pdf_bytes.as_slice())?;
 
-    Ok(target.to_path_buf())
+    Ok(ExportOutput {
+        path: target.to_path_buf(),
+        fonts: Some(report),
+        warnings: Vec::new(),
+    })
 }
 
 #[cfg(test)]
@@ -885,6 +1414,48 @@ mod tests {
         assert!(result.contains("fn main() {}"));
     }
 
+    #[test]
+    fn test_math_translated_to_typst() {
+        let doc = DocumentConfig::default();
+        let result = TypstRenderer::render("Euler: $e^{i\\pi} = -1$\n\n$$\\frac{a}{b}$$", &doc);
+        assert!(result.contains("$e^(i π) = - 1$"), "got: {}", result);
+        assert!(result.contains("$ frac(a, b) $"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_unparseable_math_stays_literal() {
+        let doc = DocumentConfig::default();
+        let result = TypstRenderer::render("$\\left( x$", &doc);
+        assert!(!result.contains("lr("));
+        // The inline-math event, not prose: no escaped `$` delimiters
+        assert!(!result.contains("\\$"));
+        assert!(result.contains("\\\\left( x"));
+    }
+
+    #[test]
+    fn test_diagram_without_renderer_stays_code_block() {
+        let mut doc = DocumentConfig::default();
+        doc.diagrams.mermaid_command = vec!["claude-workbench-no-such-renderer".to_string()];
+        doc.diagrams.cache = false;
+        let r = TypstRenderer::run("```mermaid\ngraph TD; A-->B\n```", &doc, Path::new("."));
+        assert!(r.out.contains("```mermaid"));
+        assert!(r.assets.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_diagram_rendered_as_virtual_image() {
+        let mut doc = DocumentConfig::default();
+        doc.diagrams.plantuml_command = vec!["cat".to_string()];
+        doc.diagrams.cache = false;
+        let r = TypstRenderer::run("```plantuml\n<svg><g/></svg>\n```", &doc, Path::new("."));
+        assert!(r
+            .out
+            .contains("#align(center, image(\"/.workbench/diagram-"));
+        assert!(!r.out.contains("```plantuml"));
+        assert_eq!(r.assets.len(), 1);
+    }
+
     #[test]
     fn test_markdown_to_typst_list() {
         let doc = DocumentConfig::default();
@@ -935,8 +1506,11 @@ mod tests {
             author: "Test Author".to_string(),
             date: "26.03.2026".to_string(),
             format: crate::browser::pdf_export::ExportFormat::Pdf,
+            template: None,
+            meta: Default::default(),
+            profile: None,
         };
-        let result = build_typst_document("Hello world", &options, &doc);
+        let result = build_typst_document("Hello world", &options, &doc, None).unwrap();
         assert!(result.contains("paper: \"a4\""));
         assert!(result.contains("Test Document"));
         assert!(result.contains("26.03.2026"));
@@ -1014,6 +1588,217 @@ mod tests {
         assert!(result.contains("`cargo build`"));
     }
 
+    fn template_options() -> ExportOptions {
+        ExportOptions {
+            title: "Handbuch".to_string(),
+            author: "Musterfirma".to_string(),
+            date: "01.01.2026".to_string(),
+            format: crate::browser::pdf_export::ExportFormat::Pdf,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn test_page_label_localized() {
+        let mut doc = DocumentConfig::default();
+        let de = build_typst_document("x", &template_options(), &doc, None).unwrap();
+        assert!(de.contains(
+            "[Seite #context counter(page).display(); von #context counter(page).final().first();]"
+        ));
+        assert!(de.contains("lang: \"de\""));
+
+        doc.template.language = "en".to_string();
+        let en = build_typst_document("x", &template_options(), &doc, None).unwrap();
+        assert!(en.contains("[Page #context counter(page).display(); of "));
+        assert!(en.contains("lang: \"en\""));
+
+        doc.template.page_label = "{page}/{total}".to_string();
+        let custom = build_typst_document("x", &template_options(), &doc, None).unwrap();
+        assert!(custom.contains("[#context counter(page).display();/#context"));
+    }
+
+    #[test]
+    fn test_cover_page_and_toc() {
+        let mut doc = DocumentConfig::default();
+        let plain = build_typst_document("x", &template_options(), &doc, None).unwrap();
+        assert!(!plain.contains("#outline"));
+        assert!(!plain.contains("counter(page).update(1)"));
+
+        doc.template.cover_page = true;
+        doc.template.toc = true;
+        doc.template.version = "2.1".to_string();
+        let result =
+            build_typst_document("x", &template_options(), &doc, Some("/.workbench/logo.png"))
+                .unwrap();
+        assert!(result.contains("#image(\"/.workbench/logo.png\", width: 40%)"));
+        assert!(result.contains("[Version 2.1]"));
+        assert!(result.contains("weight: \"bold\")[Handbuch]"));
+        assert!(result.contains("#outline(title: [Inhaltsverzeichnis]"));
+        // Cover and TOC come before the body
+        assert!(result.find("#outline").unwrap() < result.rfind('x').unwrap());
+        assert!(!result.contains("{cover_page}") && !result.contains("{toc}"));
+    }
+
+    #[test]
+    fn test_front_matter_metadata_in_document() {
+        let mut doc = DocumentConfig::default();
+        doc.template.version = "1.2".to_string();
+        let mut options = template_options();
+        options.author = "Jane \"JD\" Doe".to_string();
+        options.meta.subtitle = "Teil 1".to_string();
+        options.meta.confidentiality = "Vertraulich".to_string();
+        options.meta.tags = vec!["ops".to_string()];
+        let result = build_typst_document("x", &options, &doc, None).unwrap();
+
+        assert!(result.contains(
+            "#set document(title: \"Handbuch\", author: \"Jane \\\"JD\\\" Doe\", keywords: (\"ops\",))"
+        ));
+        assert!(result.contains("Handbuch — Teil 1 #h(1fr) Vertraulich"));
+        assert!(result.contains("[01.01.2026 · Version 1.2]"));
+    }
+
+    #[test]
+    fn test_front_matter_stripped_from_body() {
+        let md = "---\ntitle: Handbuch\n---\n# Kapitel\n";
+        let body = TypstRenderer::render(md, &DocumentConfig::default());
+        assert!(!body.contains("title:"));
+        assert!(body.contains("Kapitel"));
+    }
+
+    #[test]
+    fn test_book_chapter_links_become_internal() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("guide")).unwrap();
+        std::fs::write(tmp.path().join("guide/img.png"), b"\x89PNG").unwrap();
+        let book = Book {
+            root: tmp.path().to_path_buf(),
+            title: None,
+            chapters: vec!["intro.md".to_string(), "guide/setup.md".to_string()],
+        };
+        let md = "## Ziel\n\n[Intro](../intro.md) [Ziel](#ziel) [Extern](https://x.org/a.md) ![Bild](img.png)";
+        let r = TypstRenderer::run_chapter(
+            md,
+            &DocumentConfig::default(),
+            &book.root,
+            Some(book.chapter_context(1)),
+        );
+        let out = r.out;
+        assert!(out.contains("#label(\"ch2-ziel\")"));
+        assert!(out.contains("#link(label(\"ch1\"))[Intro]"));
+        assert!(out.contains("#link(label(\"ch2-ziel\"))[Ziel]"));
+        assert!(out.contains("#link(\"https://x.org/a.md\")"));
+        // Image relative to the chapter folder, embedded as a virtual asset
+        assert_eq!(r.assets.len(), 1);
+        assert!(out.contains("image(\"/.workbench/image-"));
+    }
+
+    #[test]
+    fn test_local_image_embedded_with_caption() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("diagram.svg"), "<svg/>").unwrap();
+        let md = "![Ablauf](diagram.svg \"Abb. Ablauf\")\n\n![Logo](logo.png)";
+        let r = TypstRenderer::run(md, &DocumentConfig::default(), tmp.path());
+
+        let (vpath, data) = r.assets.iter().next().unwrap();
+        assert_eq!(&data[..], b"<svg/>");
+        let vpath = vpath.to_string_lossy();
+        assert!(r.out.contains(&format!(
+            "#figure(layout(size => {{ let img = image(\"{}\");",
+            vpath
+        )));
+        assert!(r.out.contains("caption: [Abb. Ablauf])"));
+        // Alt text is not rendered as body text
+        assert_eq!(r.out.matches("Ablauf").count(), 1);
+        // Missing image: placeholder instead of a compile error
+        assert!(r.out.contains("Image not found: logo.png (Logo)"));
+        assert!(!r.out.contains("image(\"logo.png\")"));
+    }
+
+    #[test]
+    fn test_image_outside_root_is_not_embedded() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("docs")).unwrap();
+        std::fs::write(tmp.path().join("secret.png"), b"x").unwrap();
+        let r = TypstRenderer::run(
+            "![x](../secret.png)",
+            &DocumentConfig::default(),
+            &tmp.path().join("docs"),
+        );
+        assert!(r.assets.is_empty());
+        assert!(r.out.contains("Image not found"));
+    }
+
+    #[test]
+    fn test_percent_encoded_image_path_is_decoded() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("my image.png"), b"png").unwrap();
+        let r = TypstRenderer::run(
+            "![x](my%20image.png)",
+            &DocumentConfig::default(),
+            tmp.path(),
+        );
+        assert_eq!(r.assets.len(), 1);
+        assert!(r.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_absolute_image_paths_are_filesystem_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = tmp.path().join("docs");
+        std::fs::create_dir_all(&docs).unwrap();
+        std::fs::write(docs.join("inside.png"), b"x").unwrap();
+        std::fs::write(tmp.path().join("outside.png"), b"x").unwrap();
+
+        // Inside the document folder: embedded like a relative path
+        let md = format!("![x]({})", docs.join("inside.png").display());
+        let r = TypstRenderer::run(&md, &DocumentConfig::default(), &docs);
+        assert_eq!(r.assets.len(), 1);
+
+        // Outside: placeholder plus a warning naming the reason
+        let outside = tmp.path().join("outside.png");
+        let md = format!("![x]({})", outside.display());
+        let r = TypstRenderer::run(&md, &DocumentConfig::default(), &docs);
+        assert!(r.assets.is_empty());
+        assert!(r.out.contains("Image not found"));
+        assert_eq!(
+            r.warnings,
+            vec![format!(
+                "Image skipped: {}: outside the document folder",
+                outside.display()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_invalid_template_name_is_error() {
+        let doc = DocumentConfig::default();
+        let mut options = template_options();
+        options.template = Some("../evil".to_string());
+        assert!(build_typst_document("x", &options, &doc, None).is_err());
+    }
+
+    #[test]
+    fn test_us_page_sizes_use_typst_paper_names() {
+        let mut doc = DocumentConfig::default();
+        let options = ExportOptions {
+            title: "T".to_string(),
+            author: "A".to_string(),
+            date: "01.01.2026".to_string(),
+            format: crate::browser::pdf_export::ExportFormat::Pdf,
+            template: None,
+            meta: Default::default(),
+            profile: None,
+        };
+        doc.pdf.page_size = "Letter".to_string();
+        let result = build_typst_document("x", &options, &doc, None).unwrap();
+        assert!(result.contains("paper: \"us-letter\""));
+        doc.pdf.page_size = "legal".to_string();
+        let result = build_typst_document("x", &options, &doc, None).unwrap();
+        assert!(result.contains("paper: \"us-legal\""));
+    }
+
     #[test]
     fn test_build_typst_document_per_side_margins() {
         // resolved_margins() feeds all four sides; default config → uniform 2.5cm.
@@ -1023,8 +1808,11 @@ mod tests {
             author: "A".to_string(),
             date: "01.01.2026".to_string(),
             format: crate::browser::pdf_export::ExportFormat::Pdf,
+            template: None,
+            meta: Default::default(),
+            profile: None,
         };
-        let result = build_typst_document("x", &options, &doc);
+        let result = build_typst_document("x", &options, &doc, None).unwrap();
         assert!(result.contains("left: 2.5cm"));
         assert!(result.contains("right: 2.5cm"));
         assert!(result.contains("top: 2.5cm"));
@@ -1033,6 +1821,34 @@ mod tests {
         assert!(!result.contains("{margin"));
     }
 
+    #[test]
+    fn test_resolve_fonts_against_book() {
+        let mut book = FontBook::new();
+        for data in [CARLITO_REGULAR, DEJAVU_SANS_REGULAR] {
+            for font in Font::iter(Bytes::new(data.to_vec())) {
+                book.push(font.info().clone());
+            }
+        }
+        let mut doc = DocumentConfig::default();
+        doc.fonts.body = "'Not Installed', 'DejaVu Sans', sans-serif".to_string();
+        doc.fonts.code = "'SF Mono', monospace".to_string();
+
+        let (resolved, report) = resolve_fonts(&doc, &book);
+        assert_eq!(resolved.fonts.body, "DejaVu Sans");
+        assert_eq!(report.body_requested.as_deref(), Some("Not Installed"));
+        assert_eq!(report.body, "DejaVu Sans");
+        // No configured code font installed: the appended fallbacks count
+        assert_eq!(report.code.as_deref(), Some("DejaVu Sans"));
+
+        doc.fonts.body = "Calibri".to_string();
+        let (resolved, report) = resolve_fonts(&doc, &book);
+        assert_eq!(resolved.fonts.body, BUNDLED_BODY_FONT);
+        assert_eq!(
+            report.summary(),
+            "Font: Carlito (instead of Calibri) · Code: DejaVu Sans"
+        );
+    }
+
     /// Verify that every character in the required Unicode symbol set is covered
     /// by at least one of the 5 bundled font byte slices.
     ///
@@ -1090,11 +1906,17 @@ mod tests {
             author: "test".to_string(),
             date: "11.06.2026".to_string(),
             format: ExportFormat::Pdf,
+            template: None,
+            meta: Default::default(),
+            profile: None,
         };
         let doc = crate::config::DocumentConfig::default();
 
-        export_markdown_to_pdf(Path::new(&src), Path::new(&pdf_path), &options, &doc)
+        let output = export_markdown_to_pdf(Path::new(&src), Path::new(&pdf_path), &options, &doc)
             .expect("PDF export must succeed for integration test");
+        // Carlito has no ☐ — the report must name the font it fell back to
+        let report = output.fonts.expect("PDF export reports fonts");
+        assert!(report.fallbacks.values().any(|chars| chars.contains(&'☐')));
 
         let pdf_bytes = std::fs::read(&pdf_path).expect("PDF file must exist after export");
 
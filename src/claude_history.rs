@@ -0,0 +1,369 @@
+//! Claude CLI conversation history.
+//!
+//! Claude stores one JSONL transcript per session under
+//! `~/.claude/projects/<encoded project path>/<session id>.jsonl`. This module
+//! lists the sessions of a project and parses a transcript into readable turns
+//! (prompts, answers, tool calls with their diffs, tool results).
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Maximum length of the first-prompt preview in the session list
+const PREVIEW_CHARS: usize = 120;
+/// Tool results longer than this are shortened in the transcript view
+const MAX_RESULT_LINES: usize = 20;
+
+/// One past session of the current project.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    /// Session id (file stem), passed to `claude --resume`
+    pub id: String,
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    /// First real user prompt (slash commands and meta messages skipped)
+    pub first_prompt: String,
+}
+
+/// A readable piece of a transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Turn {
+    User(String),
+    Assistant(String),
+    /// Tool invocation: name, one-line summary (command, file, pattern) and
+    /// for edits a unified-style diff (`-`/`+` lines)
+    ToolCall {
+        name: String,
+        summary: String,
+        diff: Vec<String>,
+    },
+    ToolResult {
+        text: String,
+        is_error: bool,
+    },
+}
+
+impl Turn {
+    /// Plain text used for full-text search
+    pub fn text(&self) -> String {
+        match self {
+            Turn::User(t) | Turn::Assistant(t) => t.clone(),
+            Turn::ToolCall {
+                name,
+                summary,
+                diff,
+            } => format!("{} {}\n{}", name, summary, diff.join("\n")),
+            Turn::ToolResult { text, .. } => text.clone(),
+        }
+    }
+}
+
+/// Root of the Claude transcripts (`$CLAUDE_CONFIG_DIR/projects` or `~/.claude/projects`)
+pub fn projects_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("CLAUDE_CONFIG_DIR") {
+        return Some(PathBuf::from(dir).join("projects"));
+    }
+    dirs::home_dir().map(|home| home.join(".claude").join("projects"))
+}
+
+/// Directory name Claude uses for a project: every character that is not
+/// ASCII alphanumeric becomes `-` (`/home/me/my.app` → `-home-me-my-app`).
+pub fn encode_project_path(project: &Path) -> String {
+    project
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Sessions of `project`, newest first. Claude names the transcript
+/// directory after the resolved path, so symlinks are resolved first.
+pub fn list_sessions(project: &Path) -> Vec<SessionSummary> {
+    let Some(root) = projects_dir() else {
+        return Vec::new();
+    };
+    let project = project
+        .canonicalize()
+        .unwrap_or_else(|_| project.to_path_buf());
+    list_sessions_in(&root.join(encode_project_path(&project)))
+}
+
+/// Sessions stored in a project transcript directory, newest first.
+pub fn list_sessions_in(dir: &Path) -> Vec<SessionSummary> {
+    let mut sessions: Vec<SessionSummary> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|x| x == "jsonl"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_str()?.to_string();
+            let first_prompt = read_first_prompt(&path)?;
+            Some(SessionSummary {
+                id,
+                modified: std::fs::metadata(&path).and_then(|m| m.modified()).ok(),
+                path,
+                first_prompt,
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.modified));
+    sessions
+}
+
+/// First user prompt of a transcript file, read only up to that prompt
+/// (transcripts grow to many megabytes).
+fn read_first_prompt(path: &Path) -> Option<String> {
+    let reader = BufReader::new(std::fs::File::open(path).ok()?);
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .find_map(|line| first_prompt(&line))
+}
+
+/// First user prompt of a transcript (`None` for sessions without one,
+/// e.g. aborted starts).
+fn first_prompt(content: &str) -> Option<String> {
+    parse_transcript(content)
+        .into_iter()
+        .find_map(|turn| match turn {
+            Turn::User(text) => Some(text),
+            _ => None,
+        })
+        .map(|text| {
+            let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            match line.char_indices().nth(PREVIEW_CHARS) {
+                Some((i, _)) => format!("{}…", &line[..i]),
+                None => line,
+            }
+        })
+}
+
+/// Read and parse a transcript file.
+pub fn load_transcript(path: &Path) -> Result<Vec<Turn>> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    Ok(parse_transcript(&content))
+}
+
+/// Parse JSONL transcript content. Unknown record types, meta messages,
+/// sidechains (subagents) and thinking blocks are skipped; malformed lines
+/// are ignored so a partially written session still shows.
+pub fn parse_transcript(content: &str) -> Vec<Turn> {
+    let mut turns = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if record["isMeta"].as_bool() == Some(true) || record["isSidechain"].as_bool() == Some(true)
+        {
+            continue;
+        }
+        let is_user = match record["type"].as_str() {
+            Some("user") => true,
+            Some("assistant") => false,
+            _ => continue,
+        };
+        match &record["message"]["content"] {
+            Value::String(text) => push_text(&mut turns, is_user, text),
+            Value::Array(blocks) => {
+                for block in blocks {
+                    push_block(&mut turns, is_user, block);
+                }
+            }
+            _ => {}
+        }
+    }
+    turns
+}
+
+fn push_text(turns: &mut Vec<Turn>, is_user: bool, text: &str) {
+    let text = text.trim();
+    // Slash commands and hook output are wrapped in pseudo tags
+    if text.is_empty() || (is_user && text.starts_with('<')) {
+        return;
+    }
+    turns.push(if is_user {
+        Turn::User(text.to_string())
+    } else {
+        Turn::Assistant(text.to_string())
+    });
+}
+
+fn push_block(turns: &mut Vec<Turn>, is_user: bool, block: &Value) {
+    match block["type"].as_str() {
+        Some("text") => push_text(turns, is_user, block["text"].as_str().unwrap_or("")),
+        Some("tool_use") => {
+            let name = block["name"].as_str().unwrap_or("tool").to_string();
+            let (summary, diff) = describe_tool_call(&name, &block["input"]);
+            turns.push(Turn::ToolCall {
+                name,
+                summary,
+                diff,
+            });
+        }
+        Some("tool_result") => {
+            let text = match &block["content"] {
+                Value::String(s) => s.clone(),
+                Value::Array(parts) => parts
+                    .iter()
+                    .filter_map(|p| p["text"].as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+            turns.push(Turn::ToolResult {
+                text: shorten_lines(text.trim_end(), MAX_RESULT_LINES),
+                is_error: block["is_error"].as_bool().unwrap_or(false),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// One-line summary and diff lines for a tool call.
+fn describe_tool_call(name: &str, input: &Value) -> (String, Vec<String>) {
+    let field = |key: &str| input[key].as_str().unwrap_or("").to_string();
+    match name {
+        "Bash" => (field("command"), Vec::new()),
+        "Edit" => (
+            field("file_path"),
+            diff_lines(&field("old_string"), &field("new_string")),
+        ),
+        "MultiEdit" => {
+            let diff = input["edits"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|edit| {
+                    diff_lines(
+                        edit["old_string"].as_str().unwrap_or(""),
+                        edit["new_string"].as_str().unwrap_or(""),
+                    )
+                })
+                .collect();
+            (field("file_path"), diff)
+        }
+        "Write" => (field("file_path"), diff_lines("", &field("content"))),
+        "Read" | "NotebookEdit" => (field("file_path"), Vec::new()),
+        "Grep" | "Glob" => (field("pattern"), Vec::new()),
+        "WebFetch" => (field("url"), Vec::new()),
+        "WebSearch" => (field("query"), Vec::new()),
+        "Task" | "Agent" => (field("description"), Vec::new()),
+        _ => {
+            let compact = input.to_string();
+            (shorten_chars(&compact, PREVIEW_CHARS), Vec::new())
+        }
+    }
+}
+
+/// Replacement shown as removed/added lines.
+fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    old.lines()
+        .map(|l| format!("-{}", l))
+        .chain(new.lines().map(|l| format!("+{}", l)))
+        .collect()
+}
+
+fn shorten_lines(text: &str, max: usize) -> String {
+    let count = text.lines().count();
+    if count <= max {
+        return text.to_string();
+    }
+    let mut kept: Vec<&str> = text.lines().take(max).collect();
+    let more = format!("… ({} more lines)", count - max);
+    kept.push(&more);
+    kept.join("\n")
+}
+
+fn shorten_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((i, _)) => format!("{}…", &text[..i]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"summary","summary":"Fix parser"}
+{"type":"user","isMeta":true,"message":{"role":"user","content":"Caveat: local commands"}}
+{"type":"user","message":{"role":"user","content":"<command-name>/clear</command-name>"}}
+{"type":"user","message":{"role":"user","content":"Fix the   parser\nplease"}}
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"hmm"},{"type":"text","text":"Looking at it."},{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"src/parser.rs","old_string":"a + b","new_string":"a - b"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"File updated","is_error":false}]}}
+{"type":"assistant","isSidechain":true,"message":{"role":"assistant","content":"subagent noise"}}
+not json
+{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+{"type":"user","message":{"role":"user","content":[{"type":"tool_result","content":[{"type":"text","text":"1 failed"}],"is_error":true}]}}
+"#;
+
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(
+            encode_project_path(Path::new("/home/me/my.app")),
+            "-home-me-my-app"
+        );
+        assert_eq!(encode_project_path(Path::new("/root/crate")), "-root-crate");
+    }
+
+    #[test]
+    fn test_parse_transcript_turns() {
+        let turns = parse_transcript(TRANSCRIPT);
+        assert_eq!(
+            turns,
+            vec![
+                Turn::User("Fix the   parser\nplease".to_string()),
+                Turn::Assistant("Looking at it.".to_string()),
+                Turn::ToolCall {
+                    name: "Edit".to_string(),
+                    summary: "src/parser.rs".to_string(),
+                    diff: vec!["-a + b".to_string(), "+a - b".to_string()],
+                },
+                Turn::ToolResult {
+                    text: "File updated".to_string(),
+                    is_error: false,
+                },
+                Turn::ToolCall {
+                    name: "Bash".to_string(),
+                    summary: "cargo test".to_string(),
+                    diff: Vec::new(),
+                },
+                Turn::ToolResult {
+                    text: "1 failed".to_string(),
+                    is_error: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_sessions_uses_first_prompt() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("abc-123.jsonl"), TRANSCRIPT).unwrap();
+        // No user prompt → not listed
+        std::fs::write(
+            tmp.path().join("empty.jsonl"),
+            "{\"type\":\"summary\",\"summary\":\"x\"}\n",
+        )
+        .unwrap();
+        let sessions = list_sessions_in(tmp.path());
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "abc-123");
+        assert_eq!(sessions[0].first_prompt, "Fix the parser please");
+    }
+
+    #[test]
+    fn test_shorten_long_tool_result() {
+        let text = (1..=30)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let short = shorten_lines(&text, MAX_RESULT_LINES);
+        assert_eq!(short.lines().count(), MAX_RESULT_LINES + 1);
+        assert!(short.ends_with("… (10 more lines)"));
+    }
+}
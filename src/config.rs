@@ -1,9 +1,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
-use crate::types::{ClaudeEffort, ClaudeModel, ClaudePermissionMode};
+use crate::types::{
+    ClaudeEffort, ClaudeModel, ClaudePermissionMode, CopyTarget, DesktopNotification, PaneId,
+    RestartPolicy,
+};
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
@@ -34,12 +38,19 @@ pub struct PtyConfig {
     pub claude_command: Vec<String>,
     pub lazygit_command: Vec<String>,
     pub scrollback_lines: usize,
-    /// Auto-restart PTY processes when they exit (default: true)
-    #[serde(default = "default_true")]
-    pub auto_restart: bool,
+    /// Per-pane restart policies with backoff and crash-loop limit
+    #[serde(default)]
+    pub restart: RestartConfig,
+    /// Legacy switch (replaced by `restart`); `false` is migrated to
+    /// `restart: never` for all panes on load
+    #[serde(default, skip_serializing)]
+    pub auto_restart: Option<bool>,
     /// Number of lines to copy when pressing F9 in terminal panes (default: 50)
     #[serde(default = "default_copy_lines_count")]
     pub copy_lines_count: usize,
+    /// Where `y` in copy mode sends the text: clipboard (default) or claude
+    #[serde(default)]
+    pub copy_mode_target: CopyTarget,
     /// Leader key for the User-Terminal-pane passthrough (tmux-style). Format
     /// `"ctrl+<letter>"` (default `"ctrl+b"`). When passthrough is active, all
     /// keys go straight to the PTY (so nano/mc/vim work) and Workbench commands
@@ -47,12 +58,98 @@ pub struct PtyConfig {
     /// passthrough and restores the legacy behavior (F-keys = Workbench).
     #[serde(default = "default_terminal_prefix")]
     pub terminal_prefix: String,
+    /// asciicast recording of the terminal panes (Shift+F5)
+    #[serde(default)]
+    pub recording: RecordingConfig,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Restart behaviour of the terminal panes when their process exits
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RestartConfig {
+    #[serde(default)]
+    pub claude: RestartPolicy,
+    #[serde(default)]
+    pub lazygit: RestartPolicy,
+    #[serde(default)]
+    pub terminal: RestartPolicy,
+    /// Delay before restarting; doubles with every restart inside the window
+    #[serde(default = "default_restart_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Stop restarting after this many restarts within `window_secs` (0 = no limit)
+    #[serde(default = "default_restart_max")]
+    pub max_restarts: u32,
+    /// Sliding window for the crash-loop limit and the backoff
+    #[serde(default = "default_restart_window_secs")]
+    pub window_secs: u64,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            claude: RestartPolicy::Always,
+            lazygit: RestartPolicy::Always,
+            terminal: RestartPolicy::Always,
+            backoff_ms: default_restart_backoff_ms(),
+            max_restarts: default_restart_max(),
+            window_secs: default_restart_window_secs(),
+        }
+    }
+}
+
+impl RestartConfig {
+    pub fn policy(&self, pane: PaneId) -> RestartPolicy {
+        match pane {
+            PaneId::Claude => self.claude,
+            PaneId::LazyGit => self.lazygit,
+            PaneId::Terminal => self.terminal,
+            _ => RestartPolicy::Never,
+        }
+    }
+}
+
+/// Where and what to record with Shift+F5
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct RecordingConfig {
+    /// Directory for `.cast` files (empty = `recordings/` in the data directory,
+    /// e.g. `~/.local/share/claude-workbench/recordings`)
+    #[serde(default)]
+    pub dir: String,
+    /// Also record keys sent to the pane (off: they may contain passwords)
+    #[serde(default)]
+    pub record_input: bool,
+}
+
+impl RecordingConfig {
+    /// Recording directory with `~` expanded; relative paths are resolved
+    /// against `root`, the empty default lies in [`data_dir`]
+    pub fn dir_path(&self, root: &Path) -> std::path::PathBuf {
+        let dir = self.dir.trim();
+        if dir.is_empty() {
+            return data_dir().map_or_else(|| root.join("recordings"), |d| d.join("recordings"));
+        }
+        match dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map_or_else(|| root.join(rest), |h| h.join(rest)),
+            None => root.join(dir),
+        }
+    }
+}
+
+fn default_restart_backoff_ms() -> u64 {
+    500
+}
+
+fn default_restart_max() -> u32 {
+    5
+}
+
+fn default_restart_window_secs() -> u64 {
+    60
+}
+
 fn default_copy_lines_count() -> usize {
     50
 }
@@ -125,9 +222,12 @@ impl Default for PtyConfig {
             claude_command: vec![],
             lazygit_command: vec!["lazygit".to_string()],
             scrollback_lines: 1000,
-            auto_restart: true,
+            restart: RestartConfig::default(),
+            auto_restart: None,
             copy_lines_count: 50,
+            copy_mode_target: CopyTarget::Clipboard,
             terminal_prefix: default_terminal_prefix(),
+            recording: RecordingConfig::default(),
         }
     }
 }
@@ -148,6 +248,18 @@ pub struct ClaudePrefix {
     pub description: String,
 }
 
+/// Prompt library entry (Shift+F4).
+///
+/// `template` may contain the placeholders `{file}`, `{selection}`,
+/// `{git_diff}` and `{branch}`, filled from the current workbench state.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PromptTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub template: String,
+}
+
 /// Claude-specific configuration
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ClaudeConfig {
@@ -175,6 +287,15 @@ pub struct ClaudeConfig {
     /// Default worktree name (--worktree flag, empty = not passed)
     #[serde(default)]
     pub default_worktree: String,
+    /// Prompt library shown in the prompt picker (Shift+F4)
+    #[serde(default = "default_prompts")]
+    pub prompts: Vec<PromptTemplate>,
+    /// Ask before sending more than this many KB into Claude (0 = never ask)
+    #[serde(default = "default_context_warn_kb")]
+    pub context_warn_kb: usize,
+    /// Notifications when Claude finishes or needs input
+    #[serde(default)]
+    pub notifications: ClaudeNotifyConfig,
 }
 
 impl Default for ClaudeConfig {
@@ -188,14 +309,110 @@ impl Default for ClaudeConfig {
             default_effort: ClaudeEffort::Unset,
             default_session_name: String::new(),
             default_worktree: String::new(),
+            prompts: default_prompts(),
+            context_warn_kb: default_context_warn_kb(),
+            notifications: ClaudeNotifyConfig::default(),
+        }
+    }
+}
+
+/// When and how to notify about the Claude pane: per-event toggles
+/// (`on_*`) and per-channel toggles
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ClaudeNotifyConfig {
+    /// Output went idle after a busy stretch
+    #[serde(default = "default_true")]
+    pub on_finish: bool,
+    /// The Claude CLI rang the bell (BEL)
+    #[serde(default = "default_true")]
+    pub on_bell: bool,
+    /// A permission prompt appeared
+    #[serde(default = "default_true")]
+    pub on_permission: bool,
+    /// Seconds without output after which Claude counts as finished
+    #[serde(default = "default_notify_idle_secs")]
+    pub idle_secs: u64,
+    /// Only output that kept coming this long after the last input counts
+    /// as a task (keeps echoed typing from notifying)
+    #[serde(default = "default_notify_min_busy_secs")]
+    pub min_busy_secs: u64,
+    /// Ring the host terminal's bell
+    #[serde(default = "default_true")]
+    pub bell: bool,
+    /// Desktop notification through the host terminal: osc9 | osc777 | off
+    #[serde(default)]
+    pub desktop: DesktopNotification,
+    /// Flash the message in the footer
+    #[serde(default = "default_true")]
+    pub flash: bool,
+    /// Badge in the Claude pane title until the pane is focused
+    #[serde(default = "default_true")]
+    pub badge: bool,
+}
+
+impl Default for ClaudeNotifyConfig {
+    fn default() -> Self {
+        Self {
+            on_finish: true,
+            on_bell: true,
+            on_permission: true,
+            idle_secs: default_notify_idle_secs(),
+            min_busy_secs: default_notify_min_busy_secs(),
+            bell: true,
+            desktop: DesktopNotification::default(),
+            flash: true,
+            badge: true,
         }
     }
 }
 
+fn default_notify_idle_secs() -> u64 {
+    3
+}
+
+fn default_notify_min_busy_secs() -> u64 {
+    10
+}
+
 fn default_show_permission_dialog() -> bool {
     true
 }
 
+fn default_context_warn_kb() -> usize {
+    64
+}
+
+fn default_prompts() -> Vec<PromptTemplate> {
+    let prompt = |name: &str, description: &str, template: &str| PromptTemplate {
+        name: name.to_string(),
+        description: description.to_string(),
+        template: template.to_string(),
+    };
+    vec![
+        prompt(
+            "Explain file",
+            "Explain the file open in the preview",
+            "Explain what @{file} does and how it fits into the project.",
+        ),
+        prompt(
+            "Review changes",
+            "Review the uncommitted diff",
+            "Review these uncommitted changes on branch {branch}. \
+             Point out bugs, missing tests and unclear code.\n\n```diff\n{git_diff}\n```",
+        ),
+        prompt(
+            "Explain selection",
+            "Explain the selected lines",
+            "Explain these lines:\n\n```\n{selection}\n```",
+        ),
+        prompt(
+            "Fix error",
+            "Fix the error in the selected output",
+            "Fix the cause of this error:\n\n```\n{selection}\n```",
+        ),
+    ]
+}
+
 /// SSH-specific configuration.
 ///
 /// Activates when `clipboard::is_ssh_session()` reports true. Image paste in
@@ -233,6 +450,10 @@ impl Default for SshConfig {
 pub struct TerminalConfig {
     pub shell_path: String,
     pub shell_args: Vec<String>,
+    /// Inject OSC 133 / OSC 7 hooks into bash, zsh and fish (only while
+    /// `shell_args` is empty, custom arguments are left untouched)
+    #[serde(default = "default_true")]
+    pub shell_integration: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -257,6 +478,10 @@ pub struct UiConfig {
     /// Default export directory for Markdown/PDF exports (empty = ~/Downloads)
     #[serde(default)]
     pub export_dir: String,
+    /// Serve browser previews from a localhost server that reloads the page
+    /// when the file changes (false = one-off temp HTML files)
+    #[serde(default = "default_true")]
+    pub live_preview: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -284,6 +509,9 @@ pub struct FileBrowserConfig {
     pub show_file_info: bool,
     pub date_format: String,
     pub auto_refresh_ms: u64, // 0 = disabled
+    /// Follow the Terminal pane's working directory (reported via OSC 7)
+    #[serde(default)]
+    pub follow_terminal_cwd: bool,
 }
 
 impl Default for FileBrowserConfig {
@@ -293,6 +521,7 @@ impl Default for FileBrowserConfig {
             show_file_info: true,
             date_format: "%d.%m.%Y %H:%M:%S".to_string(),
             auto_refresh_ms: 2000, // 2 seconds
+            follow_terminal_cwd: false,
         }
     }
 }
@@ -303,6 +532,7 @@ impl Default for Config {
             terminal: TerminalConfig {
                 shell_path: default_shell_path(),
                 shell_args: vec![],
+                shell_integration: true,
             },
             ui: UiConfig {
                 theme: "default".into(),
@@ -314,6 +544,7 @@ impl Default for Config {
                 browser: String::new(),
                 external_editor: String::new(),
                 export_dir: String::new(),
+                live_preview: true,
             },
             layout: LayoutConfig::default(),
             file_browser: FileBrowserConfig::default(),
@@ -668,6 +899,37 @@ impl PdfPageConfig {
     }
 }
 
+/// True for the page sizes the exporters know: A0–A10, Letter, Legal and
+/// the Typst names `us-letter`, `us-legal`, `us-executive`, `us-tabloid`
+pub fn is_known_page_size(name: &str) -> bool {
+    let name = name.trim().to_ascii_lowercase();
+    if matches!(
+        name.as_str(),
+        "letter" | "legal" | "us-letter" | "us-legal" | "us-executive" | "us-tabloid"
+    ) {
+        return true;
+    }
+    name.strip_prefix('a').is_some_and(|n| {
+        !n.is_empty()
+            && n.chars().all(|c| c.is_ascii_digit())
+            && n.parse::<u8>().is_ok_and(|n| n <= 10)
+    })
+}
+
+/// True for a page length like `2cm`, `12.5mm`, `1in` or `72pt`
+pub fn is_valid_length(value: &str) -> bool {
+    let value = value.trim();
+    let Some(number) = ["mm", "cm", "in", "pt"]
+        .iter()
+        .find_map(|unit| value.strip_suffix(unit))
+    else {
+        return false;
+    };
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let mut parts = number.splitn(2, '.');
+    parts.next().is_some_and(digits) && parts.next().is_none_or(digits)
+}
+
 impl Default for PdfPageConfig {
     fn default() -> Self {
         Self {
@@ -681,6 +943,170 @@ impl Default for PdfPageConfig {
     }
 }
 
+/// Diagram rendering for fenced ```mermaid / ```plantuml blocks in exports.
+///
+/// Each command is an argv list. `{input}` is replaced by a temp file holding
+/// the diagram source and `{output}` by the SVG path the renderer writes to;
+/// without `{input}` the source is piped to stdin, without `{output}` the SVG
+/// is read from stdout. If the renderer is missing or fails, the block is
+/// exported as a plain code block.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DiagramConfig {
+    /// Render diagram blocks at all (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Mermaid renderer (mermaid-cli)
+    #[serde(default = "default_mermaid_command")]
+    pub mermaid_command: Vec<String>,
+    /// PlantUML renderer, e.g. `["java", "-jar", "/opt/plantuml.jar", "-tsvg", "-pipe"]`
+    #[serde(default = "default_plantuml_command")]
+    pub plantuml_command: Vec<String>,
+    /// Kill the renderer after this many seconds
+    #[serde(default = "default_diagram_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Cache rendered SVGs in ~/.cache/claude-workbench/diagrams (default: true)
+    #[serde(default = "default_true")]
+    pub cache: bool,
+}
+
+fn default_mermaid_command() -> Vec<String> {
+    [
+        "mmdc",
+        "-i",
+        "{input}",
+        "-o",
+        "{output}",
+        "-b",
+        "transparent",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+fn default_plantuml_command() -> Vec<String> {
+    ["plantuml", "-tsvg", "-pipe"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+fn default_diagram_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for DiagramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mermaid_command: default_mermaid_command(),
+            plantuml_command: default_plantuml_command(),
+            timeout_secs: default_diagram_timeout_secs(),
+            cache: true,
+        }
+    }
+}
+
+/// Export template settings (PDF layout, localization)
+///
+/// Custom Typst templates are `.typ` files in `~/.config/claude-workbench/templates/`
+/// and are picked per export in the export chooser. They use the same
+/// `{placeholder}` substitutions as the built-in template.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ExportTemplateConfig {
+    /// Template preselected in the export chooser (file stem; empty = built-in)
+    #[serde(default)]
+    pub default: String,
+    /// Prepend a cover page with logo, title, version, author and date
+    #[serde(default)]
+    pub cover_page: bool,
+    /// Insert an auto-generated table of contents
+    #[serde(default)]
+    pub toc: bool,
+    /// Logo image for the cover page (PNG, JPG, SVG; `~` is expanded)
+    #[serde(default)]
+    pub logo: String,
+    /// Document version shown on the cover page (optional)
+    #[serde(default)]
+    pub version: String,
+    /// Document language (hyphenation, localized labels), e.g. "de", "en"
+    #[serde(default = "default_template_language")]
+    pub language: String,
+    /// Page number text with `{page}` / `{total}`; empty = localized default
+    #[serde(default)]
+    pub page_label: String,
+}
+
+fn default_template_language() -> String {
+    "de".to_string()
+}
+
+impl Default for ExportTemplateConfig {
+    fn default() -> Self {
+        Self {
+            default: String::new(),
+            cover_page: false,
+            toc: false,
+            logo: String::new(),
+            version: String::new(),
+            language: default_template_language(),
+            page_label: String::new(),
+        }
+    }
+}
+
+impl ExportTemplateConfig {
+    /// Normalized language code: lowercase ASCII letters/hyphen, "de" if invalid
+    pub fn resolved_language(&self) -> String {
+        let lang = self.language.trim().to_ascii_lowercase();
+        if !lang.is_empty() && lang.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+            lang
+        } else {
+            default_template_language()
+        }
+    }
+
+    /// Page number text — configured `page_label` or the language default
+    pub fn resolved_page_label(&self) -> String {
+        if !self.page_label.trim().is_empty() {
+            return self.page_label.clone();
+        }
+        let lang = self.resolved_language();
+        match lang.split('-').next().unwrap_or("") {
+            "de" => "Seite {page} von {total}",
+            "fr" => "Page {page} sur {total}",
+            "es" => "Página {page} de {total}",
+            "it" => "Pagina {page} di {total}",
+            "nl" => "Pagina {page} van {total}",
+            _ => "Page {page} of {total}",
+        }
+        .to_string()
+    }
+
+    /// Localized table of contents heading
+    pub fn toc_title(&self) -> &'static str {
+        let lang = self.resolved_language();
+        match lang.split('-').next().unwrap_or("") {
+            "de" => "Inhaltsverzeichnis",
+            "fr" => "Table des matières",
+            "es" => "Índice",
+            "it" => "Indice",
+            "nl" => "Inhoudsopgave",
+            _ => "Contents",
+        }
+    }
+
+    /// Logo path with `~` expanded (None if unset)
+    pub fn logo_path(&self) -> Option<std::path::PathBuf> {
+        let logo = self.logo.trim();
+        if logo.is_empty() {
+            return None;
+        }
+        match logo.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|h| h.join(rest)),
+            None => Some(std::path::PathBuf::from(logo)),
+        }
+    }
+}
+
 /// Central document configuration for HTML and PDF exports
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct DocumentConfig {
@@ -694,9 +1120,101 @@ pub struct DocumentConfig {
     pub sizes: DocSizeConfig,
     #[serde(default)]
     pub pdf: PdfPageConfig,
+    #[serde(default)]
+    pub diagrams: DiagramConfig,
+    #[serde(default)]
+    pub template: ExportTemplateConfig,
+    /// Named branding profiles as written: partial `document` overrides
+    /// (company, fonts, colors, sizes, pdf, template/logo)
+    #[serde(
+        default,
+        rename = "profiles",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    profile_overrides: BTreeMap<String, serde_yaml_ng::Value>,
+    /// The profiles merged onto the settings above, see [`Self::resolve_profiles`]
+    #[serde(skip)]
+    pub profiles: BTreeMap<String, DocumentConfig>,
+    /// Automatic profile selection by path glob; the first match wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profile_rules: Vec<ProfileRule>,
+}
+
+/// Path glob → profile, e.g. `clients/acme/**` → `acme`.
+/// Relative patterns match at any directory level of the exported file.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ProfileRule {
+    pub pattern: String,
+    pub profile: String,
 }
 
 impl DocumentConfig {
+    /// Names of the configured branding profiles (sorted)
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    /// Profile selected by `profile_rules` for `path`, if any
+    pub fn profile_for_path(&self, path: &Path) -> Option<&str> {
+        let path = path.to_string_lossy().replace('\\', "/");
+        self.profile_rules
+            .iter()
+            .find(|rule| glob_matches_path(&rule.pattern, &path))
+            .map(|rule| rule.profile.as_str())
+    }
+
+    /// Merge every profile onto the base settings, done once at config load.
+    /// A broken profile is left out and described in the returned messages
+    /// instead of failing the whole config. Unset profile keys keep the base
+    /// values; nested sections are merged key by key.
+    pub fn resolve_profiles(&mut self) -> Vec<String> {
+        let base = match serde_yaml_ng::to_value(DocumentConfig {
+            profile_overrides: BTreeMap::new(),
+            profiles: BTreeMap::new(),
+            profile_rules: Vec::new(),
+            ..self.clone()
+        }) {
+            Ok(base) => base,
+            Err(e) => return vec![format!("Cannot resolve document profiles: {}", e)],
+        };
+        let mut profiles = BTreeMap::new();
+        let mut errors = Vec::new();
+        for (name, overrides) in &self.profile_overrides {
+            let mut merged = base.clone();
+            merge_yaml(&mut merged, overrides);
+            match serde_yaml_ng::from_value::<DocumentConfig>(merged) {
+                Ok(resolved) => {
+                    profiles.insert(name.clone(), resolved);
+                }
+                Err(e) => errors.push(format!("Invalid document profile {}: {}", name, e)),
+            }
+        }
+        self.profiles = profiles;
+        errors
+    }
+
+    /// Settings of profile `name`
+    pub fn with_profile(&self, name: &str) -> Result<DocumentConfig> {
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown document profile: {}", name))?;
+        Ok(DocumentConfig {
+            profiles: self.profiles.clone(),
+            profile_rules: self.profile_rules.clone(),
+            ..profile.clone()
+        })
+    }
+
+    /// Settings for exporting `path`: the explicit profile, else the first
+    /// matching rule, else the base settings.
+    pub fn resolve_profile(&self, explicit: Option<&str>, path: &Path) -> Result<DocumentConfig> {
+        match explicit.or_else(|| self.profile_for_path(path)) {
+            Some(name) => self.with_profile(name),
+            None => Ok(self.clone()),
+        }
+    }
+
     /// Resolve {company_name} placeholder in footer_text
     pub fn resolved_footer_text(&self) -> String {
         self.company
@@ -717,6 +1235,60 @@ impl DocumentConfig {
     }
 }
 
+/// Recursively merge `overrides` into `base` (mappings key by key, other values replaced).
+fn merge_yaml(base: &mut serde_yaml_ng::Value, overrides: &serde_yaml_ng::Value) {
+    use serde_yaml_ng::Value;
+    match (base, overrides) {
+        (Value::Mapping(base), Value::Mapping(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+/// Glob match for profile rules: `**` spans directories, `*` and `?` stay
+/// within one path segment. Relative patterns may match at any directory level.
+fn glob_matches_path(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim().trim_start_matches("./");
+    if pattern.starts_with('/') {
+        return glob_match(pattern.as_bytes(), path.as_bytes());
+    }
+    let path = path.trim_start_matches('/');
+    std::iter::once(0)
+        .chain(path.match_indices('/').map(|(i, _)| i + 1))
+        .any(|start| glob_match(pattern.as_bytes(), &path.as_bytes()[start..]))
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            // `**/` matches zero or more whole directories
+            glob_match(rest, text)
+                || text
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| **c == b'/')
+                    .any(|(i, _)| glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(rest, &text[1..])
+        }
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
 /// Get XDG-style config directory: ~/.config/claude-workbench/
 fn get_config_dir() -> Option<std::path::PathBuf> {
     // Use $XDG_CONFIG_HOME if set, otherwise ~/.config
@@ -728,13 +1300,28 @@ fn get_config_dir() -> Option<std::path::PathBuf> {
     dirs::home_dir().map(|home| home.join(".config").join("claude-workbench"))
 }
 
+/// Directory for user-provided Typst export templates (`*.typ`)
+pub fn export_templates_dir() -> Option<std::path::PathBuf> {
+    get_config_dir().map(|dir| dir.join("templates"))
+}
+
+/// Directory for files the workbench writes on request (recordings, saved
+/// scrollback): `~/.local/share/claude-workbench/` on Linux
+pub fn data_dir() -> Option<std::path::PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("claude-workbench"))
+}
+
+/// Directory for additional PDF export fonts (`*.ttf`, `*.otf`, `*.ttc`)
+pub fn export_fonts_dir() -> Option<std::path::PathBuf> {
+    get_config_dir().map(|dir| dir.join("fonts"))
+}
+
 pub fn load_config() -> Result<Config> {
     // 1. Check local config.yaml (project-specific override)
     let local_config = Path::new("config.yaml");
     if local_config.exists() {
         let contents = fs::read_to_string(local_config)?;
-        let config: Config = serde_yaml_ng::from_str(&contents)?;
-        return Ok(config);
+        return parse_config(&contents);
     }
 
     // 2. Check ~/.config/claude-workbench/config.yaml (XDG-style)
@@ -742,8 +1329,7 @@ pub fn load_config() -> Result<Config> {
         let config_path = config_dir.join("config.yaml");
         if config_path.exists() {
             let contents = fs::read_to_string(&config_path)?;
-            let config: Config = serde_yaml_ng::from_str(&contents)?;
-            return Ok(config);
+            return parse_config(&contents);
         }
     }
 
@@ -751,6 +1337,30 @@ pub fn load_config() -> Result<Config> {
     Ok(Config::default())
 }
 
+/// Load the configuration from an explicit file (`--config`).
+pub fn load_config_file(path: &Path) -> Result<Config> {
+    let contents = fs::read_to_string(path)?;
+    parse_config(&contents)
+}
+
+/// Parse a config file and migrate legacy settings.
+fn parse_config(contents: &str) -> Result<Config> {
+    let mut config: Config = serde_yaml_ng::from_str(contents)?;
+    // `pty.auto_restart: false` (pre restart policies) = never restart
+    if config.pty.auto_restart.take() == Some(false) {
+        let restart = &mut config.pty.restart;
+        restart.claude = RestartPolicy::Never;
+        restart.lazygit = RestartPolicy::Never;
+        restart.terminal = RestartPolicy::Never;
+    }
+    // A broken branding profile is skipped, the rest of the config still
+    // loads (printed before the TUI takes over the screen)
+    for error in config.document.resolve_profiles() {
+        eprintln!("Warning: {} (profile skipped)", error);
+    }
+    Ok(config)
+}
+
 /// Set restrictive file permissions (0600 - owner read/write only) on Unix systems
 #[cfg(unix)]
 fn set_restrictive_permissions(path: &Path) -> Result<()> {
@@ -862,4 +1472,183 @@ mod tests {
         c.terminal_prefix = "ctrl+1".to_string();
         assert_eq!(c.prefix_key(), None);
     }
+
+    #[test]
+    fn profile_rules_glob_matching() {
+        assert!(glob_matches_path(
+            "clients/acme/**",
+            "/home/u/docs/clients/acme/a.md"
+        ));
+        assert!(glob_matches_path(
+            "clients/acme/**",
+            "clients/acme/sub/b.md"
+        ));
+        assert!(!glob_matches_path(
+            "clients/acme/**",
+            "/home/u/clients/acme-old/a.md"
+        ));
+        assert!(glob_matches_path("**/*.md", "/x/y/z.md"));
+        assert!(glob_matches_path("reports/*.md", "/p/reports/q1.md"));
+        assert!(!glob_matches_path("reports/*.md", "/p/reports/2026/q1.md"));
+        assert!(glob_matches_path("/abs/**", "/abs/a/b.md"));
+        assert!(!glob_matches_path("/abs/**", "/other/abs/a.md"));
+    }
+
+    #[test]
+    fn profile_merges_onto_base() {
+        let mut doc: DocumentConfig = serde_yaml_ng::from_str(
+            r##"
+company:
+  name: "Musterfirma"
+profiles:
+  acme:
+    company:
+      name: "ACME Corp"
+    colors:
+      link: "#cc0000"
+    pdf:
+      page_size: "Letter"
+    template:
+      logo: "~/acme.png"
+profile_rules:
+  - pattern: "clients/acme/**"
+    profile: acme
+"##,
+        )
+        .unwrap();
+        assert!(doc.resolve_profiles().is_empty());
+        assert_eq!(doc.profile_names(), vec!["acme"]);
+        assert_eq!(
+            doc.profile_for_path(Path::new("/w/clients/acme/spec.md")),
+            Some("acme")
+        );
+        assert_eq!(doc.profile_for_path(Path::new("/w/notes.md")), None);
+
+        let acme = doc.with_profile("acme").unwrap();
+        assert_eq!(acme.company.name, "ACME Corp");
+        assert_eq!(acme.colors.link, "#cc0000");
+        assert_eq!(acme.pdf.page_size, "Letter");
+        assert_eq!(acme.template.logo, "~/acme.png");
+        // Untouched keys keep the base values
+        assert_eq!(acme.colors.code_bg, doc.colors.code_bg);
+        assert_eq!(acme.company.author, doc.company.author);
+
+        assert!(doc.with_profile("missing").is_err());
+        let plain = doc.resolve_profile(None, Path::new("/w/notes.md")).unwrap();
+        assert_eq!(plain.company.name, "Musterfirma");
+    }
+
+    #[test]
+    fn invalid_profile_is_skipped_at_load() {
+        let mut doc: serde_yaml_ng::Value = serde_yaml_ng::to_value(Config::default()).unwrap();
+        doc["document"]["profiles"] = serde_yaml_ng::from_str(
+            "acme:\n  pdf:\n    margin_top: [1]\nintern:\n  company:\n    name: Intern\n",
+        )
+        .unwrap();
+        let config = parse_config(&serde_yaml_ng::to_string(&doc).unwrap()).unwrap();
+        assert_eq!(config.document.profile_names(), vec!["intern"]);
+
+        let mut broken = config.document.clone();
+        let errors = broken.resolve_profiles();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Invalid document profile acme"));
+    }
+
+    #[test]
+    fn profiles_survive_saving_the_config() {
+        let mut doc: serde_yaml_ng::Value = serde_yaml_ng::to_value(Config::default()).unwrap();
+        doc["document"]["profiles"] =
+            serde_yaml_ng::from_str("acme:\n  company:\n    name: ACME Corp\n").unwrap();
+        let config = parse_config(&serde_yaml_ng::to_string(&doc).unwrap()).unwrap();
+
+        // What save_config writes
+        let saved = serde_yaml_ng::to_string(&config).unwrap();
+        let reloaded = parse_config(&saved).unwrap();
+        assert_eq!(reloaded.document.profile_names(), vec!["acme"]);
+        let acme = reloaded.document.with_profile("acme").unwrap();
+        assert_eq!(acme.company.name, "ACME Corp");
+        // Only the overrides are written, not the merged settings
+        assert_eq!(saved.matches("ACME Corp").count(), 1);
+    }
+
+    #[test]
+    fn template_page_label_follows_language() {
+        let mut t = ExportTemplateConfig::default();
+        assert_eq!(t.resolved_page_label(), "Seite {page} von {total}");
+        assert_eq!(t.toc_title(), "Inhaltsverzeichnis");
+        t.language = "en-US".to_string();
+        assert_eq!(t.resolved_page_label(), "Page {page} of {total}");
+        assert_eq!(t.toc_title(), "Contents");
+        t.page_label = "S. {page}".to_string();
+        assert_eq!(t.resolved_page_label(), "S. {page}");
+    }
+
+    #[test]
+    fn page_size_and_length_validation() {
+        assert!(is_known_page_size("A4"));
+        assert!(is_known_page_size("letter"));
+        assert!(is_known_page_size("us-legal"));
+        assert!(!is_known_page_size("a11"));
+        assert!(!is_known_page_size("a4\") #eval(\"x\")"));
+        assert!(is_valid_length("2cm"));
+        assert!(is_valid_length(" 12.5mm"));
+        assert!(is_valid_length("1in"));
+        assert!(!is_valid_length("2 cm"));
+        assert!(!is_valid_length(".5cm"));
+        assert!(!is_valid_length("2cm, top: 0pt"));
+        assert!(!is_valid_length("2"));
+    }
+
+    #[test]
+    fn template_language_rejects_invalid_values() {
+        let mut t = ExportTemplateConfig::default();
+        t.language = "de\"); #evil".to_string();
+        assert_eq!(t.resolved_language(), "de");
+    }
+
+    #[test]
+    fn claude_prompts_default_and_override() {
+        let cfg: ClaudeConfig = serde_yaml_ng::from_str("remote_control: false").unwrap();
+        assert!(!cfg.prompts.is_empty());
+
+        let cfg: ClaudeConfig = serde_yaml_ng::from_str(
+            "prompts:\n  - name: Tests\n    template: \"Write tests for @{file}\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.prompts,
+            vec![PromptTemplate {
+                name: "Tests".to_string(),
+                description: String::new(),
+                template: "Write tests for @{file}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn restart_policy_parse_and_legacy_migration() {
+        let restart: RestartConfig =
+            serde_yaml_ng::from_str("claude: on-failure\nmax_restarts: 0\n").unwrap();
+        assert_eq!(restart.claude, RestartPolicy::OnFailure);
+        assert_eq!(restart.terminal, RestartPolicy::Always);
+        assert_eq!(restart.max_restarts, 0);
+        assert_eq!(restart.backoff_ms, 500);
+
+        let mut doc: serde_yaml_ng::Value = serde_yaml_ng::to_value(Config::default()).unwrap();
+        doc["pty"]["auto_restart"] = serde_yaml_ng::Value::Bool(false);
+        let config = parse_config(&serde_yaml_ng::to_string(&doc).unwrap()).unwrap();
+        assert_eq!(config.pty.auto_restart, None);
+        assert_eq!(
+            config.pty.restart.policy(PaneId::Claude),
+            RestartPolicy::Never
+        );
+        assert_eq!(
+            config.pty.restart.policy(PaneId::LazyGit),
+            RestartPolicy::Never
+        );
+        assert_eq!(
+            config.pty.restart.policy(PaneId::Terminal),
+            RestartPolicy::Never
+        );
+    }
 }
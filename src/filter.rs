@@ -4,6 +4,7 @@
 //! detects syntax for code blocks.
 
 use regex::Regex;
+use std::collections::HashSet;
 use std::sync::LazyLock;
 
 /// Filter options for terminal output
@@ -19,6 +20,9 @@ pub struct FilterOptions {
     pub filter_dir_listings: bool,
     /// Detect programming language for syntax highlighting
     pub detect_syntax: bool,
+    /// Exact prompt lines (indices into the input) from shell integration
+    /// (OSC 133); replaces the prompt patterns when set
+    pub prompt_lines: Option<HashSet<usize>>,
 }
 
 impl Default for FilterOptions {
@@ -29,6 +33,7 @@ impl Default for FilterOptions {
             preserve_tracebacks: true,
             filter_dir_listings: true,
             detect_syntax: true,
+            prompt_lines: None,
         }
     }
 }
@@ -230,7 +235,7 @@ pub fn filter_lines(input: Vec<String>, options: &FilterOptions) -> FilteredOutp
     let mut consecutive_blanks = 0;
     let mut in_traceback = false;
 
-    for line in &input {
+    for (idx, line) in input.iter().enumerate() {
         let trimmed = line.trim();
 
         // Check for error/traceback patterns
@@ -248,7 +253,11 @@ pub fn filter_lines(input: Vec<String>, options: &FilterOptions) -> FilteredOutp
         }
 
         // Filter prompts (unless in traceback)
-        if options.filter_prompts && !in_traceback && is_prompt_line(line) {
+        let is_prompt = match &options.prompt_lines {
+            Some(prompts) => prompts.contains(&idx),
+            None => is_prompt_line(line),
+        };
+        if options.filter_prompts && !in_traceback && is_prompt {
             continue;
         }
 
@@ -314,6 +323,21 @@ mod tests {
         assert_eq!(result.lines[0], "ls -la");
     }
 
+    #[test]
+    fn test_exact_prompt_lines_replace_patterns() {
+        let input = vec![
+            "~/src on main".to_string(),
+            "λ make".to_string(),
+            "$ not a prompt here".to_string(),
+        ];
+        let options = FilterOptions {
+            prompt_lines: Some(HashSet::from([0, 1])),
+            ..FilterOptions::default()
+        };
+        let result = filter_lines(input, &options);
+        assert_eq!(result.lines, vec!["$ not a prompt here".to_string()]);
+    }
+
     #[test]
     fn test_traceback_preservation() {
         let input = vec![
@@ -43,6 +43,25 @@ pub fn get_current_branch(repo_root: &Path) -> Option<String> {
     }
 }
 
+/// Uncommitted changes (staged and unstaged) against HEAD as a unified diff
+pub fn get_diff(repo_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["diff", "HEAD", "--no-color"])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string(),
+        )
+    } else {
+        None
+    }
+}
+
 /// Parse git status porcelain output to get file statuses
 fn parse_git_status(repo_root: &Path) -> HashMap<PathBuf, GitFileStatus> {
     let mut statuses = HashMap::new();
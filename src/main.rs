@@ -1,13 +1,18 @@
 pub mod app;
 pub mod app_detector;
+pub mod asciicast;
 pub mod browser;
+pub mod claude_history;
 pub mod clipboard;
 pub mod config;
 pub mod filter;
 pub mod git;
 pub mod input;
+pub mod prompts;
+pub mod scrollback;
 pub mod session;
 pub mod setup;
+pub mod shell_integration;
 pub mod syntax_registry;
 pub mod terminal;
 pub mod types;
@@ -16,7 +21,7 @@ pub mod update;
 
 use anyhow::Result;
 use app::App;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use config::load_config;
 use session::load_session;
 use std::io::Write;
@@ -65,6 +70,58 @@ struct Args {
     /// Use when image paste in the Claude pane fails over SSH from a Mac.
     #[arg(long)]
     ssh_paste_diag: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Export Markdown files without starting the TUI (e.g. in CI).
+    /// Uses the `document` settings of the config file.
+    Export(ExportArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct ExportArgs {
+    /// Markdown files or directories (all top-level .md files)
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = CliExportFormat::Pdf)]
+    format: CliExportFormat,
+
+    /// Output directory (created if missing)
+    #[arg(short, long, default_value = ".")]
+    out: PathBuf,
+
+    /// Custom Typst template from the config templates dir (PDF only)
+    #[arg(long)]
+    template: Option<String>,
+
+    /// Branding profile from `document.profiles` (default: path rules)
+    #[arg(long)]
+    profile: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum CliExportFormat {
+    Pdf,
+    Md,
+    Html,
+    Docx,
+}
+
+impl From<CliExportFormat> for browser::pdf_export::ExportFormat {
+    fn from(format: CliExportFormat) -> Self {
+        match format {
+            CliExportFormat::Pdf => Self::Pdf,
+            CliExportFormat::Md => Self::Markdown,
+            CliExportFormat::Html => Self::Html,
+            CliExportFormat::Docx => Self::Docx,
+        }
+    }
 }
 
 /// Run update check from CLI and exit
@@ -150,6 +207,121 @@ fn run_update_to_version_cli(target_version: &str) -> Result<()> {
     Ok(())
 }
 
+/// Run the headless `export` subcommand. Prints one line per file and exits
+/// with status 1 if any export failed.
+fn run_export_cli(args: ExportArgs, config_path: Option<PathBuf>) -> Result<()> {
+    use browser::pdf_export::{self, ExportFormat, ExportOptions};
+
+    let config = match config_path {
+        Some(path) => config::load_config_file(&path)?,
+        None => load_config()?,
+    };
+    let format = ExportFormat::from(args.format);
+    if args.template.is_some() && format != ExportFormat::Pdf {
+        eprintln!("--template only applies to PDF export");
+        std::process::exit(1);
+    }
+
+    // Expand directories to their Markdown files
+    let mut sources = Vec::new();
+    for input in &args.inputs {
+        if input.is_dir() {
+            sources.extend(pdf_export::markdown_files_in(input));
+        } else {
+            sources.push(input.clone());
+        }
+    }
+    if sources.is_empty() {
+        eprintln!("No Markdown files found");
+        std::process::exit(1);
+    }
+
+    // Every file lands directly in --out: equal file stems from different
+    // folders would overwrite each other
+    let target_for = |source: &PathBuf| {
+        let stem = source
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("export");
+        args.out.join(format!("{}.{}", stem, format.extension()))
+    };
+    let mut targets: std::collections::HashMap<PathBuf, &PathBuf> =
+        std::collections::HashMap::new();
+    let mut collisions = 0usize;
+    for source in &sources {
+        if let Some(first) = targets.insert(target_for(source), source) {
+            collisions += 1;
+            eprintln!(
+                "✗ {} and {} would both be written to {}",
+                first.display(),
+                source.display(),
+                target_for(source).display()
+            );
+        }
+    }
+    if collisions > 0 {
+        std::process::exit(1);
+    }
+
+    std::fs::create_dir_all(&args.out)?;
+    let date = pdf_export::date_now_dmy();
+    let mut failed = 0usize;
+    for source in &sources {
+        let stem = source
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("export");
+        let target = target_for(source);
+        let options = ExportOptions {
+            title: stem.to_string(),
+            author: config.document.resolved_author(),
+            date: date.clone(),
+            format,
+            template: args.template.clone(),
+            meta: Default::default(),
+            profile: args.profile.clone(),
+        };
+
+        let result = if !source.is_file() {
+            Err(anyhow::anyhow!("file not found"))
+        } else if same_file(source, &target) {
+            Err(anyhow::anyhow!("output would overwrite the source"))
+        } else {
+            pdf_export::export_markdown(source, &target, &options, &config.document)
+        };
+        match result {
+            Ok(output) => {
+                println!("✓ {} → {}", source.display(), output.path.display());
+                if let Some(report) = output.fonts {
+                    println!("  {}", report.summary());
+                }
+                for warning in &output.warnings {
+                    println!("  ! {}", warning);
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("✗ {}: {:#}", source.display(), e);
+            }
+        }
+    }
+
+    println!();
+    println!("{} exported, {} failed", sources.len() - failed, failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// True if both paths exist and point to the same file.
+fn same_file(a: &std::path::Path, b: &std::path::Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
 /// Restore terminal to normal state - called on exit, panic, or signal
 fn restore_terminal() {
     let _ = crossterm::execute!(
@@ -341,6 +513,11 @@ fn main() -> Result<()> {
         return run_ssh_paste_diag_cli();
     }
 
+    // Handle `export` subcommand (headless, exit without starting TUI)
+    if let Some(Command::Export(export)) = args.command {
+        return run_export_cli(export, args.config);
+    }
+
     // Run the async main with tokio runtime
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
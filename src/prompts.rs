@@ -0,0 +1,82 @@
+//! Prompt library placeholder expansion.
+//!
+//! Templates come from `claude.prompts` in the config. Known placeholders
+//! (`{file}`, `{selection}`, `{git_diff}`, `{branch}`) are replaced with
+//! values from the current workbench state; any other `{...}` is left as-is
+//! so code snippets with braces survive.
+
+use std::collections::HashMap;
+
+/// Placeholders understood by [`expand`]
+pub const PLACEHOLDERS: &[&str] = &["file", "selection", "git_diff", "branch"];
+
+/// Known placeholders that occur in `template` (lets callers skip expensive
+/// lookups such as `git diff` when a template does not need them).
+pub fn used_placeholders(template: &str) -> Vec<&'static str> {
+    PLACEHOLDERS
+        .iter()
+        .copied()
+        .filter(|name| template.contains(&format!("{{{}}}", name)))
+        .collect()
+}
+
+/// Replace known placeholders in `template` with `values`.
+/// Known placeholders without a value expand to an empty string.
+pub fn expand(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name = after.find('}').map(|end| &after[..end]);
+        match name.filter(|n| PLACEHOLDERS.contains(n)) {
+            Some(name) => {
+                if let Some(value) = values.get(name) {
+                    out.push_str(value);
+                }
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_known_placeholders() {
+        let values = HashMap::from([
+            ("file", "src/main.rs".to_string()),
+            ("branch", "main".to_string()),
+        ]);
+        assert_eq!(
+            expand("Review @{file} on {branch}:\n{selection}!", &values),
+            "Review @src/main.rs on main:\n!"
+        );
+    }
+
+    #[test]
+    fn test_expand_keeps_unknown_braces() {
+        let values = HashMap::from([("file", "a.rs".to_string())]);
+        assert_eq!(
+            expand("fn x() { {y} } {file} {", &values),
+            "fn x() { {y} } a.rs {"
+        );
+    }
+
+    #[test]
+    fn test_used_placeholders() {
+        assert_eq!(
+            used_placeholders("{git_diff} on {branch} ({branch})"),
+            vec!["git_diff", "branch"]
+        );
+        assert!(used_placeholders("plain {other}").is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+//! Save the scrollback of a terminal pane (history plus live screen) to a
+//! file: plain text, text with the ANSI colors kept, or an HTML page in the
+//! dark template of the text preview (`browser::syntax`).
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::config::DocumentConfig;
+use crate::terminal::visit_buffer_pages;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollbackFormat {
+    Text,
+    Ansi,
+    Html,
+}
+
+impl ScrollbackFormat {
+    /// Chosen by extension: `.html`/`.htm`, `.ansi`/`.ans`, anything else
+    /// is plain text
+    pub fn from_path(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "html" | "htm" => Self::Html,
+            "ansi" | "ans" => Self::Ansi,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// xterm palette for indices 0-15 (the VS Code terminal colors, which suit
+/// the dark page background)
+const ANSI_COLORS: [&str; 16] = [
+    "#000000", "#cd3131", "#0dbc79", "#e5e510", "#2472c8", "#bc3fbc", "#11a8cd", "#e5e5e5",
+    "#666666", "#f14c4c", "#23d18b", "#f5f543", "#3b8eea", "#d670d6", "#29b8db", "#ffffff",
+];
+/// Page colors of the syntax template, used for inverse video
+const DEFAULT_FG: &str = "#d4d4d4";
+const DEFAULT_BG: &str = "#1e1e1e";
+
+fn css_color(color: vt100::Color) -> Option<String> {
+    match color {
+        vt100::Color::Default => None,
+        vt100::Color::Idx(i @ 0..=15) => Some(ANSI_COLORS[i as usize].to_string()),
+        vt100::Color::Idx(i @ 16..=231) => {
+            // 6×6×6 color cube
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                level(i / 36),
+                level(i / 6 % 6),
+                level(i % 6)
+            ))
+        }
+        vt100::Color::Idx(i) => {
+            let gray = 8 + (i - 232) * 10;
+            Some(format!("#{:02x}{:02x}{:02x}", gray, gray, gray))
+        }
+        vt100::Color::Rgb(r, g, b) => Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+    }
+}
+
+/// Inline CSS for a cell; empty for default attributes
+fn cell_style(cell: &vt100::Cell) -> String {
+    let mut fg = css_color(cell.fgcolor());
+    let mut bg = css_color(cell.bgcolor());
+    if cell.inverse() {
+        (fg, bg) = (
+            Some(bg.unwrap_or_else(|| DEFAULT_BG.to_string())),
+            Some(fg.unwrap_or_else(|| DEFAULT_FG.to_string())),
+        );
+    }
+    let mut style = String::new();
+    if let Some(fg) = fg {
+        let _ = write!(style, "color:{};", fg);
+    }
+    if let Some(bg) = bg {
+        let _ = write!(style, "background:{};", bg);
+    }
+    if cell.bold() {
+        style.push_str("font-weight:bold;");
+    }
+    if cell.dim() {
+        style.push_str("opacity:0.7;");
+    }
+    if cell.italic() {
+        style.push_str("font-style:italic;");
+    }
+    if cell.underline() {
+        style.push_str("text-decoration:underline;");
+    }
+    style
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// One visible row as `<span>`s, one per run of equally styled cells
+fn html_row(screen: &vt100::Screen, row: u16, cols: u16) -> String {
+    let cells: Vec<&vt100::Cell> = (0..cols)
+        .filter_map(|col| screen.cell(row, col))
+        .filter(|cell| !cell.is_wide_continuation())
+        .collect();
+    // Drop the blank tail, unless it is colored
+    let end = cells
+        .iter()
+        .rposition(|cell| {
+            cell.has_contents() || cell.bgcolor() != vt100::Color::Default || cell.inverse()
+        })
+        .map_or(0, |i| i + 1);
+
+    let mut html = String::new();
+    let mut run = String::new();
+    let mut run_style = String::new();
+    let flush = |html: &mut String, run: &mut String, style: &str| {
+        if run.is_empty() {
+            return;
+        }
+        if style.is_empty() {
+            html.push_str(run);
+        } else {
+            let _ = write!(html, "<span style=\"{}\">{}</span>", style, run);
+        }
+        run.clear();
+    };
+    for cell in &cells[..end] {
+        let style = cell_style(cell);
+        if style != run_style {
+            flush(&mut html, &mut run, &run_style);
+            run_style = style;
+        }
+        if cell.has_contents() {
+            escape_html(cell.contents(), &mut run);
+        } else {
+            run.push(' ');
+        }
+    }
+    flush(&mut html, &mut run, &run_style);
+    html
+}
+
+/// The whole buffer of `screen` in `format`. Trailing blank lines (the
+/// unused part of the live screen) are dropped; `title` names the HTML page.
+pub fn render(
+    screen: &mut vt100::Screen,
+    format: ScrollbackFormat,
+    title: &str,
+    doc: &DocumentConfig,
+) -> Vec<u8> {
+    let cols = screen.size().1;
+    // Plain text (to find blank lines) and the line in `format`
+    let mut lines: Vec<(String, String)> = Vec::new();
+    visit_buffer_pages(screen, |screen, take| {
+        let texts = screen
+            .rows(0, cols)
+            .take(take)
+            .map(|text| text.trim_end().to_string());
+        match format {
+            ScrollbackFormat::Text => lines.extend(texts.map(|text| (text.clone(), text))),
+            // A width other than the screen width keeps vt100 from joining
+            // wrapped rows with cursor moves, so every row stands alone;
+            // attributes carry over between rows, hence the reset.
+            ScrollbackFormat::Ansi => lines.extend(
+                texts
+                    .zip(screen.rows_formatted(0, u16::MAX))
+                    .map(|(text, row)| (text, format!("{}\x1b[m", String::from_utf8_lossy(&row)))),
+            ),
+            ScrollbackFormat::Html => lines.extend(
+                texts
+                    .enumerate()
+                    .map(|(row, text)| (text, html_row(screen, row as u16, cols))),
+            ),
+        }
+    });
+    while lines.last().is_some_and(|(text, _)| text.is_empty()) {
+        lines.pop();
+    }
+
+    let mut body = lines
+        .iter()
+        .map(|(_, line)| line.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    match format {
+        ScrollbackFormat::Html => {
+            let size: usize = lines.iter().map(|(text, _)| text.len() + 1).sum();
+            let mut escaped_title = String::new();
+            escape_html(title, &mut escaped_title);
+            crate::browser::syntax::render_terminal_page(
+                &escaped_title,
+                &body,
+                size,
+                lines.len(),
+                doc,
+            )
+            .into_bytes()
+        }
+        _ => {
+            if !body.is_empty() {
+                body.push('\n');
+            }
+            body.into_bytes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen_with(output: &str) -> vt100::Parser {
+        let mut parser = vt100::Parser::new(3, 20, 100);
+        parser.process(output.as_bytes());
+        parser
+    }
+
+    #[test]
+    fn format_follows_the_extension() {
+        assert_eq!(
+            ScrollbackFormat::from_path(Path::new("out.HTML")),
+            ScrollbackFormat::Html
+        );
+        assert_eq!(
+            ScrollbackFormat::from_path(Path::new("out.ans")),
+            ScrollbackFormat::Ansi
+        );
+        assert_eq!(
+            ScrollbackFormat::from_path(Path::new("out.log")),
+            ScrollbackFormat::Text
+        );
+        assert_eq!(
+            ScrollbackFormat::from_path(Path::new("out")),
+            ScrollbackFormat::Text
+        );
+    }
+
+    #[test]
+    fn renders_history_and_screen_in_every_format() {
+        let output = "one\r\ntwo\r\n\x1b[31mred\x1b[m <b>\r\nfour\r\nfive";
+        let doc = DocumentConfig::default();
+        let mut parser = screen_with(output);
+        let render = |parser: &mut vt100::Parser, format| {
+            String::from_utf8(render(parser.screen_mut(), format, "T & T", &doc)).unwrap()
+        };
+
+        assert_eq!(
+            render(&mut parser, ScrollbackFormat::Text),
+            "one\ntwo\nred <b>\nfour\nfive\n"
+        );
+        // Scrollback offset is left alone
+        assert_eq!(parser.screen().scrollback(), 0);
+
+        let ansi = render(&mut parser, ScrollbackFormat::Ansi);
+        assert_eq!(ansi.lines().count(), 5);
+        assert!(ansi.contains("\x1b[31mred"));
+        assert!(ansi.lines().all(|line| line.ends_with("\x1b[m")));
+
+        let html = render(&mut parser, ScrollbackFormat::Html);
+        assert!(html.contains("<title>T &amp; T</title>"));
+        assert!(html.contains("<span style=\"color:#cd3131;\">red</span> &lt;b&gt;\nfour"));
+        assert!(html.contains("Terminal • 26 B • 5 lines"));
+    }
+
+    #[test]
+    fn palette_covers_cube_and_grays() {
+        assert_eq!(css_color(vt100::Color::Idx(16)).unwrap(), "#000000");
+        assert_eq!(css_color(vt100::Color::Idx(196)).unwrap(), "#ff0000");
+        assert_eq!(css_color(vt100::Color::Idx(255)).unwrap(), "#eeeeee");
+        assert_eq!(css_color(vt100::Color::Default), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Shell integration for the Terminal pane: small startup scripts for bash,
+//! zsh and fish that load the user's own startup files and then report
+//! prompts, commands and exit codes (OSC 133) plus the working directory
+//! (OSC 7). The PTY parser turns these marks into command blocks.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::TerminalConfig;
+
+const BASH_RC: &str = r#"# claude-workbench shell integration: load ~/.bashrc as usual, then report
+# prompts and exit codes (OSC 133) and the working directory (OSC 7)
+[[ -f ~/.bashrc ]] && source ~/.bashrc
+__cwb_prompt() {
+  printf '\e]133;D;%s\a\e]133;A\a\e]7;file://%s%s\a' "$?" "$HOSTNAME" "$PWD"
+}
+PROMPT_COMMAND="__cwb_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+PS1="${PS1}"'\[\e]133;B\a\]'
+PS0="${PS0}"$'\e]133;C\a'
+"#;
+
+const ZSH_ENV: &str = r#"# claude-workbench shell integration: restore the user's ZDOTDIR, load their
+# .zshenv and report prompts and exit codes (OSC 133) and the working
+# directory (OSC 7)
+if [[ -n $CWB_USER_ZDOTDIR ]]; then
+  ZDOTDIR=$CWB_USER_ZDOTDIR
+else
+  unset ZDOTDIR
+fi
+unset CWB_USER_ZDOTDIR
+[[ -f ${ZDOTDIR:-$HOME}/.zshenv ]] && source ${ZDOTDIR:-$HOME}/.zshenv
+if [[ -o interactive ]]; then
+  __cwb_precmd() {
+    printf '\e]133;D;%s\a\e]133;A\a\e]7;file://%s%s\a' $? $HOST $PWD
+    [[ $PS1 == *'133;B'* ]] || PS1+=$'%{\e]133;B\a%}'
+  }
+  __cwb_preexec() { printf '\e]133;C\a' }
+  precmd_functions=(__cwb_precmd $precmd_functions)
+  preexec_functions+=(__cwb_preexec)
+fi
+"#;
+
+const FISH_INIT: &str = r#"# claude-workbench shell integration: report prompts and exit codes
+# (OSC 133) and the working directory (OSC 7)
+function __cwb_preexec --on-event fish_preexec
+    printf '\e]133;C\a'
+end
+function __cwb_postexec --on-event fish_postexec
+    printf '\e]133;D;%s\a' $status
+end
+function __cwb_prompt --on-event fish_prompt
+    printf '\e]133;A\a\e]7;file://%s%s\a' $hostname $PWD
+end
+"#;
+
+/// Where the startup scripts are written
+fn script_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("claude-workbench").join("shell"))
+}
+
+/// Shell name without directory and extension (`/usr/bin/zsh` → `zsh`)
+fn shell_name(shell_path: &str) -> &str {
+    Path::new(shell_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(shell_path)
+}
+
+/// Command line that starts `shell` with the scripts in `dir`; `None` for
+/// shells without integration. `user_zdotdir` is the caller's `$ZDOTDIR`,
+/// which the zsh script restores.
+fn integrated_command(
+    shell: &str,
+    dir: &Path,
+    user_zdotdir: Option<String>,
+) -> Option<Vec<String>> {
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    match shell_name(shell) {
+        "bash" => Some(vec![
+            shell.to_string(),
+            "--rcfile".to_string(),
+            path("bashrc"),
+        ]),
+        "zsh" => {
+            // zsh has no rcfile option: point ZDOTDIR at our .zshenv
+            let mut cmd = vec!["env".to_string(), format!("ZDOTDIR={}", path("zsh"))];
+            cmd.extend(user_zdotdir.map(|dir| format!("CWB_USER_ZDOTDIR={}", dir)));
+            cmd.push(shell.to_string());
+            Some(cmd)
+        }
+        "fish" => Some(vec![
+            shell.to_string(),
+            "--init-command".to_string(),
+            format!("source '{}'", path("init.fish").replace('\'', "\\'")),
+        ]),
+        _ => None,
+    }
+}
+
+fn write_scripts(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir.join("zsh"))?;
+    std::fs::write(dir.join("bashrc"), BASH_RC)?;
+    std::fs::write(dir.join("zsh").join(".zshenv"), ZSH_ENV)?;
+    std::fs::write(dir.join("init.fish"), FISH_INIT)?;
+    Ok(())
+}
+
+/// Command line for the Terminal pane: the configured shell, with the
+/// integration scripts when enabled, supported and no custom arguments are
+/// set. Falls back to the plain shell when the scripts cannot be written.
+pub fn terminal_command(config: &TerminalConfig) -> Vec<String> {
+    let plain = || {
+        let mut cmd = vec![config.shell_path.clone()];
+        cmd.extend(config.shell_args.clone());
+        cmd
+    };
+    if !config.shell_integration || !config.shell_args.is_empty() {
+        return plain();
+    }
+    let Some(dir) = script_dir() else {
+        return plain();
+    };
+    let Some(cmd) = integrated_command(&config.shell_path, &dir, std::env::var("ZDOTDIR").ok())
+    else {
+        return plain();
+    };
+    match write_scripts(&dir) {
+        Ok(()) => cmd,
+        Err(_) => plain(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrated_command_per_shell() {
+        let dir = Path::new("/cache/shell");
+        assert_eq!(
+            integrated_command("/bin/bash", dir, None),
+            Some(vec![
+                "/bin/bash".to_string(),
+                "--rcfile".to_string(),
+                "/cache/shell/bashrc".to_string()
+            ])
+        );
+        assert_eq!(
+            integrated_command("/usr/bin/zsh", dir, Some("/home/me/.zsh".to_string())),
+            Some(vec![
+                "env".to_string(),
+                "ZDOTDIR=/cache/shell/zsh".to_string(),
+                "CWB_USER_ZDOTDIR=/home/me/.zsh".to_string(),
+                "/usr/bin/zsh".to_string()
+            ])
+        );
+        assert_eq!(
+            integrated_command("fish", dir, None).map(|cmd| cmd[2].clone()),
+            Some("source '/cache/shell/init.fish'".to_string())
+        );
+        assert_eq!(integrated_command("/bin/dash", dir, None), None);
+    }
+
+    #[test]
+    fn custom_shell_args_disable_integration() {
+        let config = TerminalConfig {
+            shell_path: "/bin/bash".to_string(),
+            shell_args: vec!["-l".to_string()],
+            shell_integration: true,
+        };
+        assert_eq!(terminal_command(&config), vec!["/bin/bash", "-l"]);
+    }
+}
@@ -1,9 +1,14 @@
 use anyhow::Result;
 use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use std::collections::VecDeque;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::asciicast::Recorder;
 
 /// Lock a mutex, recovering from poisoning.
 /// If another thread panicked while holding the lock, we still get access
@@ -14,25 +19,284 @@ fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
         .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
+/// Most recent OSC 8 hyperlink spans kept per terminal
+const MAX_HYPERLINKS: usize = 512;
+
+/// One row of an OSC 8 hyperlink. `line` is a buffer line (history first,
+/// like [`PseudoTerminal::extract_all_lines`]), `start..end` the columns.
+#[derive(Debug, Clone)]
+struct Hyperlink {
+    line: usize,
+    start: u16,
+    end: u16,
+    uri: String,
+    /// Cell text when the link was closed, to detect overwritten spans
+    text: String,
+}
+
+/// Most recent OSC 133 commands kept per terminal
+const MAX_COMMANDS: usize = 256;
+
+/// A shell command delimited by OSC 133 semantic prompt marks. Lines are
+/// buffer lines at the time of the mark.
+#[derive(Debug, Clone, Default)]
+struct ShellCommand {
+    /// `A`: prompt start
+    prompt: usize,
+    /// `B`: start of the command line as (lines below `prompt`, column)
+    input: Option<(usize, u16)>,
+    /// `C`: command executed, output starts here
+    output: Option<usize>,
+    /// `D;<code>`: exit code of the finished command
+    exit_code: Option<i32>,
+    /// Time of `C` and of `D` (or of the next prompt when `D` is missing)
+    started: Option<Instant>,
+    finished: Option<Instant>,
+    /// Prompt line text at `C`, to find the line again after history lines
+    /// dropped out of the full scrollback buffer
+    prompt_text: String,
+}
+
+/// A command located in the current buffer, see [`PseudoTerminal::command_blocks`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandBlock {
+    /// First prompt line
+    pub prompt: usize,
+    /// Start of the typed command (line, column); `None` when the shell
+    /// sends no `B` mark
+    pub input: Option<(usize, u16)>,
+    /// First output line; `None` while the prompt waits for input
+    pub output: Option<usize>,
+    /// End of the block (exclusive): the next prompt or the end of the buffer
+    pub end: usize,
+    /// `None` while running or when the shell does not report it
+    pub exit_code: Option<i32>,
+    /// Run time so far (while running) or in total
+    pub duration: Option<Duration>,
+    /// Output started and the command has not finished yet
+    pub running: bool,
+}
+
 /// Callbacks for handling terminal queries (DSR, DA) that require responses
-/// back to the child process running inside the PTY.
+/// back to the child process running inside the PTY, and for recording
+/// OSC 8 hyperlinks (vt100 cells carry no hyperlink attribute), OSC 7
+/// working-directory reports and OSC 133 prompt marks, as well as output
+/// activity and bells for the Claude notifications.
 pub struct PtyCallbacks {
     pending_responses: Vec<Vec<u8>>,
+    /// Open OSC 8 link: start (buffer line, column) and URI
+    open_hyperlink: Option<(usize, u16, String)>,
+    hyperlinks: VecDeque<Hyperlink>,
+    /// Last working directory reported via OSC 7
+    cwd: Option<PathBuf>,
+    /// Set when `cwd` changed and nobody has picked it up yet
+    cwd_changed: bool,
+    commands: VecDeque<ShellCommand>,
+    /// BEL characters received since the last `take_bells`
+    bells: usize,
+    /// When the child last wrote output
+    last_output: Option<Instant>,
+    /// Bumped on every processed output chunk and resize
+    generation: u64,
 }
 
 impl PtyCallbacks {
     fn new() -> Self {
         Self {
             pending_responses: Vec::new(),
+            open_hyperlink: None,
+            hyperlinks: VecDeque::new(),
+            cwd: None,
+            cwd_changed: false,
+            commands: VecDeque::new(),
+            bells: 0,
+            last_output: None,
+            generation: 0,
         }
     }
 
     fn drain_responses(&mut self) -> Vec<Vec<u8>> {
         std::mem::take(&mut self.pending_responses)
     }
+
+    /// Buffer position of the cursor
+    fn cursor_line_col(screen: &mut vt100::Screen) -> (usize, u16) {
+        let (row, col) = screen.cursor_position();
+        (history_depth(screen) + row as usize, col)
+    }
+
+    /// End the open OSC 8 link at the cursor, one span per covered row
+    fn close_hyperlink(&mut self, screen: &mut vt100::Screen) {
+        let Some((start_line, start_col, uri)) = self.open_hyperlink.take() else {
+            return;
+        };
+        let (end_line, end_col) = Self::cursor_line_col(screen);
+        if end_line < start_line {
+            return;
+        }
+        let cols = screen.size().1;
+        for line in start_line..=end_line {
+            let start = if line == start_line { start_col } else { 0 };
+            let end = if line == end_line { end_col } else { cols };
+            if start >= end {
+                continue;
+            }
+            if self.hyperlinks.len() == MAX_HYPERLINKS {
+                self.hyperlinks.pop_front();
+            }
+            self.hyperlinks.push_back(Hyperlink {
+                line,
+                start,
+                end,
+                uri: uri.clone(),
+                text: cell_text(screen, line, start, end),
+            });
+        }
+    }
+
+    /// OSC 133 `A` (prompt start), `B` (command start), `C` (output start)
+    /// and `D;<exit code>`
+    fn prompt_mark(&mut self, screen: &mut vt100::Screen, kind: &[u8], args: &[&[u8]]) {
+        let (line, col) = Self::cursor_line_col(screen);
+        match kind {
+            b"A" => match self.commands.back_mut() {
+                // Prompt redrawn (same or earlier line) before a command ran
+                Some(last) if last.output.is_none() && line <= last.prompt => last.prompt = line,
+                last => {
+                    // A new prompt also ends a command whose shell sent no D
+                    if let Some(last) = last.filter(|c| c.started.is_some()) {
+                        last.finished.get_or_insert_with(Instant::now);
+                    }
+                    if self.commands.len() == MAX_COMMANDS {
+                        self.commands.pop_front();
+                    }
+                    self.commands.push_back(ShellCommand {
+                        prompt: line,
+                        ..ShellCommand::default()
+                    });
+                }
+            },
+            b"B" => {
+                if let Some(last) = self
+                    .commands
+                    .back_mut()
+                    .filter(|c| c.output.is_none() && line >= c.prompt)
+                {
+                    last.input = Some((line - last.prompt, col));
+                }
+            }
+            b"C" => {
+                if let Some(last) = self.commands.back_mut().filter(|c| c.output.is_none()) {
+                    let cols = screen.size().1;
+                    last.output = Some(line);
+                    last.started = Some(Instant::now());
+                    last.prompt_text = cell_text(screen, last.prompt, 0, cols)
+                        .trim_end()
+                        .to_string();
+                }
+            }
+            b"D" => {
+                // Shells also send D for an empty command line: no C, ignored
+                if let Some(last) = self
+                    .commands
+                    .back_mut()
+                    .filter(|c| c.output.is_some() && c.finished.is_none())
+                {
+                    last.finished = Some(Instant::now());
+                    last.exit_code = args
+                        .first()
+                        .and_then(|code| std::str::from_utf8(code).ok())
+                        .and_then(|code| code.parse().ok());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Directory of an OSC 7 report `file://host/path` (percent-encoded)
+fn parse_osc7(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Some(PathBuf::from(
+        String::from_utf8_lossy(&decoded).into_owned(),
+    ))
+}
+
+/// Locate recorded commands in `lines` (the current buffer). Once the
+/// scrollback is full, every new line drops the oldest one and shifts all
+/// recorded line numbers up; the shift is recovered from each command's
+/// prompt text. It only grows for older commands, which keeps the search
+/// from matching a newer, identical prompt line.
+fn locate_commands(lines: &[String], commands: &VecDeque<ShellCommand>) -> Vec<CommandBlock> {
+    let mut blocks: Vec<CommandBlock> = Vec::new();
+    let mut shift = 0;
+    for cmd in commands.iter().rev() {
+        if cmd.output.is_some() {
+            let found =
+                (shift..=cmd.prompt).find(|k| lines.get(cmd.prompt - k) == Some(&cmd.prompt_text));
+            match found {
+                Some(k) => shift = k,
+                // Scrolled out or wiped (e.g. `clear`)
+                None => continue,
+            }
+        }
+        let Some(prompt) = cmd.prompt.checked_sub(shift) else {
+            break;
+        };
+        let end = blocks.last().map_or(lines.len(), |newer| newer.prompt);
+        if prompt >= end {
+            continue;
+        }
+        let duration = match (cmd.started, cmd.finished) {
+            (Some(started), Some(finished)) => Some(finished - started),
+            (Some(started), None) => Some(started.elapsed()),
+            _ => None,
+        };
+        blocks.push(CommandBlock {
+            prompt,
+            input: cmd
+                .input
+                .map(|(below, col)| (prompt + below, col))
+                .filter(|(line, _)| *line < end),
+            output: cmd
+                .output
+                .and_then(|line| line.checked_sub(shift))
+                .map(|line| line.min(end)),
+            end,
+            exit_code: cmd.exit_code,
+            duration,
+            running: cmd.started.is_some() && cmd.finished.is_none(),
+        });
+    }
+    blocks.reverse();
+    blocks
 }
 
 impl vt100::Callbacks for PtyCallbacks {
+    fn audible_bell(&mut self, _screen: &mut vt100::Screen) {
+        self.bells += 1;
+    }
+
     fn unhandled_csi(
         &mut self,
         screen: &mut vt100::Screen,
@@ -60,6 +324,41 @@ impl vt100::Callbacks for PtyCallbacks {
             _ => {}
         }
     }
+
+    fn unhandled_osc(&mut self, screen: &mut vt100::Screen, params: &[&[u8]]) {
+        // OSC 8 ; params ; URI — an empty URI ends the link. vt100 splits
+        // on every ';', so a URI containing one arrives in several parts.
+        if let [b"8", _, uri @ ..] = params {
+            let uri = uri
+                .iter()
+                .map(|part| String::from_utf8_lossy(part))
+                .collect::<Vec<_>>()
+                .join(";");
+            self.close_hyperlink(screen);
+            if !uri.is_empty() {
+                let (line, col) = Self::cursor_line_col(screen);
+                self.open_hyperlink = Some((line, col, uri));
+            }
+            return;
+        }
+        match params {
+            // OSC 7 ; file://host/path — working directory of the shell
+            [b"7", uri @ ..] => {
+                let uri = uri
+                    .iter()
+                    .map(|part| String::from_utf8_lossy(part))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                if let Some(dir) = parse_osc7(&uri) {
+                    self.cwd_changed |= self.cwd.as_ref() != Some(&dir);
+                    self.cwd = Some(dir);
+                }
+            }
+            // OSC 133 ; A|B|C|D[;args] — semantic prompt marks
+            [b"133", kind, args @ ..] => self.prompt_mark(screen, kind, args),
+            _ => {}
+        }
+    }
 }
 
 /// Direction of a mouse-wheel event forwarded to the PTY.
@@ -101,12 +400,104 @@ pub(crate) fn encode_wheel_event(
     buf
 }
 
+/// Number of history lines above the live screen. Leaves the scrollback
+/// offset unchanged.
+pub(crate) fn history_depth(screen: &mut vt100::Screen) -> usize {
+    // set_scrollback clamps to the real fill level, so reading it back
+    // after requesting the max yields the true history depth.
+    let saved = screen.scrollback();
+    screen.set_scrollback(usize::MAX);
+    let history = screen.scrollback();
+    screen.set_scrollback(saved);
+    history
+}
+
+/// Walk the whole grid (history then live screen) page by page: `page`
+/// gets the screen scrolled so that its first `take` visible rows are the
+/// next buffer lines. Leaves the scrollback offset unchanged.
+///
+/// vt100's `cell(row, col)` is relative to the current scrollback offset,
+/// so the history is read in non-overlapping windows of `rows` lines: at
+/// offset `history - start`, visible row 0 == history line `start`.
+pub(crate) fn visit_buffer_pages(
+    screen: &mut vt100::Screen,
+    mut page: impl FnMut(&vt100::Screen, usize),
+) {
+    let saved = screen.scrollback();
+    let rows = screen.size().0 as usize;
+    if rows == 0 {
+        return;
+    }
+
+    let history = history_depth(screen);
+    let mut start = 0;
+    while start < history {
+        screen.set_scrollback(history - start);
+        page(screen, (history - start).min(rows));
+        start += rows;
+    }
+
+    // Live visible screen sits at offset 0.
+    screen.set_scrollback(0);
+    page(screen, rows);
+
+    screen.set_scrollback(saved);
+}
+
+/// Text of buffer `line` (history first), columns `start..end`. Leaves the
+/// scrollback offset unchanged.
+fn cell_text(screen: &mut vt100::Screen, line: usize, start: u16, end: u16) -> String {
+    let saved = screen.scrollback();
+    let history = history_depth(screen);
+    // Scroll just far enough that `line` is on screen
+    let offset = history.saturating_sub(line);
+    screen.set_scrollback(offset);
+    let row = (line + offset - history) as u16;
+    let mut text = String::new();
+    for col in start..end {
+        if let Some(cell) = screen.cell(row, col) {
+            PseudoTerminal::push_cell_content(&mut text, cell);
+        }
+    }
+    screen.set_scrollback(saved);
+    text
+}
+
+/// Bytes for pasting `text` into a PTY. With `bracketed` (the child enabled
+/// DECSET 2004) the text is wrapped in `ESC[200~ … ESC[201~` so embedded
+/// newlines are inserted instead of submitting; end markers inside the
+/// text are removed until none is left (removing one can join the pieces
+/// of another) so the paste cannot be terminated early. Without it,
+/// carriage returns are dropped (CRLF would inject extra Enter presses).
+pub(crate) fn paste_bytes(text: &str, bracketed: bool) -> Vec<u8> {
+    if bracketed {
+        let mut text = text.to_string();
+        while text.contains("\x1b[201~") {
+            text = text.replace("\x1b[201~", "");
+        }
+        format!("\x1b[200~{}\x1b[201~", text).into_bytes()
+    } else {
+        text.replace('\r', "").into_bytes()
+    }
+}
+
 pub struct PseudoTerminal {
     pub parser: Arc<Mutex<vt100::Parser<PtyCallbacks>>>,
     pub writer: Arc<Mutex<Box<dyn Write + Send>>>,
     pub master: Box<dyn portable_pty::MasterPty + Send>,
     /// Flag indicating if the PTY process has exited (reader thread got EOF)
     pub exited: Arc<AtomicBool>,
+    /// Child process handle, kept so the exit status can be collected
+    child: Mutex<Box<dyn portable_pty::Child + Send + Sync>>,
+    /// Exit status once the child has been reaped
+    exit_status: Mutex<Option<portable_pty::ExitStatus>>,
+    /// When input was last written to the child
+    last_input: Option<Instant>,
+    /// asciicast recording, fed by the reader thread
+    recorder: Arc<Mutex<Option<Recorder>>>,
+    /// Buffer lines for locating command blocks, with the content
+    /// generation they were read at
+    line_cache: Mutex<Option<(u64, Vec<String>)>>,
 }
 
 impl PseudoTerminal {
@@ -129,6 +520,8 @@ impl PseudoTerminal {
         let mut reader = pair.master.try_clone_reader()?;
         let parser_clone = parser.clone();
         let exited_clone = exited.clone();
+        let recorder: Arc<Mutex<Option<Recorder>>> = Arc::new(Mutex::new(None));
+        let recorder_clone = recorder.clone();
 
         // Spawn the command before taking the writer, so we have the slave end
         let cmd_str = &command[0];
@@ -150,10 +543,7 @@ impl PseudoTerminal {
         cmd.env("LANG", "en_US.UTF-8");
         cmd.env("LC_ALL", "en_US.UTF-8");
 
-        let _child = pair.slave.spawn_command(cmd)?;
-
-        // We drop _child here, it runs in background.
-        // In a real app we might want to keep it to check exit status.
+        let child = pair.slave.spawn_command(cmd)?;
 
         let writer: Arc<Mutex<Box<dyn Write + Send>>> =
             Arc::new(Mutex::new(pair.master.take_writer()?));
@@ -174,7 +564,15 @@ impl PseudoTerminal {
                         let responses = {
                             let mut parser = lock_or_recover(&parser_clone);
                             parser.process(&buffer[..n]);
-                            parser.callbacks_mut().drain_responses()
+                            // Under the parser lock: `start_recording`
+                            // snapshots the screen with the same lock held
+                            if let Some(rec) = lock_or_recover(&recorder_clone).as_mut() {
+                                let _ = rec.output(&buffer[..n]);
+                            }
+                            let callbacks = parser.callbacks_mut();
+                            callbacks.last_output = Some(Instant::now());
+                            callbacks.generation += 1;
+                            callbacks.drain_responses()
                         };
                         if !responses.is_empty() {
                             if let Ok(mut w) = writer_clone.lock() {
@@ -199,6 +597,11 @@ impl PseudoTerminal {
             writer,
             master: pair.master,
             exited,
+            child: Mutex::new(child),
+            exit_status: Mutex::new(None),
+            last_input: None,
+            recorder,
+            line_cache: Mutex::new(None),
         })
     }
 
@@ -220,10 +623,15 @@ impl PseudoTerminal {
         })?;
         let mut parser = lock_or_recover(&self.parser);
         parser.screen_mut().set_size(rows, cols);
+        parser.callbacks_mut().generation += 1;
+        if let Some(rec) = lock_or_recover(&self.recorder).as_mut() {
+            let _ = rec.resize(cols, rows);
+        }
         Ok(())
     }
 
     pub fn write_input(&mut self, input: &[u8]) -> Result<()> {
+        self.last_input = Some(Instant::now());
         // If typing, reset scrollback
         {
             let mut parser = lock_or_recover(&self.parser);
@@ -232,12 +640,28 @@ impl PseudoTerminal {
                 screen.set_scrollback(0);
             }
         }
+        if let Some(rec) = lock_or_recover(&self.recorder).as_mut() {
+            let _ = rec.input(input);
+        }
         let mut writer = lock_or_recover(&self.writer);
         writer.write_all(input)?;
         writer.flush()?;
         Ok(())
     }
 
+    /// Whether the child enabled bracketed paste (DECSET 2004).
+    /// vt100 tracks the mode while parsing the child's output.
+    pub fn bracketed_paste(&self) -> bool {
+        lock_or_recover(&self.parser).screen().bracketed_paste()
+    }
+
+    /// Write programmatically injected text (pastes, paths, prompts),
+    /// bracketed when the child asked for it. See [`paste_bytes`].
+    pub fn write_paste(&mut self, text: &str) -> Result<()> {
+        let bytes = paste_bytes(text, self.bracketed_paste());
+        self.write_input(&bytes)
+    }
+
     pub fn scroll_up(&self, lines: usize) {
         let mut parser = lock_or_recover(&self.parser);
         let screen = parser.screen_mut();
@@ -380,45 +804,79 @@ impl PseudoTerminal {
         Self::collect_buffer_lines(parser.screen_mut())
     }
 
-    /// Collect the whole grid (history then live screen) as trimmed lines.
-    ///
-    /// vt100's `cell(row, col)` is relative to the current scrollback offset,
-    /// so the history is read in non-overlapping windows of `rows` lines: at
-    /// offset `history - start`, visible row 0 == history line `start`.
-    fn collect_buffer_lines(screen: &mut vt100::Screen) -> Vec<String> {
-        let saved = screen.scrollback();
-        let (rows, cols) = screen.size();
-        let rows = rows as usize;
-        if rows == 0 {
-            return Vec::new();
-        }
+    /// The entire buffer like `extract_all_lines`, but as displayed text:
+    /// the blank second half of wide characters is dropped (`日本`, not
+    /// `日 本`), so cell columns follow from the `unicode-width` of a prefix.
+    pub fn extract_all_text(&self) -> Vec<String> {
+        let mut parser = lock_or_recover(&self.parser);
+        Self::collect_buffer_text(parser.screen_mut())
+    }
+
+    /// Visible `row` (screen-relative) as displayed text, like the lines of
+    /// [`Self::extract_all_text`]
+    pub fn extract_row_text(&self, row: u16) -> String {
+        let parser = lock_or_recover(&self.parser);
+        let screen = parser.screen();
+        Self::read_screen_text(screen, row, screen.size().1)
+    }
 
-        // set_scrollback clamps to the real fill level, so reading it back
-        // after requesting the max yields the true history depth.
-        screen.set_scrollback(usize::MAX);
-        let history = screen.scrollback();
+    /// Changes whenever the buffer content may have changed (output,
+    /// resize); caches of buffer-derived data compare it to stay fresh.
+    pub fn content_generation(&self) -> u64 {
+        lock_or_recover(&self.parser).callbacks().generation
+    }
 
-        let mut lines = Vec::with_capacity(history + rows);
-        let mut start = 0;
-        while start < history {
-            screen.set_scrollback(history - start);
-            let take = (history - start).min(rows);
+    /// The entire buffer like `extract_all_lines`, rendered for saving
+    pub fn export_scrollback(
+        &self,
+        format: crate::scrollback::ScrollbackFormat,
+        title: &str,
+        doc: &crate::config::DocumentConfig,
+    ) -> Vec<u8> {
+        let mut parser = lock_or_recover(&self.parser);
+        crate::scrollback::render(parser.screen_mut(), format, title, doc)
+    }
+
+    /// Collect the whole grid (history then live screen) as trimmed lines.
+    fn collect_buffer_lines(screen: &mut vt100::Screen) -> Vec<String> {
+        let cols = screen.size().1;
+        let mut lines = Vec::new();
+        visit_buffer_pages(screen, |screen, take| {
             for row in 0..take {
                 lines.push(Self::read_screen_row(screen, row, cols));
             }
-            start += rows;
-        }
-
-        // Live visible screen sits at offset 0.
-        screen.set_scrollback(0);
-        for row in 0..rows {
-            lines.push(Self::read_screen_row(screen, row, cols));
-        }
+        });
+        lines
+    }
 
-        screen.set_scrollback(saved);
+    /// Collect the whole grid like `collect_buffer_lines`, skipping wide
+    /// character continuation cells.
+    fn collect_buffer_text(screen: &mut vt100::Screen) -> Vec<String> {
+        let cols = screen.size().1;
+        let mut lines = Vec::new();
+        visit_buffer_pages(screen, |screen, take| {
+            for row in 0..take {
+                lines.push(Self::read_screen_text(screen, row as u16, cols));
+            }
+        });
         lines
     }
 
+    /// Visible `row` like `read_screen_row`, without wide character
+    /// continuation cells.
+    fn read_screen_text(screen: &vt100::Screen, row: u16, cols: u16) -> String {
+        let mut line = String::new();
+        for col in 0..cols {
+            match screen.cell(row, col) {
+                Some(cell) if !cell.is_wide_continuation() => {
+                    Self::push_cell_content(&mut line, cell)
+                }
+                _ => {}
+            }
+        }
+        line.trim_end().to_string()
+    }
+
     /// Read a single visible row (at the current scrollback offset) as a
     /// trailing-trimmed string.
     fn read_screen_row(screen: &vt100::Screen, row: usize, cols: u16) -> String {
@@ -491,12 +949,165 @@ impl PseudoTerminal {
         screen.cursor_position().0
     }
 
+    /// Number of visible rows
+    pub fn rows(&self) -> u16 {
+        lock_or_recover(&self.parser).screen().size().0
+    }
+
+    /// Screen size as `(rows, cols)`
+    pub fn size(&self) -> (u16, u16) {
+        lock_or_recover(&self.parser).screen().size()
+    }
+
     /// Get current scrollback offset
     pub fn scrollback(&self) -> usize {
         let parser = lock_or_recover(&self.parser);
         parser.screen().scrollback()
     }
 
+    /// Scroll so that `line` of the whole buffer (history first, as returned
+    /// by [`Self::extract_all_lines`]) is roughly centered in the pane.
+    pub fn scroll_to_line(&self, line: usize) {
+        let mut parser = lock_or_recover(&self.parser);
+        let screen = parser.screen_mut();
+        let history = history_depth(screen);
+        let rows = screen.size().0 as usize;
+        let offset = if line >= history {
+            0
+        } else {
+            (history - line + rows / 2).min(history)
+        };
+        screen.set_scrollback(offset);
+    }
+
+    /// Buffer line shown in the first visible row (history lines hidden
+    /// above the view) and the visible cursor position (row, col)
+    pub fn view_origin(&self) -> (usize, (u16, u16)) {
+        let mut parser = lock_or_recover(&self.parser);
+        let screen = parser.screen_mut();
+        let top = history_depth(screen) - screen.scrollback();
+        (top, screen.cursor_position())
+    }
+
+    /// Scroll the minimum needed to make buffer `line` visible
+    pub fn scroll_line_into_view(&self, line: usize) {
+        let mut parser = lock_or_recover(&self.parser);
+        let screen = parser.screen_mut();
+        let history = history_depth(screen);
+        let rows = (screen.size().0 as usize).max(1);
+        let top = history - screen.scrollback();
+        if line < top {
+            screen.set_scrollback(history - line);
+        } else if line >= top + rows {
+            screen.set_scrollback(history.saturating_sub(line + 1 - rows));
+        }
+    }
+
+    /// OSC 8 hyperlink covering buffer `line` / `col` as (start, end, URI),
+    /// ignoring spans whose text has since been overwritten
+    pub fn hyperlink_at(&self, line: usize, col: u16) -> Option<(u16, u16, String)> {
+        let mut parser = lock_or_recover(&self.parser);
+        let link = parser
+            .callbacks()
+            .hyperlinks
+            .iter()
+            .rev()
+            .find(|l| l.line == line && (l.start..l.end).contains(&col))
+            .cloned()?;
+        (cell_text(parser.screen_mut(), line, link.start, link.end) == link.text)
+            .then_some((link.start, link.end, link.uri))
+    }
+
+    /// Working directory last reported by the shell (OSC 7)
+    pub fn cwd(&self) -> Option<PathBuf> {
+        lock_or_recover(&self.parser).callbacks().cwd.clone()
+    }
+
+    /// The reported working directory, once per change
+    pub fn take_cwd_change(&self) -> Option<PathBuf> {
+        let mut parser = lock_or_recover(&self.parser);
+        let callbacks = parser.callbacks_mut();
+        std::mem::take(&mut callbacks.cwd_changed)
+            .then(|| callbacks.cwd.clone())
+            .flatten()
+    }
+
+    /// When the child last wrote output
+    pub fn last_output(&self) -> Option<Instant> {
+        lock_or_recover(&self.parser).callbacks().last_output
+    }
+
+    /// When input (keys, pastes) was last written to the child
+    pub fn last_input(&self) -> Option<Instant> {
+        self.last_input
+    }
+
+    /// Number of BEL characters received since the last call
+    pub fn take_bells(&self) -> usize {
+        std::mem::take(&mut lock_or_recover(&self.parser).callbacks_mut().bells)
+    }
+
+    /// Start recording to the asciicast file `path`. The current screen is
+    /// written first, so the recording does not start from a blank terminal.
+    pub fn start_recording(&self, path: &Path, title: &str, record_input: bool) -> Result<()> {
+        let parser = lock_or_recover(&self.parser);
+        let screen = parser.screen();
+        let (rows, cols) = screen.size();
+        let mut rec = Recorder::create(path, cols, rows, title, record_input)?;
+        rec.output(&screen.contents_formatted())?;
+        *lock_or_recover(&self.recorder) = Some(rec);
+        Ok(())
+    }
+
+    /// Stop recording; returns the file written
+    pub fn stop_recording(&self) -> Option<Result<PathBuf>> {
+        let rec = lock_or_recover(&self.recorder).take()?;
+        Some(rec.finish())
+    }
+
+    /// Whether an asciicast recording is running
+    pub fn is_recording(&self) -> bool {
+        lock_or_recover(&self.recorder).is_some()
+    }
+
+    /// Whether any OSC 133 prompt mark was received
+    pub fn has_command_marks(&self) -> bool {
+        !lock_or_recover(&self.parser)
+            .callbacks()
+            .commands
+            .is_empty()
+    }
+
+    /// The whole buffer (as [`Self::extract_all_lines`]) and the commands
+    /// located in it from OSC 133 marks, oldest first. Empty without shell
+    /// integration.
+    pub fn command_blocks(&self) -> (Vec<String>, Vec<CommandBlock>) {
+        self.with_buffer_lines(|lines, commands| (lines.to_vec(), locate_commands(lines, commands)))
+    }
+
+    /// Only the commands of [`Self::command_blocks`]; cheap enough for
+    /// every frame, as the buffer is re-read only when its content changed
+    pub fn command_block_list(&self) -> Vec<CommandBlock> {
+        self.with_buffer_lines(locate_commands)
+    }
+
+    /// Run `f` on the whole buffer (as [`Self::extract_all_lines`]) and the
+    /// recorded commands. Reading the grid is the costly part, so the lines
+    /// are kept until [`Self::content_generation`] changes.
+    fn with_buffer_lines<R>(&self, f: impl FnOnce(&[String], &VecDeque<ShellCommand>) -> R) -> R {
+        let mut parser = lock_or_recover(&self.parser);
+        let generation = parser.callbacks().generation;
+        let mut cache = lock_or_recover(&self.line_cache);
+        if cache
+            .as_ref()
+            .is_none_or(|(cached, _)| *cached != generation)
+        {
+            *cache = Some((generation, Self::collect_buffer_lines(parser.screen_mut())));
+        }
+        let (_, lines) = cache.as_ref().expect("filled above");
+        f(lines, &parser.callbacks().commands)
+    }
+
     /// Set scrollback position by ratio (0.0 = top/max scrollback, 1.0 = bottom/current)
     pub fn set_scrollback_position(&self, ratio: f64) {
         let max_scrollback = 1000usize;
@@ -510,6 +1121,18 @@ impl PseudoTerminal {
     pub fn has_exited(&self) -> bool {
         self.exited.load(Ordering::SeqCst)
     }
+
+    /// Exit code/signal of the child, `None` while it is still running (or
+    /// not yet reaped right after the reader saw EOF).
+    pub fn exit_status(&self) -> Option<portable_pty::ExitStatus> {
+        let mut status = lock_or_recover(&self.exit_status);
+        if status.is_none() {
+            if let Ok(Some(s)) = lock_or_recover(&self.child).try_wait() {
+                *status = Some(s);
+            }
+        }
+        status.clone()
+    }
 }
 
 #[cfg(test)]
@@ -534,6 +1157,18 @@ mod tests {
         assert_eq!(got, expected);
     }
 
+    #[test]
+    fn collect_buffer_text_skips_wide_continuations() {
+        let mut parser = vt100::Parser::new(2, 20, 10);
+        parser.process("日本 code".as_bytes());
+        let screen = parser.screen_mut();
+        assert_eq!(
+            PseudoTerminal::collect_buffer_lines(screen)[0],
+            "日 本  code"
+        );
+        assert_eq!(PseudoTerminal::collect_buffer_text(screen)[0], "日本 code");
+    }
+
     // --- mouse-wheel forwarding ---
 
     #[test]
@@ -622,4 +1257,147 @@ mod tests {
         );
         assert_eq!(bytes, vec![0x1b, b'[', b'M', 0x61, 0xff, 0xff]);
     }
+
+    // --- OSC 8 hyperlinks ---
+
+    #[test]
+    fn osc8_hyperlinks_recorded_per_row() {
+        let mut parser = vt100::Parser::new_with_callbacks(3, 10, 100, PtyCallbacks::new());
+        parser.process(b"see \x1b]8;;https://a.b/c;d\x1b\\link\x1b]8;;\x1b\\.\r\n");
+        // Wraps onto the next row
+        parser.process(b"\x1b]8;id=1;file:///x\x07abcdefghijkl\x1b]8;;\x07");
+
+        let links: Vec<_> = parser.callbacks().hyperlinks.iter().cloned().collect();
+        assert_eq!(links.len(), 3);
+        assert_eq!((links[0].line, links[0].start, links[0].end), (0, 4, 8));
+        assert_eq!(links[0].uri, "https://a.b/c;d");
+        assert_eq!(links[0].text, "link");
+        assert_eq!((links[1].line, links[1].start, links[1].end), (1, 0, 10));
+        assert_eq!((links[2].line, links[2].start, links[2].end), (2, 0, 2));
+
+        // Overwritten text no longer matches the recorded span
+        parser.process(b"\x1b[1;5Hxxxx");
+        assert_ne!(cell_text(parser.screen_mut(), 0, 4, 8), links[0].text);
+    }
+
+    // --- OSC 7 / OSC 133 shell integration ---
+
+    #[test]
+    fn osc7_reports_working_directory() {
+        assert_eq!(
+            parse_osc7("file://host/home/me/my%20dir"),
+            Some(PathBuf::from("/home/me/my dir"))
+        );
+        assert_eq!(parse_osc7("file:///tmp"), Some(PathBuf::from("/tmp")));
+        assert_eq!(parse_osc7("http://host/tmp"), None);
+
+        let mut parser = vt100::Parser::new_with_callbacks(3, 20, 10, PtyCallbacks::new());
+        parser.process(b"\x1b]7;file://h/srv/app\x07");
+        let callbacks = parser.callbacks_mut();
+        assert_eq!(callbacks.cwd, Some(PathBuf::from("/srv/app")));
+        assert!(std::mem::take(&mut callbacks.cwd_changed));
+        // Same directory again: no change
+        parser.process(b"\x1b]7;file://h/srv/app\x07");
+        assert!(!parser.callbacks().cwd_changed);
+    }
+
+    #[test]
+    fn osc133_marks_delimit_commands() {
+        let mut parser = vt100::Parser::new_with_callbacks(6, 20, 100, PtyCallbacks::new());
+        let prompt = |cmd: &str| format!("\x1b]133;A\x07$ \x1b]133;B\x07{cmd}\r\n\x1b]133;C\x07");
+        parser.process(format!("{}out1\r\nout2\r\n\x1b]133;D;0\x07", prompt("ls")).as_bytes());
+        parser.process(format!("{}boom\r\n\x1b]133;D;2\x07", prompt("make")).as_bytes());
+        // Empty command line: D without C
+        parser.process(b"\x1b]133;A\x07$ \r\n\x1b]133;D\x07\x1b]133;A\x07$ ");
+
+        let lines = PseudoTerminal::collect_buffer_lines(parser.screen_mut());
+        let blocks = locate_commands(&lines, &parser.callbacks().commands);
+        let summary: Vec<_> = blocks
+            .iter()
+            .map(|b| (b.prompt, b.output, b.end, b.exit_code))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, Some(1), 3, Some(0)),
+                (3, Some(4), 5, Some(2)),
+                (5, None, 6, None),
+                (6, None, lines.len(), None),
+            ]
+        );
+        assert_eq!(blocks[1].input, Some((3, 2)));
+        assert!(blocks[1].duration.is_some() && !blocks[1].running);
+        assert_eq!((blocks[3].input, blocks[3].duration), (None, None));
+
+        // Output started without D: running until the next prompt
+        parser.process(b"\x1b]133;B\x07sleep 1\r\n\x1b]133;C\x07");
+        let running = |parser: &vt100::Parser<PtyCallbacks>| {
+            parser
+                .callbacks()
+                .commands
+                .back()
+                .map(|c| c.started.is_some() && c.finished.is_none())
+        };
+        assert_eq!(running(&parser), Some(true));
+        parser.process(b"\x1b]133;A\x07$ ");
+        assert_eq!(parser.callbacks().commands.len(), 5);
+        assert!(parser.callbacks().commands[3].finished.is_some());
+        assert_eq!(running(&parser), Some(false));
+    }
+
+    #[test]
+    fn commands_relocated_after_history_drops() {
+        let cmd = |prompt: usize, text: &str| ShellCommand {
+            prompt,
+            output: Some(prompt + 1),
+            exit_code: Some(0),
+            prompt_text: text.to_string(),
+            ..ShellCommand::default()
+        };
+        // Recorded at lines 4 and 7, since then 3 lines dropped out
+        let commands: VecDeque<_> = [cmd(4, "$ a"), cmd(7, "$ b")].into();
+        let lines: Vec<String> = ["x", "$ a", "1", "2", "$ b", "3"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let blocks = locate_commands(&lines, &commands);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].prompt, blocks[0].end), (1, 4));
+        assert_eq!((blocks[1].prompt, blocks[1].output), (4, Some(5)));
+    }
+
+    #[test]
+    fn bells_counted_outside_osc_terminators() {
+        let mut parser = vt100::Parser::new_with_callbacks(3, 20, 0, PtyCallbacks::new());
+        parser.process(b"\x1b]0;title\x07done\x07\x07");
+        assert_eq!(parser.callbacks().bells, 2);
+    }
+
+    // --- bracketed paste ---
+
+    #[test]
+    fn bracketed_paste_mode_tracked_from_decset_2004() {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        assert!(!parser.screen().bracketed_paste());
+        parser.process(b"\x1b[?2004h");
+        assert!(parser.screen().bracketed_paste());
+        parser.process(b"\x1b[?2004l");
+        assert!(!parser.screen().bracketed_paste());
+    }
+
+    #[test]
+    fn paste_bytes_wraps_only_when_enabled() {
+        assert_eq!(paste_bytes("a\r\nb", false), b"a\nb");
+        assert_eq!(paste_bytes("a\r\nb", true), b"\x1b[200~a\r\nb\x1b[201~");
+        // An embedded end marker must not close the paste early
+        assert_eq!(
+            paste_bytes("x\x1b[201~rm -rf\n", true),
+            b"\x1b[200~xrm -rf\n\x1b[201~"
+        );
+        // Nor one that appears once the inner marker is removed
+        assert_eq!(
+            paste_bytes("\x1b[20\x1b[201~1~rm -rf\n", true),
+            b"\x1b[200~rm -rf\n\x1b[201~"
+        );
+    }
 }
@@ -67,6 +67,30 @@ impl ClaudePermissionMode {
     }
 }
 
+/// What to do when a pane's process exits (`pty.restart.<pane>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Restart after every exit
+    #[default]
+    Always,
+    /// Restart only after a non-zero exit code or a signal
+    OnFailure,
+    /// Keep the pane stopped (Enter restarts manually)
+    Never,
+}
+
+impl RestartPolicy {
+    /// Whether an exit with the given success flag should trigger a restart
+    pub fn restarts_after(self, success: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnFailure => !success,
+            Self::Never => false,
+        }
+    }
+}
+
 /// Claude Code model selection for --model flag
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ClaudeModel {
@@ -110,6 +134,50 @@ impl ClaudeModel {
     }
 }
 
+/// How desktop notifications reach the host terminal
+/// (`claude.notifications.desktop`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DesktopNotification {
+    Off,
+    /// `OSC 9 ; message` (iTerm2, WezTerm, kitty, Windows Terminal)
+    #[default]
+    Osc9,
+    /// `OSC 777 ; notify ; title ; message` (foot, urxvt, Ghostty, VTE)
+    Osc777,
+}
+
+/// Something the user may want to hear about while working elsewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaudeEvent {
+    /// Output went idle after a busy stretch
+    Finished,
+    /// The Claude CLI rang the bell
+    Bell,
+    /// A permission prompt appeared
+    Permission,
+}
+
+impl ClaudeEvent {
+    /// Notification text
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Finished => "Claude finished",
+            Self::Bell => "Claude needs attention",
+            Self::Permission => "Claude asks for permission",
+        }
+    }
+
+    /// Badge in the Claude pane title
+    pub fn badge(self) -> &'static str {
+        match self {
+            Self::Finished => "✓ done",
+            Self::Bell => "● bell",
+            Self::Permission => "? input",
+        }
+    }
+}
+
 /// Claude Code reasoning effort level for --effort flag
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ClaudeEffort {
@@ -316,6 +384,138 @@ impl TerminalSelection {
     }
 }
 
+/// Selection shape in copy mode (`v`, `V`, Ctrl+V)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopySelectionKind {
+    #[default]
+    Char,
+    Line,
+    Block,
+}
+
+/// Where copy mode sends yanked text (`pty.copy_mode_target`, `t` toggles)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CopyTarget {
+    #[default]
+    Clipboard,
+    Claude,
+}
+
+/// Vim-style copy mode over a terminal pane's scrollback (Shift+F6, `Ctrl+B [`).
+/// Positions are (buffer line, column) with buffer lines counted from the
+/// oldest history line, as returned by `PseudoTerminal::extract_all_lines`.
+#[derive(Debug, Clone, Default)]
+pub struct CopyModeState {
+    /// Pane in copy mode; `None` = inactive
+    pub pane: Option<PaneId>,
+    pub cursor: (usize, usize),
+    /// Selection start; `None` = nothing selected
+    pub anchor: Option<(usize, usize)>,
+    pub kind: CopySelectionKind,
+    pub target: CopyTarget,
+    /// Last `/` search (query, regex, case-sensitive) for `n` / `N`
+    pub last_search: Option<(String, bool, bool)>,
+}
+
+impl CopyModeState {
+    pub fn open(&mut self, pane: PaneId, cursor: (usize, usize), target: CopyTarget) {
+        self.pane = Some(pane);
+        self.cursor = cursor;
+        self.anchor = None;
+        self.kind = CopySelectionKind::Char;
+        self.target = target;
+    }
+
+    pub fn close(&mut self) {
+        self.pane = None;
+        self.anchor = None;
+    }
+
+    /// Start a selection of `kind`, switch to it, or clear it when it is
+    /// already the active kind (like vim's visual modes)
+    pub fn toggle_selection(&mut self, kind: CopySelectionKind) {
+        if self.anchor.is_some() && self.kind == kind {
+            self.anchor = None;
+        } else {
+            self.anchor.get_or_insert(self.cursor);
+            self.kind = kind;
+        }
+    }
+
+    /// Whether buffer cell (line, col) is inside the selection
+    pub fn is_selected(&self, line: usize, col: usize) -> bool {
+        let Some(anchor) = self.anchor else {
+            return false;
+        };
+        let (start, end) = (anchor.min(self.cursor), anchor.max(self.cursor));
+        if line < start.0 || line > end.0 {
+            return false;
+        }
+        match self.kind {
+            CopySelectionKind::Line => true,
+            CopySelectionKind::Block => {
+                let (left, right) = (anchor.1.min(self.cursor.1), anchor.1.max(self.cursor.1));
+                col >= left && col <= right
+            }
+            CopySelectionKind::Char => {
+                (line > start.0 || col >= start.1) && (line < end.0 || col <= end.1)
+            }
+        }
+    }
+}
+
+/// Command-block navigation in a terminal pane (`Ctrl+B ↑`): one command
+/// of the OSC 133 marked history is selected at a time.
+#[derive(Debug, Clone, Default)]
+pub struct CommandBlockState {
+    /// Pane in block mode; `None` = inactive
+    pub pane: Option<PaneId>,
+    /// Prompt line (buffer line) of the selected block
+    pub prompt: usize,
+}
+
+impl CommandBlockState {
+    pub fn open(&mut self, pane: PaneId, prompt: usize) {
+        self.pane = Some(pane);
+        self.prompt = prompt;
+    }
+
+    pub fn close(&mut self) {
+        self.pane = None;
+    }
+
+    /// Index of the selected block in `blocks`: the one containing the
+    /// selected prompt line, the oldest when it scrolled out
+    pub fn selected(&self, blocks: &[crate::terminal::CommandBlock]) -> Option<usize> {
+        blocks
+            .iter()
+            .rposition(|b| b.prompt <= self.prompt)
+            .or((!blocks.is_empty()).then_some(0))
+    }
+}
+
+/// Target of a clickable link in a terminal pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    Url(String),
+    /// Existing file, optionally with a 1-based line
+    File {
+        path: std::path::PathBuf,
+        line: Option<usize>,
+    },
+}
+
+/// Link at a cell of a terminal pane (hover underline, Ctrl+click).
+/// `row` and the columns are relative to the pane's visible screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneLink {
+    pub row: u16,
+    pub start_col: u16,
+    pub end_col: u16,
+    pub target: LinkTarget,
+}
+
 /// Drag and drop state for file browser to terminal panes
 #[derive(Debug, Clone, Default)]
 pub struct DragState {
@@ -999,6 +1199,36 @@ impl SearchState {
     }
 }
 
+/// Scrollback search in a terminal pane (Ctrl+F, `Ctrl+B /` in the User Terminal)
+#[derive(Debug, Clone, Default)]
+pub struct TerminalSearchState {
+    /// Pane being searched; `None` = search bar closed
+    pub pane: Option<PaneId>,
+    /// Query and matches; match lines index the whole buffer (history first),
+    /// columns are terminal cells
+    pub search: SearchState,
+    /// Interpret the query as a regular expression
+    pub regex: bool,
+    /// Compile error of the current regex query
+    pub error: Option<String>,
+    /// `PseudoTerminal::content_generation` the matches were computed for
+    pub generation: u64,
+}
+
+impl TerminalSearchState {
+    pub fn open(&mut self, pane: PaneId) {
+        self.pane = Some(pane);
+        self.search.open();
+        self.error = None;
+    }
+
+    pub fn close(&mut self) {
+        self.pane = None;
+        self.search.close();
+        self.error = None;
+    }
+}
+
 /// Result of checking for remote changes
 #[derive(Debug, Clone)]
 pub enum GitRemoteCheckResult {
@@ -1027,10 +1257,22 @@ pub struct GitRemoteState {
 pub struct ExportChooserState {
     pub visible: bool,
     pub source_path: std::path::PathBuf,
-    pub selected: usize, // 0 = Markdown, 1 = PDF
+    pub selected: usize, // index into ExportFormat::ALL
     /// When true, source_path is a directory — triggers batch export instead of
     /// the filename input dialog.
     pub is_batch: bool,
+    /// Custom Typst templates found in the config dir (PDF only)
+    pub templates: Vec<String>,
+    /// 0 = built-in layout, n = `templates[n - 1]`
+    pub template: usize,
+    /// Batch only: merge the folder into one PDF book instead of one file each
+    pub book: bool,
+    /// Branding profiles from `document.profiles`
+    pub profiles: Vec<String>,
+    /// 0 = automatic (path rules), n = `profiles[n - 1]`
+    pub profile: usize,
+    /// Profile the path rules select for `source_path` (shown for "Auto")
+    pub auto_profile: Option<String>,
 }
 
 impl Default for ExportChooserState {
@@ -1040,10 +1282,118 @@ impl Default for ExportChooserState {
             source_path: std::path::PathBuf::new(),
             selected: 0,
             is_batch: false,
+            templates: Vec::new(),
+            template: 0,
+            book: false,
+            profiles: Vec::new(),
+            profile: 0,
+            auto_profile: None,
         }
     }
 }
 
+impl ExportChooserState {
+    /// Open the chooser for `source_path`, preselecting the configured default
+    /// template if it exists among the available templates.
+    pub fn open(
+        source_path: std::path::PathBuf,
+        is_batch: bool,
+        doc: &crate::config::DocumentConfig,
+    ) -> Self {
+        let templates = crate::browser::pdf_export::available_templates();
+        let template = templates
+            .iter()
+            .position(|t| *t == doc.template.default)
+            .map_or(0, |i| i + 1);
+        let auto_profile = doc.profile_for_path(&source_path).map(String::from);
+        Self {
+            visible: true,
+            source_path,
+            selected: 0,
+            is_batch,
+            templates,
+            template,
+            book: false,
+            profiles: doc.profile_names(),
+            profile: 0,
+            auto_profile,
+        }
+    }
+
+    /// Explicitly chosen profile (`None` = automatic via path rules)
+    pub fn selected_profile(&self) -> Option<String> {
+        self.profile
+            .checked_sub(1)
+            .and_then(|i| self.profiles.get(i))
+            .cloned()
+    }
+
+    /// Display name of the selected profile
+    pub fn profile_label(&self) -> String {
+        match self
+            .profile
+            .checked_sub(1)
+            .and_then(|i| self.profiles.get(i))
+        {
+            Some(name) => name.clone(),
+            None => match &self.auto_profile {
+                Some(name) => format!("Auto ({})", name),
+                None => "Auto".to_string(),
+            },
+        }
+    }
+
+    /// Cycle through automatic + named profiles
+    pub fn cycle_profile(&mut self, forward: bool) {
+        let count = self.profiles.len() + 1;
+        self.profile = if forward {
+            (self.profile + 1) % count
+        } else {
+            (self.profile + count - 1) % count
+        };
+    }
+
+    /// Selected custom template name (`None` = built-in)
+    pub fn selected_template(&self) -> Option<String> {
+        self.template
+            .checked_sub(1)
+            .and_then(|i| self.templates.get(i))
+            .cloned()
+    }
+
+    /// Display name of the selected template
+    pub fn template_label(&self) -> &str {
+        self.template
+            .checked_sub(1)
+            .and_then(|i| self.templates.get(i))
+            .map_or("Default", |s| s.as_str())
+    }
+
+    /// Toggle book mode (folder exports only); a book is always a PDF
+    pub fn toggle_book(&mut self) {
+        if !self.is_batch {
+            return;
+        }
+        self.book = !self.book;
+        if self.book {
+            self.selected = crate::browser::pdf_export::ExportFormat::ALL
+                .iter()
+                .position(|f| *f == crate::browser::pdf_export::ExportFormat::Pdf)
+                .unwrap_or(self.selected);
+        }
+    }
+
+    /// Cycle through built-in + custom templates
+    pub fn cycle_template(&mut self, forward: bool) {
+        let count = self.templates.len() + 1;
+        self.template = if forward {
+            (self.template + 1) % count
+        } else {
+            (self.template + count - 1) % count
+        };
+    }
+}
+
 /// Result of a batch (folder) export operation
 #[derive(Debug)]
 pub struct BatchExportResult {
@@ -1057,7 +1407,7 @@ pub struct BatchExportResult {
 /// Batch carries aggregate results from a folder export.
 #[derive(Debug)]
 pub enum ExportJobResult {
-    Single(Result<std::path::PathBuf, String>),
+    Single(Result<crate::browser::pdf_export::ExportOutput, String>),
     Batch(BatchExportResult),
 }
 
@@ -1125,4 +1475,50 @@ mod tests {
         assert_eq!(all.len(), 6);
         assert_eq!(all[0], ClaudeEffort::Unset);
     }
+
+    #[test]
+    fn test_export_chooser_template_cycle() {
+        let mut state = ExportChooserState {
+            templates: vec!["firma".to_string(), "intern".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(state.selected_template(), None);
+        assert_eq!(state.template_label(), "Default");
+        state.cycle_template(true);
+        assert_eq!(state.selected_template().as_deref(), Some("firma"));
+        state.cycle_template(false);
+        state.cycle_template(false);
+        assert_eq!(state.selected_template().as_deref(), Some("intern"));
+    }
+
+    #[test]
+    fn test_export_chooser_book_toggle() {
+        let mut state = ExportChooserState::default();
+        state.toggle_book();
+        assert!(!state.book, "single-file exports have no book mode");
+
+        state.is_batch = true;
+        state.toggle_book();
+        assert!(state.book);
+        assert_eq!(
+            crate::browser::pdf_export::ExportFormat::ALL[state.selected],
+            crate::browser::pdf_export::ExportFormat::Pdf
+        );
+    }
+
+    #[test]
+    fn test_export_chooser_profile_cycle() {
+        let mut state = ExportChooserState {
+            profiles: vec!["acme".to_string(), "intern".to_string()],
+            auto_profile: Some("acme".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(state.selected_profile(), None);
+        assert_eq!(state.profile_label(), "Auto (acme)");
+        state.cycle_profile(false);
+        assert_eq!(state.selected_profile().as_deref(), Some("intern"));
+        state.cycle_profile(true);
+        state.cycle_profile(true);
+        assert_eq!(state.profile_label(), "acme");
+    }
 }
@@ -0,0 +1,133 @@
+//! "Send to Claude" context menu (Ctrl+F4)
+//!
+//! Lists the context-injection actions; each one collects a piece of
+//! workbench state and pastes it into the Claude pane.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClaudeContext {
+    /// File open in the preview (or selected in the browser) as `@path`
+    CurrentFile,
+    /// Uncommitted changes as a fenced diff
+    GitDiff,
+    /// Most recent Terminal command block that contains an error
+    FailingCommand,
+    /// Files marked in the file browser (Space) as `@path` references
+    MarkedFiles,
+    /// Lines matching the preview search
+    SearchResults,
+}
+
+impl ClaudeContext {
+    pub const ALL: [ClaudeContext; 5] = [
+        ClaudeContext::CurrentFile,
+        ClaudeContext::GitDiff,
+        ClaudeContext::FailingCommand,
+        ClaudeContext::MarkedFiles,
+        ClaudeContext::SearchResults,
+    ];
+
+    pub fn key(self) -> char {
+        match self {
+            ClaudeContext::CurrentFile => 'f',
+            ClaudeContext::GitDiff => 'd',
+            ClaudeContext::FailingCommand => 'e',
+            ClaudeContext::MarkedFiles => 'm',
+            ClaudeContext::SearchResults => 's',
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ClaudeContext::CurrentFile => "Current file (@path)",
+            ClaudeContext::GitDiff => "Git diff",
+            ClaudeContext::FailingCommand => "Last failing command",
+            ClaudeContext::MarkedFiles => "Marked files (@paths)",
+            ClaudeContext::SearchResults => "Preview search results",
+        }
+    }
+
+    pub fn from_key(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|ctx| ctx.key() == c)
+    }
+}
+
+#[derive(Default)]
+pub struct ContextMenu {
+    pub visible: bool,
+    pub selected: usize,
+}
+
+impl ContextMenu {
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.selected = 0;
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % ClaudeContext::ALL.len();
+    }
+
+    pub fn prev(&mut self) {
+        let len = ClaudeContext::ALL.len();
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn action(&self) -> ClaudeContext {
+        ClaudeContext::ALL[self.selected]
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, menu: &ContextMenu) {
+    if !menu.visible {
+        return;
+    }
+
+    let width = 40u16.min(area.width);
+    let height = (ClaudeContext::ALL.len() + 2) as u16;
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + 2;
+    let popup_area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Send to Claude (Esc to close) ")
+        .style(Style::default().bg(Color::DarkGray));
+
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    for (i, ctx) in ClaudeContext::ALL.iter().enumerate() {
+        let style = if i == menu.selected {
+            Style::default()
+                .bg(Color::Blue)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let line = Line::from(vec![
+            Span::styled(
+                format!(" [{}] ", ctx.key()),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(ctx.label(), style),
+        ]);
+
+        let item_area = Rect::new(inner.x, inner.y + i as u16, inner.width, 1);
+        f.render_widget(
+            Paragraph::new(line).style(Style::default().bg(Color::DarkGray)),
+            item_area,
+        );
+    }
+}
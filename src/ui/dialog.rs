@@ -61,6 +61,30 @@ pub enum DialogAction {
     ExportMarkdown {
         source: std::path::PathBuf,
         format: crate::browser::pdf_export::ExportFormat,
+        /// Custom Typst template (PDF); `None` = built-in
+        template: Option<String>,
+        /// Branding profile; `None` = chosen by path rules
+        profile: Option<String>,
+    },
+    /// Restart the Claude PTY in `project` with `--resume <session_id>`
+    ResumeClaudeSession {
+        project: std::path::PathBuf,
+        session_id: String,
+    },
+    /// Paste a large payload into Claude after confirmation (size guard);
+    /// `clear_marks` unmarks the file browser selection once it is sent
+    SendToClaude {
+        text: String,
+        clear_marks: bool,
+    },
+    /// Save the scrollback of a terminal pane (value is the target file)
+    SaveScrollback {
+        pane: crate::types::PaneId,
+    },
+    /// Replace an existing file with the scrollback of a pane (after confirmation)
+    OverwriteScrollback {
+        pane: crate::types::PaneId,
+        path: std::path::PathBuf,
     },
 }
 
@@ -245,7 +269,8 @@ impl Dialog {
                 action:
                     DialogAction::GoToPath
                     | DialogAction::OpenMarkdownPreview
-                    | DialogAction::ExportMarkdown { .. },
+                    | DialogAction::ExportMarkdown { .. }
+                    | DialogAction::SaveScrollback { .. },
                 ..
             } => value.clone(),
             _ => return false,
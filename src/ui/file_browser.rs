@@ -9,14 +9,14 @@ use ratatui::{
     },
     Frame,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
 /// Format file modification date for display using local timezone.
 /// Validates timestamp range to avoid undefined behavior in libc calls.
-fn format_file_date(utc_secs: u64) -> String {
+pub(crate) fn format_file_date(utc_secs: u64) -> String {
     // Validate timestamp range to prevent UB in localtime_r/localtime_s.
     // Max safe value: 2038-01-19 on 32-bit, but we cap at year 9999 for sanity.
     const MAX_SAFE_TIMESTAMP: u64 = 253_402_300_799; // 9999-12-31 23:59:59 UTC
@@ -83,6 +83,8 @@ pub struct FileBrowserState {
     pub expanded_dirs: HashSet<PathBuf>,
     /// Previous directory for F7 toggle (back from ~/.claude)
     pub previous_dir: Option<PathBuf>,
+    /// Files marked with Space (multi-selection for "Send to Claude")
+    pub marked: BTreeSet<PathBuf>,
 }
 
 impl FileBrowserState {
@@ -101,6 +103,7 @@ impl FileBrowserState {
             show_hidden,
             expanded_dirs: HashSet::new(),
             previous_dir: None,
+            marked: BTreeSet::new(),
         };
         // Start with root expanded
         s.expanded_dirs.insert(root_dir);
@@ -250,6 +253,26 @@ impl FileBrowserState {
         self.list_state.select(Some(i));
     }
 
+    /// Mark/unmark the selected entry and move to the next one
+    pub fn toggle_mark(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        if entry.name == ".." {
+            return;
+        }
+        let path = entry.path.clone();
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+        self.down();
+    }
+
+    /// Marked paths that still exist, in path order
+    pub fn marked_files(&self) -> Vec<PathBuf> {
+        self.marked.iter().filter(|p| p.exists()).cloned().collect()
+    }
+
     /// Toggle expand/collapse for directories, return file path for files
     pub fn enter_selected(&mut self) -> Option<PathBuf> {
         if let Some(i) = self.list_state.selected() {
@@ -513,12 +536,24 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut FileBrowserState, is_focuse
             let status_symbol = entry.git_status.symbol();
             let status_style = style_for_git_status(entry.git_status);
 
+            // Marked entries (Space) get a check mark and bold yellow name
+            let (mark, name_style) = if state.marked.contains(&entry.path) {
+                (
+                    "✓",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", status_style)
+            };
+
             // Build the line with indent, tree icon, and name
             let line = Line::from(vec![
                 Span::styled(status_symbol, status_style),
-                Span::raw(" "),
+                Span::styled(mark, name_style),
                 Span::raw(indent),
-                Span::styled(format!("{}{}", tree_icon, entry.name), status_style),
+                Span::styled(format!("{}{}", tree_icon, entry.name), name_style),
             ]);
 
             ListItem::new(line)
@@ -602,11 +637,14 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut FileBrowserState, is_focuse
     };
 
     // Combine file info and git info
-    let info_text = if git_info_str.is_empty() {
+    let mut info_text = if git_info_str.is_empty() {
         file_info_text
     } else {
         format!("{} │ {}", file_info_text, git_info_str)
     };
+    if !state.marked.is_empty() {
+        info_text.push_str(&format!(" │ ✓ {} marked", state.marked.len()));
+    }
 
     let info = Paragraph::new(info_text).style(Style::default().fg(Color::DarkGray));
     f.render_widget(info, info_area);
@@ -666,6 +704,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toggle_mark_marks_and_advances() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut fb = browser_with_files(tmp.path(), 3);
+        let first_file = fb.entries.iter().position(|e| !e.is_dir).unwrap();
+        fb.list_state.select(Some(first_file));
+        let path = fb.entries[first_file].path.clone();
+
+        fb.toggle_mark();
+        assert_eq!(fb.marked_files(), vec![path.clone()]);
+        assert_eq!(fb.list_state.selected(), Some(first_file + 1));
+
+        fb.up();
+        fb.toggle_mark();
+        assert!(fb.marked_files().is_empty());
+
+        fb.marked.insert(tmp.path().join("gone.txt"));
+        assert!(fb.marked_files().is_empty(), "deleted files are skipped");
+    }
+
     #[test]
     fn refresh_clamps_offset_when_entries_shrink() {
         let tmp = tempfile::tempdir().unwrap();
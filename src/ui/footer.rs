@@ -61,6 +61,8 @@ pub struct Footer {
     pub preview_maximized: bool,
     /// Error flash for failed clipboard operations (3 s, red).
     pub clipboard_error: Option<String>,
+    /// Claude notification flash: finished or needs input (5 s, magenta).
+    pub notification: Option<String>,
     /// Persistent warning banner for missing clipboard helpers (10 s, yellow).
     pub clipboard_warning: Option<String>,
     /// One-time hint shown after Ctrl+V in the Claude pane during an SSH
@@ -131,6 +133,7 @@ impl Default for Footer {
             copy_flash_message: None,
             preview_maximized: false,
             clipboard_error: None,
+            notification: None,
             clipboard_warning: None,
             ssh_image_paste_hint: None,
             terminal_prefix_armed: false,
@@ -346,7 +349,7 @@ impl Widget for Footer {
                     Style::default().bg(Color::DarkGray).fg(Color::White),
                 ),
                 Span::styled(
-                    " ^B  1-6 Panes · ? Help · s Select · c Copy · ^B literal ",
+                    " ^B  1-6 Panes · ? Help · s Select · c Copy · / Search · [ Copy mode · ↑ Blocks · r Rec · w Save · ^B literal ",
                     Style::default()
                         .fg(Color::Black)
                         .bg(Color::Cyan)
@@ -417,6 +420,26 @@ impl Widget for Footer {
                     Style::default().bg(Color::DarkGray).fg(Color::White),
                 ),
             ]
+        } else if let Some(ref msg) = self.notification {
+            // Claude notification — magenta 🔔
+            use ratatui::style::Modifier;
+            vec![
+                Span::styled(
+                    format!(" {} │ ", datetime_text),
+                    Style::default().bg(Color::DarkGray).fg(Color::White),
+                ),
+                Span::styled(
+                    format!(" \u{1F514} {} ", msg),
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" │ v{} ", version),
+                    Style::default().bg(Color::DarkGray).fg(Color::White),
+                ),
+            ]
         } else if self.copy_flash {
             // Flash state for copy/export feedback
             use ratatui::style::Modifier;
@@ -172,6 +172,18 @@ fn help_content() -> Vec<Line<'static>> {
             Span::styled("  F7           ", Style::default().fg(Color::Cyan)),
             Span::raw("Toggle ~/.claude (press again to go back)"),
         ]),
+        Line::from(vec![
+            Span::styled("  Shift+F4     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Prompt library (expanded and sent to Claude)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+F4      ", Style::default().fg(Color::Cyan)),
+            Span::raw("Send to Claude: file, diff, failing cmd, marked files"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Shift+F7     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Claude history (search, Enter resumes session)"),
+        ]),
         Line::from(vec![
             Span::styled("  F12          ", Style::default().fg(Color::Cyan)),
             Span::raw("Toggle this Help"),
@@ -263,6 +275,10 @@ fn help_content() -> Vec<Line<'static>> {
             Span::styled("  .            ", Style::default().fg(Color::Cyan)),
             Span::raw("Toggle hidden files"),
         ]),
+        Line::from(vec![
+            Span::styled("  Space        ", Style::default().fg(Color::Cyan)),
+            Span::raw("Mark file (Ctrl+F4 → m sends marked files)"),
+        ]),
         Line::from(vec![
             Span::styled("  F9 → i       ", Style::default().fg(Color::Cyan)),
             Span::raw("Add to .gitignore (via File Menu)"),
@@ -371,6 +387,8 @@ fn help_content() -> Vec<Line<'static>> {
         Line::from(""),
         Line::from("  - Syntax highlighting for 500+ languages"),
         Line::from("  - Markdown rendering with formatting"),
+        Line::from("  - .cast recordings play back: Space pause, ←/→ ±5s,"),
+        Line::from("    +/- speed, Home restart"),
         Line::from(vec![
             Span::styled("  j/k, ↑/↓     ", Style::default().fg(Color::Cyan)),
             Span::raw("Scroll 1 line"),
@@ -549,6 +567,26 @@ fn help_content() -> Vec<Line<'static>> {
             Span::styled("  Ctrl+S       ", Style::default().fg(Color::Cyan)),
             Span::raw("Start Selection Mode"),
         ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+F       ", Style::default().fg(Color::Cyan)),
+            Span::raw("Search scrollback (Enter/↑ older, ↓ newer, ^R regex, Tab case)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Shift+F6     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Copy mode: hjkl/w/b/e, v/V/^V select, / search, y yank, t target"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Shift+F5     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Start/stop asciicast recording (.cast, plays in the Preview)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+↑       ", Style::default().fg(Color::Cyan)),
+            Span::raw("Command blocks (Terminal): ↑↓ jump, y/Y copy output/command, s/S → Claude"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+Click   ", Style::default().fg(Color::Cyan)),
+            Span::raw("Open URL in browser / file:line in Preview (hover underlines)"),
+        ]),
         Line::from(vec![
             Span::styled("  F9           ", Style::default().fg(Color::Cyan)),
             Span::raw("Copy last N lines to clipboard (default from config.yaml)"),
@@ -557,6 +595,10 @@ fn help_content() -> Vec<Line<'static>> {
             Span::styled("  Shift+F9     ", Style::default().fg(Color::Cyan)),
             Span::raw("Copy last N lines (interactive: enter custom line count)"),
         ]),
+        Line::from(vec![
+            Span::styled("  Alt+F9       ", Style::default().fg(Color::Cyan)),
+            Span::raw("Save scrollback to a file (.txt, .ansi with colors, .html)"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "  User Terminal (F6) — Prefix Key (Ctrl+B):",
@@ -569,7 +611,19 @@ fn help_content() -> Vec<Line<'static>> {
         ]),
         Line::from(vec![
             Span::styled("  Ctrl+B ?     ", Style::default().fg(Color::Cyan)),
-            Span::raw("Help  ·  Ctrl+B s Select  ·  Ctrl+B c Copy"),
+            Span::raw("Help  ·  Ctrl+B s Select  ·  Ctrl+B c Copy  ·  Ctrl+B / Search  ·  Ctrl+B [ Copy mode"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+B ↑     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Command blocks (shell integration, see Ctrl+↑)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+B r     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Start/stop recording (same as Shift+F5)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+B w     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Save scrollback to a file (same as Alt+F9)"),
         ]),
         Line::from(vec![
             Span::styled("  Ctrl+B Ctrl+B", Style::default().fg(Color::Cyan)),
@@ -721,6 +775,8 @@ fn help_content() -> Vec<Line<'static>> {
         Line::from("  - Layout percentages"),
         Line::from("  - File browser settings (hidden files, date)"),
         Line::from("  - Claude startup prefixes (optional)"),
+        Line::from("  - Claude notifications: finished, bell, permission"),
+        Line::from("    prompt (claude.notifications)"),
         Line::from(""),
         // Footer
         Line::from(Span::styled(
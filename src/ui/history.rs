@@ -0,0 +1,440 @@
+//! Claude conversation history browser (Shift+F7)
+//!
+//! Lists the Claude sessions of the current project, shows the selected
+//! transcript as a readable conversation and offers full-text search.
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+use crate::app::{JobState, PollOutcome};
+use crate::claude_history::{self, SessionSummary, Turn};
+
+#[derive(Default)]
+pub struct HistoryBrowser {
+    pub visible: bool,
+    /// Project whose sessions are listed (Claude is resumed there)
+    pub project: PathBuf,
+    pub sessions: Vec<SessionSummary>,
+    /// Lowercased transcript text per session, loaded on first search
+    search_text: Option<Vec<String>>,
+    /// Background load of `search_text`; until it arrives only the first
+    /// prompts are searched
+    search_job: JobState<Vec<String>>,
+    /// Indices into `sessions` matching the query
+    pub filtered: Vec<usize>,
+    pub list_state: ListState,
+    pub query: String,
+    /// Typing goes to the search field
+    pub searching: bool,
+    /// Parsed transcript of the selected session (session index, turns)
+    transcript: Option<(usize, Result<Vec<Turn>, String>)>,
+    pub scroll: u16,
+}
+
+impl HistoryBrowser {
+    pub fn open(&mut self, project: &Path) {
+        self.visible = true;
+        self.project = project.to_path_buf();
+        self.sessions = claude_history::list_sessions(project);
+        self.search_text = None;
+        self.search_job = JobState::Idle;
+        self.query.clear();
+        self.searching = false;
+        self.transcript = None;
+        self.update_filter();
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.sessions.clear();
+        self.search_text = None;
+        self.search_job = JobState::Idle;
+        self.filtered.clear();
+        self.transcript = None;
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.update_filter();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.update_filter();
+    }
+
+    /// Filter sessions by full-text match on prompt and transcript
+    fn update_filter(&mut self) {
+        let query = self.query.to_lowercase();
+        if !query.is_empty() && self.search_text.is_none() && !self.search_job.is_running() {
+            self.start_search_load();
+        }
+        let filtered: Vec<usize> = (0..self.sessions.len())
+            .filter(|&i| query.is_empty() || self.session_matches(i, &query))
+            .collect();
+        self.filtered = filtered;
+        self.list_state
+            .select((!self.filtered.is_empty()).then_some(0));
+        self.scroll = 0;
+    }
+
+    fn session_matches(&self, index: usize, query: &str) -> bool {
+        match &self.search_text {
+            Some(texts) => texts[index].contains(query),
+            None => self.sessions[index]
+                .first_prompt
+                .to_lowercase()
+                .contains(query),
+        }
+    }
+
+    /// Read every transcript on a worker thread (they can be large)
+    fn start_search_load(&mut self) {
+        let sessions: Vec<(String, PathBuf)> = self
+            .sessions
+            .iter()
+            .map(|s| (s.first_prompt.clone(), s.path.clone()))
+            .collect();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let texts = sessions
+                .iter()
+                .map(|(first_prompt, path)| {
+                    let turns = claude_history::load_transcript(path).unwrap_or_default();
+                    let mut text = first_prompt.to_lowercase();
+                    for turn in &turns {
+                        text.push('\n');
+                        text.push_str(&turn.text().to_lowercase());
+                    }
+                    text
+                })
+                .collect();
+            let _ = tx.send(texts);
+        });
+        self.search_job = JobState::running(rx);
+    }
+
+    /// Take the loaded transcript texts and search them
+    fn poll_search_load(&mut self) {
+        if let PollOutcome::Ready(texts) = self.search_job.poll() {
+            self.search_text = Some(texts);
+            self.update_filter();
+        }
+    }
+
+    /// True while the transcripts for full-text search are being read
+    pub fn is_loading(&self) -> bool {
+        self.search_job.is_running()
+    }
+
+    pub fn next(&mut self) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let i = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some((i + 1) % self.filtered.len()));
+        self.scroll = 0;
+    }
+
+    pub fn prev(&mut self) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let i = self.list_state.selected().unwrap_or(0);
+        let len = self.filtered.len();
+        self.list_state.select(Some((i + len - 1) % len));
+        self.scroll = 0;
+    }
+
+    pub fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_add(lines);
+    }
+
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Currently selected session
+    pub fn selected(&self) -> Option<&SessionSummary> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.filtered.get(i))
+            .map(|&i| &self.sessions[i])
+    }
+
+    /// Parse the selected transcript (cached until the selection changes)
+    fn load_selected(&mut self) {
+        let Some(index) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.filtered.get(i))
+        else {
+            self.transcript = None;
+            return;
+        };
+        let index = *index;
+        if self.transcript.as_ref().is_some_and(|(i, _)| *i == index) {
+            return;
+        }
+        let turns = claude_history::load_transcript(&self.sessions[index].path)
+            .map_err(|e| format!("{:#}", e));
+        self.transcript = Some((index, turns));
+    }
+}
+
+/// Readable conversation lines for `turns`; lines containing `query` are highlighted.
+fn transcript_lines(turns: &[Turn], query: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let text_lines = |lines: &mut Vec<Line<'static>>, text: &str, style: Style| {
+        for l in text.lines() {
+            lines.push(Line::styled(format!("  {}", l), style));
+        }
+    };
+    for turn in turns {
+        match turn {
+            Turn::User(text) => {
+                lines.push(Line::styled(
+                    "▶ You",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                text_lines(&mut lines, text, Style::default());
+            }
+            Turn::Assistant(text) => {
+                lines.push(Line::styled(
+                    "◀ Claude",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                text_lines(&mut lines, text, Style::default());
+            }
+            Turn::ToolCall {
+                name,
+                summary,
+                diff,
+            } => {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  ⚙ {} ", name),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(summary.clone(), Style::default().fg(Color::Yellow)),
+                ]));
+                for d in diff {
+                    let color = if d.starts_with('-') {
+                        Color::Red
+                    } else {
+                        Color::Green
+                    };
+                    lines.push(Line::styled(
+                        format!("    {}", d),
+                        Style::default().fg(color),
+                    ));
+                }
+                continue;
+            }
+            Turn::ToolResult { text, is_error } => {
+                let color = if *is_error {
+                    Color::Red
+                } else {
+                    Color::DarkGray
+                };
+                for l in text.lines() {
+                    lines.push(Line::styled(
+                        format!("    ↳ {}", l),
+                        Style::default().fg(color),
+                    ));
+                }
+                continue;
+            }
+        }
+        lines.push(Line::default());
+    }
+
+    if !query.is_empty() {
+        let query = query.to_lowercase();
+        for line in &mut lines {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            if text.to_lowercase().contains(&query) {
+                *line = line
+                    .clone()
+                    .patch_style(Style::default().bg(Color::Rgb(70, 70, 0)));
+            }
+        }
+    }
+    lines
+}
+
+pub fn render(f: &mut Frame, area: Rect, browser: &mut HistoryBrowser) {
+    if !browser.visible {
+        return;
+    }
+    browser.poll_search_load();
+    browser.load_selected();
+
+    let width = (area.width * 90 / 100).max(60).min(area.width);
+    let height = (area.height * 85 / 100).max(15).min(area.height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup_area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, popup_area);
+
+    let project_name = browser
+        .project
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Claude History: {} ", project_name))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+
+    // Search line
+    let mut search = vec![
+        Span::styled("/ ", Style::default().fg(Color::Cyan)),
+        Span::styled(browser.query.clone(), Style::default().fg(Color::Yellow)),
+    ];
+    if browser.searching {
+        search.push(Span::styled(
+            "_",
+            Style::default().add_modifier(Modifier::SLOW_BLINK),
+        ));
+    }
+    search.push(Span::styled(
+        format!(
+            "   {}/{} sessions",
+            browser.filtered.len(),
+            browser.sessions.len()
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    if browser.is_loading() {
+        search.push(Span::styled(
+            "   searching transcripts…",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(search)), rows[0]);
+
+    let cols =
+        Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)]).split(rows[1]);
+
+    // Session list: date + first prompt
+    let items: Vec<ListItem> = browser
+        .filtered
+        .iter()
+        .map(|&i| {
+            let s = &browser.sessions[i];
+            let date = s
+                .modified
+                .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| crate::ui::file_browser::format_file_date(d.as_secs()))
+                .unwrap_or_default();
+            ListItem::new(vec![
+                Line::styled(date, Style::default().fg(Color::DarkGray)),
+                Line::from(s.first_prompt.clone()),
+            ])
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::RIGHT))
+        .highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_stateful_widget(list, cols[0], &mut browser.list_state);
+
+    // Transcript
+    let transcript_area = Rect::new(
+        cols[1].x + 1,
+        cols[1].y,
+        cols[1].width.saturating_sub(1),
+        cols[1].height,
+    );
+    let lines = match &browser.transcript {
+        Some((_, Ok(turns))) => transcript_lines(turns, &browser.query),
+        Some((_, Err(e))) => vec![Line::styled(e.clone(), Style::default().fg(Color::Red))],
+        None if browser.sessions.is_empty() => vec![Line::styled(
+            "No Claude sessions for this project",
+            Style::default().fg(Color::DarkGray),
+        )],
+        None => Vec::new(),
+    };
+    f.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((browser.scroll, 0)),
+        transcript_area,
+    );
+
+    let hint = if browser.searching {
+        "Type to search │ Enter/Esc: Done"
+    } else {
+        "j/k: Session │ PgUp/PgDn: Scroll │ /: Search │ Enter: Resume │ Esc: Close"
+    };
+    f.render_widget(
+        Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+        rows[2],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_lines_layout_and_highlight() {
+        let turns = vec![
+            Turn::User("Fix it".to_string()),
+            Turn::ToolCall {
+                name: "Edit".to_string(),
+                summary: "a.rs".to_string(),
+                diff: vec!["-old".to_string(), "+new".to_string()],
+            },
+            Turn::Assistant("Done".to_string()),
+        ];
+        let lines = transcript_lines(&turns, "new");
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "▶ You",
+                "  Fix it",
+                "",
+                "  ⚙ Edit a.rs",
+                "    -old",
+                "    +new",
+                "◀ Claude",
+                "  Done",
+                ""
+            ]
+        );
+        assert_eq!(lines[5].style.bg, Some(Color::Rgb(70, 70, 0)));
+        assert_eq!(lines[4].style.bg, None);
+    }
+}
@@ -1,15 +1,18 @@
 pub mod about;
 pub mod claude_startup;
+pub mod context_menu;
 pub mod dialog;
 pub mod drag_ghost;
 pub mod file_browser;
 pub mod footer;
 pub mod fuzzy_finder;
 pub mod help;
+pub mod history;
 pub mod layout;
 pub mod menu;
 pub mod permission_mode;
 pub mod preview;
+pub mod prompt_picker;
 pub mod settings;
 pub mod syntax;
 pub mod terminal_pane;
@@ -10,8 +10,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tui_textarea::TextArea;
 
+use crate::asciicast::{Cast, CastPlayer};
 use crate::types::{EditorMode, SearchMode, SearchState};
 use crate::ui::syntax::SyntaxManager;
+use crate::ui::terminal_pane::TerminalWidget;
 
 /// Check if a file is a Markdown file based on extension
 fn is_markdown_file(path: &Path) -> bool {
@@ -58,6 +60,9 @@ pub struct PreviewState {
 
     // Cached horizontal scrollbar area from last render (for accurate mouse hit testing)
     pub cached_h_scrollbar_area: Option<Rect>,
+
+    // Player for asciicast recordings (.cast)
+    pub cast: Option<CastPlayer>,
 }
 
 impl Default for PreviewState {
@@ -80,6 +85,7 @@ impl Default for PreviewState {
             selection_start: None,
             last_modified: None,
             cached_h_scrollbar_area: None,
+            cast: None,
         }
     }
 }
@@ -99,6 +105,7 @@ impl PreviewState {
         self.scroll = 0;
         self.horizontal_scroll = 0;
         self.is_markdown = is_markdown_file(&path);
+        self.cast = None;
 
         // Set syntax name (Markdown or detected syntax)
         if self.is_markdown {
@@ -114,6 +121,14 @@ impl PreviewState {
             // Store file modification time for auto-refresh
             self.last_modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
 
+            // Recordings play in the pane; their JSON stays available for editing
+            if path.extension().is_some_and(|e| e == "cast") {
+                if let Ok(cast) = Cast::parse(&content) {
+                    self.cast = Some(CastPlayer::new(cast));
+                    self.syntax_name = Some("asciicast".to_string());
+                }
+            }
+
             // Use tui-markdown for markdown files, syntect for others
             if self.is_markdown {
                 // Catch potential panics in tui-markdown library (known bug in 0.3.7)
@@ -984,6 +999,11 @@ pub fn render(
             // Render editor status bar
             render_edit_shortcuts(f, shortcut_area);
         }
+        EditorMode::ReadOnly if state.cast.is_some() => {
+            if let Some(player) = state.cast.as_mut() {
+                render_cast_player(f, area, block, player);
+            }
+        }
         EditorMode::ReadOnly => {
             let total_lines = state.highlighted_lines.len();
             let scroll_offset = state.scroll as usize;
@@ -1130,6 +1150,53 @@ pub fn render(
     }
 }
 
+/// asciicast player: the recorded screen and a status bar below it
+fn render_cast_player(f: &mut Frame, area: Rect, block: Block, player: &mut CastPlayer) {
+    player.tick(std::time::Instant::now());
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.height < 2 {
+        return;
+    }
+    let screen_area = Rect {
+        height: inner.height - 1,
+        ..inner
+    };
+    let bar_area = Rect {
+        y: inner.y + inner.height - 1,
+        height: 1,
+        ..inner
+    };
+    f.render_widget(TerminalWidget::new(player.screen()), screen_area);
+
+    let time = |secs: f64| format!("{}:{:02}", secs as u64 / 60, secs as u64 % 60);
+    let bar_style = Style::default().bg(Color::DarkGray);
+    let line = Line::from(vec![
+        Span::styled(
+            if player.playing {
+                " ▶ PLAY "
+            } else {
+                " ⏸ PAUSE "
+            },
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                " {} / {}  {}×  Space pause · ←/→ ±5s · +/- speed · Home restart",
+                time(player.position()),
+                time(player.duration()),
+                player.speed
+            ),
+            bar_style.fg(Color::White),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(line).style(bar_style), bar_area);
+}
+
 /// Render the search/replace bar at the bottom of the preview area
 fn render_search_bar(f: &mut Frame, area: Rect, state: &PreviewState) {
     let is_replace_mode = state.search.mode == SearchMode::Replace;
@@ -0,0 +1,203 @@
+//! Prompt library picker (Shift+F4)
+//!
+//! Filters the configured prompt templates by name/description and shows the
+//! raw template of the selected entry. Enter expands it and sends it to Claude.
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::PromptTemplate;
+
+#[derive(Default)]
+pub struct PromptPicker {
+    pub visible: bool,
+    pub query: String,
+    pub prompts: Vec<PromptTemplate>,
+    /// Indices into `prompts` matching the query
+    pub filtered: Vec<usize>,
+    pub list_state: ListState,
+}
+
+impl PromptPicker {
+    pub fn open(&mut self, prompts: Vec<PromptTemplate>) {
+        self.visible = true;
+        self.query.clear();
+        self.prompts = prompts;
+        self.update_filter();
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.query.clear();
+        self.prompts.clear();
+        self.filtered.clear();
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.update_filter();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.update_filter();
+    }
+
+    fn update_filter(&mut self) {
+        let query = self.query.to_lowercase();
+        self.filtered = self
+            .prompts
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| {
+                p.name.to_lowercase().contains(&query)
+                    || p.description.to_lowercase().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.list_state
+            .select((!self.filtered.is_empty()).then_some(0));
+    }
+
+    pub fn next(&mut self) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let i = self.list_state.selected().unwrap_or(0);
+        self.list_state.select(Some((i + 1) % self.filtered.len()));
+    }
+
+    pub fn prev(&mut self) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let i = self.list_state.selected().unwrap_or(0);
+        let len = self.filtered.len();
+        self.list_state.select(Some((i + len - 1) % len));
+    }
+
+    pub fn selected(&self) -> Option<&PromptTemplate> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.filtered.get(i))
+            .map(|&i| &self.prompts[i])
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, picker: &mut PromptPicker) {
+    if !picker.visible {
+        return;
+    }
+
+    let width = (area.width * 70 / 100).clamp(40.min(area.width), 90);
+    let height = (area.height * 70 / 100).clamp(12.min(area.height), 30);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let popup_area = Rect::new(x, y, width, height);
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Prompt Library (Shift+F4) ")
+        .style(Style::default().bg(Color::Black).fg(Color::White));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(6),
+        Constraint::Length(1),
+    ])
+    .split(inner);
+
+    let input_line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::styled(&picker.query, Style::default().fg(Color::Yellow)),
+        Span::styled(
+            "_",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::SLOW_BLINK),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(input_line), rows[0]);
+
+    let items: Vec<ListItem> = picker
+        .filtered
+        .iter()
+        .map(|&i| {
+            let p = &picker.prompts[i];
+            ListItem::new(Line::from(vec![
+                Span::raw(p.name.clone()),
+                Span::styled(
+                    format!("  {}", p.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, rows[1], &mut picker.list_state);
+
+    // Raw template of the selected prompt
+    let template = picker
+        .selected()
+        .map(|p| p.template.clone())
+        .unwrap_or_else(|| "No prompts — add them under claude.prompts in config.yaml".into());
+    f.render_widget(
+        Paragraph::new(template)
+            .wrap(Wrap { trim: false })
+            .style(Style::default().fg(Color::Gray))
+            .block(Block::default().borders(Borders::TOP)),
+        rows[2],
+    );
+
+    f.render_widget(
+        Paragraph::new("↑/↓: Select │ Enter: Send to Claude │ Esc: Close")
+            .style(Style::default().fg(Color::DarkGray)),
+        rows[3],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_by_name_and_description() {
+        let prompt = |name: &str, description: &str| PromptTemplate {
+            name: name.to_string(),
+            description: description.to_string(),
+            template: String::new(),
+        };
+        let mut picker = PromptPicker::default();
+        picker.open(vec![
+            prompt("Review changes", "uncommitted diff"),
+            prompt("Explain file", "preview"),
+        ]);
+        assert_eq!(picker.filtered, vec![0, 1]);
+
+        picker.push_char('d');
+        picker.push_char('i');
+        picker.push_char('f');
+        assert_eq!(picker.filtered, vec![0]);
+        assert_eq!(picker.selected().unwrap().name, "Review changes");
+
+        picker.push_char('x');
+        assert!(picker.selected().is_none());
+    }
+}
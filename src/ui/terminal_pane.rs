@@ -1,5 +1,8 @@
 use crate::app::App;
-use crate::types::PaneId;
+use crate::terminal::CommandBlock;
+use crate::types::{
+    CopyModeState, CopySelectionKind, CopyTarget, PaneId, SearchState, TerminalSearchState,
+};
 use ratatui::prelude::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -18,12 +21,32 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
     // Clear area before rendering to prevent stale content from previous frames
     f.render_widget(ratatui::widgets::Clear, area);
 
-    let title = match pane_id {
+    let mut title = Line::from(match pane_id {
         PaneId::Claude => " Claude Code ",
         PaneId::LazyGit => " LazyGit ",
         PaneId::Terminal => " Terminal ",
         _ => " Unknown ",
-    };
+    });
+    // Unseen Claude notification
+    if let Some(event) = app.claude_badge.filter(|_| pane_id == PaneId::Claude) {
+        title.push_span(Span::styled(
+            format!("[{}] ", event.badge()),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if app
+        .terminals
+        .get(&pane_id)
+        .is_some_and(|pty| pty.is_recording())
+    {
+        title.push_span(Span::styled(
+            "● REC ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
 
     let is_focused = app.active_pane == pane_id;
 
@@ -105,16 +128,40 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
 
     if let Some(pty) = app.terminals.get(&pane_id) {
         // Check if PTY process has exited
-        if pty.has_exited() && !app.config.pty.auto_restart {
-            // Show "exited" message with restart hint
-            let exit_lines: Vec<Line> = vec![
+        if pty.has_exited() {
+            // Show exit status with restart state / hint
+            let exit_status = pty.exit_status();
+            let status_color = match &exit_status {
+                Some(status) if !status.success() => Color::Red,
+                _ => Color::Gray,
+            };
+            let status = exit_status
+                .map(|status| format!("Process exited: {}", status))
+                .unwrap_or_else(|| "Process exited".to_string());
+            let mut exit_lines: Vec<Line> = vec![
                 Line::from(""),
                 Line::from(vec![
                     Span::styled("⚠ ", Style::default().fg(Color::Yellow)),
-                    Span::styled("Process exited", Style::default().fg(Color::Gray)),
+                    Span::styled(status, Style::default().fg(status_color)),
                 ]),
                 Line::from(""),
-                Line::from(vec![
+            ];
+            if let Some(delay) = app.pending_restart(pane_id) {
+                exit_lines.push(Line::from(Span::styled(
+                    format!("Restarting in {}s…", delay.as_secs_f32().ceil() as u64),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            } else {
+                if let Some(count) = app.restart_gave_up(pane_id) {
+                    exit_lines.push(Line::from(Span::styled(
+                        format!(
+                            "Crash loop: gave up after {} restarts in {}s",
+                            count, app.config.pty.restart.window_secs
+                        ),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                exit_lines.push(Line::from(vec![
                     Span::styled("Press ", Style::default().fg(Color::DarkGray)),
                     Span::styled(
                         "Enter",
@@ -123,8 +170,8 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(" to restart", Style::default().fg(Color::DarkGray)),
-                ]),
-            ];
+                ]));
+            }
             let exit_paragraph = Paragraph::new(exit_lines)
                 .style(Style::default())
                 .wrap(Wrap { trim: false });
@@ -132,11 +179,38 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
             return;
         }
 
-        let parser = pty
+        // Link under the mouse pointer (resolved once per change, not per frame)
+        let link = app
+            .hovered_link(pane_id)
+            .map(|l| (l.row as usize, l.start_col as usize, l.end_col as usize));
+
+        // Commands recorded by shell integration (gutter markers, block mode)
+        let blocks = if pty.has_command_marks() {
+            pty.command_block_list()
+        } else {
+            Vec::new()
+        };
+        let selected_block = if app.block_mode.pane == Some(pane_id) {
+            app.block_mode.selected(&blocks)
+        } else {
+            None
+        };
+
+        let mut parser = pty
             .parser
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let history = crate::terminal::history_depth(parser.screen_mut());
         let screen = parser.screen();
+        // Buffer line shown in the first row (search / copy mode coordinates)
+        let top = history.saturating_sub(screen.scrollback());
+
+        // Scrollback search hits on the visible rows
+        let search_hits = if app.terminal_search.pane == Some(pane_id) {
+            visible_search_hits(&app.terminal_search.search, top, screen.size().0 as usize)
+        } else {
+            Vec::new()
+        };
 
         // Get selection range - keyboard selection is line-based, mouse selection is char-based
         let selection_range = if selection_active {
@@ -155,8 +229,20 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
         TerminalWidget::new(screen)
             .with_selection(selection_range)
             .with_char_selection(char_selection)
+            .with_search_hits(search_hits)
+            .with_link(link)
+            .with_copy_mode((app.copy_mode.pane == Some(pane_id)).then_some((&app.copy_mode, top)))
             .render(inner_area, f.buffer_mut());
 
+        render_block_gutter(
+            f.buffer_mut(),
+            area,
+            inner_area,
+            &blocks,
+            top,
+            selected_block,
+        );
+
         // Scrollbar
         let scrollback = screen.scrollback();
         // Assuming max history 1000 as configured.
@@ -179,23 +265,262 @@ pub fn render(f: &mut Frame, area: Rect, pane_id: PaneId, app: &App) {
             }), // Inside border
             &mut scrollbar_state,
         );
+
+        if app.terminal_search.pane == Some(pane_id) {
+            render_search_bar(f, inner_area, &app.terminal_search);
+        } else if app.copy_mode.pane == Some(pane_id) {
+            render_copy_mode_bar(
+                f,
+                inner_area,
+                &app.copy_mode,
+                history + screen.size().0 as usize,
+            );
+        } else if let Some(index) = selected_block {
+            render_block_mode_bar(f, inner_area, &blocks, index);
+        }
+    }
+}
+
+/// Status marker of a command: running, succeeded, failed, finished
+/// without an exit code; `None` while the prompt waits for input
+fn block_status(block: &CommandBlock) -> Option<(&'static str, Color)> {
+    match (block.running, block.exit_code) {
+        (true, _) => Some(("◌", Color::Yellow)),
+        (false, Some(0)) => Some(("●", Color::Green)),
+        (false, Some(_)) => Some(("●", Color::Red)),
+        (false, None) if block.output.is_some() => Some(("○", Color::Gray)),
+        _ => None,
+    }
+}
+
+/// Command-block gutter on the left border: the status marker on each
+/// prompt row and a bar along the block selected in block mode
+fn render_block_gutter(
+    buf: &mut Buffer,
+    area: Rect,
+    inner: Rect,
+    blocks: &[CommandBlock],
+    top: usize,
+    selected: Option<usize>,
+) {
+    let bottom = top + inner.height as usize;
+    for (i, block) in blocks.iter().enumerate() {
+        if block.end <= top || block.prompt >= bottom {
+            continue;
+        }
+        let is_selected = selected == Some(i);
+        for line in block.prompt.max(top)..block.end.min(bottom) {
+            let marker = if line == block.prompt {
+                block_status(block)
+                    .map(|(symbol, color)| (if is_selected { "▶" } else { symbol }, color))
+            } else {
+                is_selected.then_some(("┃", Color::Cyan))
+            };
+            let Some((symbol, color)) = marker else {
+                continue;
+            };
+            let y = inner.y + (line - top) as u16;
+            if let Some(cell) = buf.cell_mut((area.x, y)) {
+                cell.set_symbol(symbol)
+                    .set_style(Style::default().fg(color));
+            }
+        }
+    }
+}
+
+/// Block-mode status line: position, exit code, duration and the keys
+fn render_block_mode_bar(f: &mut Frame, area: Rect, blocks: &[CommandBlock], index: usize) {
+    if area.height == 0 {
+        return;
+    }
+    let bar_area = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+    let bar_style = Style::default().bg(Color::DarkGray);
+    let block = &blocks[index];
+
+    let (status, color) = match (block.running, block.exit_code) {
+        (true, _) => ("running".to_string(), Color::Yellow),
+        (false, Some(0)) => ("exit 0".to_string(), Color::Green),
+        (false, Some(code)) => (format!("exit {}", code), Color::Red),
+        (false, None) => ("no exit code".to_string(), Color::Gray),
+    };
+    let duration = block
+        .duration
+        .map(|d| format!(" · {:.1}s", d.as_secs_f64()))
+        .unwrap_or_default();
+    let line = Line::from(vec![
+        Span::styled(
+            " BLOCK ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {}/{} ", index + 1, blocks.len()),
+            bar_style.fg(Color::White),
+        ),
+        Span::styled(status, bar_style.fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!(
+                "{}  ↑↓ jump · y output · Y command · s/S → Claude · q quit",
+                duration
+            ),
+            bar_style.fg(Color::White),
+        ),
+    ]);
+
+    f.render_widget(ratatui::widgets::Clear, bar_area);
+    f.render_widget(Paragraph::new(line).style(bar_style), bar_area);
+}
+
+/// Copy-mode status line at the bottom of the pane: mode, position, target
+fn render_copy_mode_bar(f: &mut Frame, area: Rect, state: &CopyModeState, total_lines: usize) {
+    if area.height == 0 {
+        return;
     }
+    let bar_area = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+    let bar_style = Style::default().bg(Color::DarkGray);
+
+    let mode = match (state.anchor, state.kind) {
+        (None, _) => " COPY ",
+        (Some(_), CopySelectionKind::Char) => " VISUAL ",
+        (Some(_), CopySelectionKind::Line) => " VISUAL LINE ",
+        (Some(_), CopySelectionKind::Block) => " VISUAL BLOCK ",
+    };
+    let target = match state.target {
+        CopyTarget::Clipboard => "Clipboard",
+        CopyTarget::Claude => "Claude",
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            mode,
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                " {}/{}:{}  y → {} (t) · v/V/^V select · / search · q quit",
+                state.cursor.0 + 1,
+                total_lines,
+                state.cursor.1 + 1,
+                target
+            ),
+            bar_style.fg(Color::White),
+        ),
+    ]);
+
+    f.render_widget(ratatui::widgets::Clear, bar_area);
+    f.render_widget(Paragraph::new(line).style(bar_style), bar_area);
+}
+
+/// Search hits on screen rows as `(row, start_col, end_col, is_current)`.
+/// `top` is the buffer line shown in row 0.
+fn visible_search_hits(
+    search: &SearchState,
+    top: usize,
+    rows: usize,
+) -> Vec<(usize, usize, usize, bool)> {
+    search
+        .matches
+        .iter()
+        .enumerate()
+        .filter(|(_, (line, _, _))| *line >= top && *line < top + rows)
+        .map(|(i, (line, start, end))| (line - top, *start, *end, i == search.current_match))
+        .collect()
 }
 
-struct TerminalWidget<'a> {
+/// One-line search bar at the bottom of the pane: query, hit counter, flags
+fn render_search_bar(f: &mut Frame, area: Rect, state: &TerminalSearchState) {
+    if area.height == 0 {
+        return;
+    }
+    let bar_area = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+    let bar_style = Style::default().bg(Color::DarkGray);
+
+    let search = &state.search;
+    let info = if let Some(err) = &state.error {
+        format!(" [{}]", err.lines().last().unwrap_or("invalid regex"))
+    } else if search.matches.is_empty() {
+        if search.query.is_empty() {
+            String::new()
+        } else {
+            " [No matches]".to_string()
+        }
+    } else {
+        format!(" [{}/{}]", search.current_match + 1, search.matches.len())
+    };
+
+    let chars: Vec<char> = search.query.chars().collect();
+    let cursor = search.query_cursor.min(chars.len());
+    let before: String = chars[..cursor].iter().collect();
+    let at: String = chars.get(cursor).map_or(" ".to_string(), |c| c.to_string());
+    let after: String = chars.iter().skip(cursor + 1).collect();
+
+    let query_style = bar_style.fg(Color::Yellow);
+    let line = Line::from(vec![
+        Span::styled(
+            if state.regex { "re/" } else { "/" },
+            bar_style.fg(Color::Cyan),
+        ),
+        Span::styled(before, query_style),
+        Span::styled(at, Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(after, query_style),
+        Span::styled(info, bar_style.fg(Color::Gray)),
+        Span::styled(
+            format!(
+                " {} {}",
+                if search.case_sensitive {
+                    "[Aa]"
+                } else {
+                    "[aa]"
+                },
+                if state.regex { "[.*]" } else { "[..]" }
+            ),
+            bar_style.fg(Color::Gray),
+        ),
+    ]);
+
+    f.render_widget(ratatui::widgets::Clear, bar_area);
+    f.render_widget(Paragraph::new(line).style(bar_style), bar_area);
+}
+
+pub(crate) struct TerminalWidget<'a> {
     screen: &'a vt100::Screen,
     /// Line-based selection range (for keyboard selection mode)
     selection_range: Option<(usize, usize)>,
     /// Character-level selection: ((start_row, start_col), (end_row, end_col))
     char_selection: Option<((usize, usize), (usize, usize))>,
+    /// Scrollback search hits: (row, start_col, end_col, is_current)
+    search_hits: Vec<(usize, usize, usize, bool)>,
+    /// Copy mode state and the buffer line shown in row 0
+    copy_mode: Option<(&'a CopyModeState, usize)>,
+    /// Hovered link: (row, start_col, end_col), drawn underlined
+    link: Option<(usize, usize, usize)>,
 }
 
 impl<'a> TerminalWidget<'a> {
-    fn new(screen: &'a vt100::Screen) -> Self {
+    pub(crate) fn new(screen: &'a vt100::Screen) -> Self {
         Self {
             screen,
             selection_range: None,
             char_selection: None,
+            search_hits: Vec::new(),
+            copy_mode: None,
+            link: None,
         }
     }
 
@@ -209,6 +534,29 @@ impl<'a> TerminalWidget<'a> {
         self
     }
 
+    fn with_search_hits(mut self, hits: Vec<(usize, usize, usize, bool)>) -> Self {
+        self.search_hits = hits;
+        self
+    }
+
+    fn with_copy_mode(mut self, copy_mode: Option<(&'a CopyModeState, usize)>) -> Self {
+        self.copy_mode = copy_mode;
+        self
+    }
+
+    fn with_link(mut self, link: Option<(usize, usize, usize)>) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Search hit covering cell (row, col): Some(is_current)
+    fn search_hit(&self, row: usize, col: usize) -> Option<bool> {
+        self.search_hits
+            .iter()
+            .find(|(r, start, end, _)| *r == row && col >= *start && col < *end)
+            .map(|(_, _, _, current)| *current)
+    }
+
     /// Check if a specific cell (row, col) is within the character-level selection
     fn is_char_selected(&self, row: usize, col: usize) -> bool {
         let Some(((start_row, start_col), (end_row, end_col))) = self.char_selection else {
@@ -290,6 +638,20 @@ impl Widget for TerminalWidget<'_> {
                         } else if row_selected {
                             // Keyboard selection: DarkGray background
                             style = style.bg(Color::DarkGray);
+                        } else if let Some((copy, top)) = self.copy_mode {
+                            let pos = (top + row_idx, col_idx);
+                            if copy.cursor == pos {
+                                style = style.bg(Color::Magenta).fg(Color::White);
+                            } else if copy.is_selected(pos.0, pos.1) {
+                                style = style.bg(Color::LightBlue).fg(Color::Black);
+                            } else if let Some(current) = self.search_hit(row_idx, col_idx) {
+                                let bg = if current { Color::Cyan } else { Color::Yellow };
+                                style = style.bg(bg).fg(Color::Black);
+                            }
+                        } else if let Some(current) = self.search_hit(row_idx, col_idx) {
+                            // Scrollback search: current hit cyan, others yellow
+                            let bg = if current { Color::Cyan } else { Color::Yellow };
+                            style = style.bg(bg).fg(Color::Black);
                         }
 
                         // Attributes (Bold, Italic, etc.)
@@ -302,7 +664,10 @@ impl Widget for TerminalWidget<'_> {
                         if cell.inverse() {
                             style = style.add_modifier(ratatui::style::Modifier::REVERSED);
                         }
-                        if cell.underline() {
+                        let in_link = self.link.is_some_and(|(row, start, end)| {
+                            row == row_idx && col_idx >= start && col_idx < end
+                        });
+                        if cell.underline() || in_link {
                             style = style.add_modifier(ratatui::style::Modifier::UNDERLINED);
                         }
 
@@ -316,8 +681,8 @@ impl Widget for TerminalWidget<'_> {
             }
         }
 
-        // Draw cursor
-        if !self.screen.hide_cursor() {
+        // Draw cursor (copy mode draws its own)
+        if !self.screen.hide_cursor() && self.copy_mode.is_none() {
             let (cr, cc) = self.screen.cursor_position();
             let cx = area.x + cc;
             let cy = area.y + cr;
@@ -1,7 +1,7 @@
 //! CLI integration tests for the claude-workbench binary.
 //!
 //! These tests exercise the binary's non-interactive entry points
-//! (`--help`, `--version`) to verify that argument parsing and basic
+//! (`--help`, `--version`, `export`) to verify that argument parsing and basic
 //! startup wiring remain intact.
 
 use std::process::Command;
@@ -104,3 +104,98 @@ fn update_to_flag_not_present_in_release_build() {
         stderr
     );
 }
+
+#[test]
+fn export_subcommand_writes_files_and_reports_results() {
+    let tmp = tempfile::tempdir().unwrap();
+    let docs = tmp.path().join("docs");
+    std::fs::create_dir(&docs).unwrap();
+    std::fs::write(docs.join("a.md"), "# A").unwrap();
+    std::fs::write(docs.join("b.md"), "# B").unwrap();
+    let out = tmp.path().join("out");
+
+    let output = Command::new(workbench_binary())
+        .current_dir(tmp.path())
+        .args(["export", "docs", "--format", "html", "--out", "out"])
+        .output()
+        .expect("failed to invoke claude-workbench export");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "export should exit 0\nstdout: {}",
+        stdout
+    );
+    assert!(out.join("a.html").is_file());
+    assert!(out.join("b.html").is_file());
+    assert!(
+        stdout.contains("2 exported, 0 failed"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn export_subcommand_fails_for_missing_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("ok.md"), "# Ok").unwrap();
+
+    let output = Command::new(workbench_binary())
+        .current_dir(tmp.path())
+        .args(["export", "ok.md", "missing.md", "-f", "md", "-o", "out"])
+        .output()
+        .expect("failed to invoke claude-workbench export");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "stdout: {}", stdout);
+    assert!(tmp.path().join("out/ok.md").is_file());
+    assert!(
+        stdout.contains("missing.md: file not found"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn export_subcommand_rejects_colliding_outputs() {
+    let tmp = tempfile::tempdir().unwrap();
+    for dir in ["a", "b"] {
+        std::fs::create_dir(tmp.path().join(dir)).unwrap();
+        std::fs::write(tmp.path().join(dir).join("README.md"), "# Readme").unwrap();
+    }
+
+    let output = Command::new(workbench_binary())
+        .current_dir(tmp.path())
+        .args(["export", "a", "b", "-f", "html", "-o", "out"])
+        .output()
+        .expect("failed to invoke claude-workbench export");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("would both be written to"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!tmp.path().join("out").exists());
+}
+
+#[test]
+fn export_subcommand_rejects_template_for_other_formats() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("doc.md"), "# Doc").unwrap();
+
+    let output = Command::new(workbench_binary())
+        .current_dir(tmp.path())
+        .args(["export", "doc.md", "-f", "html", "--template", "firma"])
+        .output()
+        .expect("failed to invoke claude-workbench export");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("--template only applies to PDF"),
+        "stderr: {}",
+        stderr
+    );
+}